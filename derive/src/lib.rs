@@ -0,0 +1,193 @@
+//! # phenopacket-builder-derive
+//!
+//! Derive macros for [`phenopacket-builder`](https://docs.rs/phenopacket-builder).
+//!
+//! Use the macros through the `derive` feature of `phenopacket-builder`.
+
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, Data, DeriveInput, Error, Fields, Ident, Path, Type};
+
+/// Generate a typestate builder for a struct with named fields.
+///
+/// The builder has one typestate parameter per field marked with `#[builder(required)]`,
+/// and implements `Build` once all required fields have been set, and `TryBuild` regardless.
+/// The optional fields that have not been set are filled with their default values.
+///
+/// The struct attribute `#[builder(...)]` accepts:
+///
+/// - `name = AgeBuilder`: the name of the builder, `{Struct}Builder` by default.
+/// - `target = path::to::Type`: the type to build, the struct itself by default.
+///   The target must have the same fields as the struct.
+///   `Buildable` is only implemented for the struct itself, not for the target.
+#[proc_macro_derive(Buildable, attributes(builder))]
+pub fn derive_buildable(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+struct BuilderField {
+    name: Ident,
+    ty: Type,
+    required: bool,
+}
+
+fn expand(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let krate = quote!(::phenopacket_builder);
+
+    let mut name = format_ident!("{}Builder", input.ident);
+    let mut target: Option<Path> = None;
+    for attr in input.attrs.iter().filter(|a| a.path().is_ident("builder")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("name") {
+                name = meta.value()?.parse()?;
+                Ok(())
+            } else if meta.path.is_ident("target") {
+                target = Some(meta.value()?.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error("expected `name` or `target`"))
+            }
+        })?;
+    }
+
+    let Data::Struct(data) = &input.data else {
+        return Err(Error::new(
+            Span::call_site(),
+            "Buildable can only be derived for structs",
+        ));
+    };
+    let Fields::Named(named) = &data.fields else {
+        return Err(Error::new(
+            Span::call_site(),
+            "Buildable can only be derived for structs with named fields",
+        ));
+    };
+
+    let mut fields = vec![];
+    for field in &named.named {
+        let mut required = false;
+        for attr in field.attrs.iter().filter(|a| a.path().is_ident("builder")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("required") {
+                    required = true;
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `required`"))
+                }
+            })?;
+        }
+        fields.push(BuilderField {
+            name: field.ident.clone().expect("the fields are named"),
+            ty: field.ty.clone(),
+            required,
+        });
+    }
+
+    let required: Vec<_> = fields.iter().filter(|f| f.required).collect();
+    let params: Vec<_> = (0..required.len()).map(|i| format_ident!("T{i}")).collect();
+
+    let field_names: Vec<_> = fields.iter().map(|f| &f.name).collect();
+    let field_types: Vec<_> = fields.iter().map(|f| &f.ty).collect();
+
+    let required_setters =
+        required.iter().enumerate().map(|(i, field)| {
+            let field_name = &field.name;
+            let ty = &field.ty;
+            let impl_params = params
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| *j != i)
+                .map(|(_, p)| p);
+            let state =
+                |marker: proc_macro2::TokenStream| {
+                    let args = params.iter().enumerate().map(move |(j, p)| {
+                        if j == i {
+                            marker.clone()
+                        } else {
+                            quote!(#p)
+                        }
+                    });
+                    quote!(#name<#(#args),*>)
+                };
+            let unset = state(quote!(#krate::Unset));
+            let set = state(quote!(#krate::Set));
+            let others: Vec<_> = field_names.iter().filter(|n| **n != field_name).collect();
+            quote! {
+                impl<#(#impl_params),*> #unset {
+                    pub fn #field_name(self, #field_name: impl Into<#ty>) -> #set {
+                        #name {
+                            #field_name: Some(#field_name.into()),
+                            #(#others: self.#others,)*
+                            data: ::std::marker::PhantomData,
+                        }
+                    }
+                }
+            }
+        });
+
+    let optional_setters = fields.iter().filter(|f| !f.required).map(|field| {
+        let field_name = &field.name;
+        let ty = &field.ty;
+        quote! {
+            pub fn #field_name(mut self, #field_name: impl Into<#ty>) -> Self {
+                self.#field_name = Some(#field_name.into());
+                self
+            }
+        }
+    });
+
+    let values = fields.iter().map(|field| {
+        let field_name = &field.name;
+        let missing = field_name.to_string();
+        if field.required {
+            quote!(#field_name: self.#field_name.ok_or(#krate::BuildError::MissingField(#missing))?)
+        } else {
+            quote!(#field_name: self.#field_name.unwrap_or_default())
+        }
+    });
+
+    let struct_name = &input.ident;
+    let buildable = target.is_none().then(|| {
+        quote! {
+            impl #krate::Buildable for #struct_name {
+                type Builder = #name;
+            }
+        }
+    });
+    let built = target.map_or_else(|| quote!(#struct_name), |target| quote!(#target));
+    let all_set = params.iter().map(|_| quote!(#krate::Set));
+
+    Ok(quote! {
+        #[derive(Debug, Default, Clone, PartialEq)]
+        pub struct #name<#(#params = #krate::Unset),*> {
+            #(#field_names: Option<#field_types>,)*
+            data: ::std::marker::PhantomData<(#(#params,)*)>,
+        }
+
+        #(#required_setters)*
+
+        impl<#(#params),*> #name<#(#params),*> {
+            #(#optional_setters)*
+        }
+
+        #buildable
+
+        impl<#(#params),*> #krate::TryBuild<#built> for #name<#(#params),*> {
+            fn try_build(self) -> Result<#built, #krate::BuildError> {
+                Ok(#built {
+                    #(#values,)*
+                })
+            }
+        }
+
+        impl #krate::Build<#built> for #name<#(#all_set),*> {
+            fn build(self) -> #built {
+                #krate::TryBuild::try_build(self).unwrap_or_else(|e| panic!("{e}"))
+            }
+        }
+    })
+}
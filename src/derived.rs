@@ -0,0 +1,24 @@
+//! Builders generated with `#[derive(Buildable)]`.
+//!
+//! # Example
+//!
+//! ```
+//! use phenopacket_builder::Build;
+//! use phenopacket_builder::derived::AgeBuilder;
+//! use phenopackets::schema::v2::core::Age;
+//!
+//! let age: Age = AgeBuilder::default().iso8601duration("P3Y4M").build();
+//!
+//! assert_eq!(&age.iso8601duration, "P3Y4M");
+//! ```
+
+use crate::Buildable;
+
+/// The fields of [`phenopackets::schema::v2::core::Age`].
+#[derive(Buildable)]
+#[builder(name = AgeBuilder, target = phenopackets::schema::v2::core::Age)]
+#[allow(dead_code)]
+struct Age {
+    #[builder(required)]
+    iso8601duration: String,
+}
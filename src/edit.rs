@@ -0,0 +1,169 @@
+//! In-place edits of assembled phenopackets.
+
+use crate::v2::ResourceBuilder;
+use crate::visit::{visit_curies, visit_term_curies};
+use crate::Build;
+use phenopackets::schema::v2::core::{File, Resource};
+use phenopackets::schema::v2::Phenopacket;
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+/// Change the subject id from `old` to `new`, along with all references to the subject.
+///
+/// The references are the biosample `individual_id`s,
+/// the `subject_or_biosample_id`s of the genomic interpretations,
+/// and the keys of the file `individual_to_file_identifiers`.
+///
+/// # Example
+///
+/// ```
+/// use phenopacket_builder::edit::rename_subject;
+/// use phenopackets::schema::v2::core::{Biosample, Individual};
+/// use phenopackets::schema::v2::Phenopacket;
+///
+/// let mut pp = Phenopacket {
+///     subject: Some(Individual { id: "John Doe".into(), ..Default::default() }),
+///     biosamples: vec![Biosample { individual_id: "John Doe".into(), ..Default::default() }],
+///     ..Default::default()
+/// };
+///
+/// rename_subject(&mut pp, "John Doe", "individual-1");
+///
+/// assert_eq!(&pp.subject.unwrap().id, "individual-1");
+/// assert_eq!(&pp.biosamples[0].individual_id, "individual-1");
+/// ```
+pub fn rename_subject(phenopacket: &mut Phenopacket, old: &str, new: &str) {
+    let rename = |id: &mut String| {
+        if id == old {
+            *id = new.to_string();
+        }
+    };
+
+    if let Some(subject) = phenopacket.subject.as_mut() {
+        rename(&mut subject.id);
+    }
+    for biosample in phenopacket.biosamples.iter_mut() {
+        rename(&mut biosample.individual_id);
+        rename_file_identifiers(&mut biosample.files, old, new);
+    }
+    for diagnosis in phenopacket
+        .interpretations
+        .iter_mut()
+        .filter_map(|interpretation| interpretation.diagnosis.as_mut())
+    {
+        for genomic_interpretation in diagnosis.genomic_interpretations.iter_mut() {
+            rename(&mut genomic_interpretation.subject_or_biosample_id);
+        }
+    }
+    rename_file_identifiers(&mut phenopacket.files, old, new);
+}
+
+fn rename_file_identifiers(files: &mut [File], old: &str, new: &str) {
+    for file in files {
+        if let Some(identifier) = file.individual_to_file_identifiers.remove(old) {
+            file.individual_to_file_identifiers
+                .insert(new.to_string(), identifier);
+        }
+    }
+}
+
+/// Remove the metadata resources whose namespace prefix is used by no CURIE of the phenopacket.
+///
+/// The CURIEs are the ids of the ontology classes, the gene descriptors,
+/// and the external references. The prefixes are compared case-sensitively.
+///
+/// # Example
+///
+/// ```
+/// use phenopacket_builder::edit::prune_unused_resources;
+/// use phenopacket_builder::{oc, Build, Buildable};
+/// use phenopackets::schema::v2::core::{MetaData, PhenotypicFeature, Resource};
+/// use phenopackets::schema::v2::Phenopacket;
+///
+/// let mut pp = Phenopacket {
+///     phenotypic_features: vec![PhenotypicFeature {
+///         r#type: Some(oc("HP:0001250", "Seizure")),
+///         ..Default::default()
+///     }],
+///     meta_data: Some(MetaData {
+///         resources: vec![
+///             Resource::builder().hpo("2024-04-26").build(),
+///             Resource::builder().mondo("2024-06-04").build(),
+///         ],
+///         ..Default::default()
+///     }),
+///     ..Default::default()
+/// };
+///
+/// prune_unused_resources(&mut pp);
+///
+/// let resources = pp.meta_data.unwrap().resources;
+/// assert_eq!(resources.len(), 1);
+/// assert_eq!(&resources[0].namespace_prefix, "HP");
+/// ```
+pub fn prune_unused_resources(phenopacket: &mut Phenopacket) {
+    let mut prefixes = HashSet::new();
+    visit_curies(phenopacket, |_, id| {
+        if let Some((prefix, _)) = id.split_once(':') {
+            prefixes.insert(prefix.to_string());
+        }
+    });
+
+    if let Some(meta_data) = phenopacket.meta_data.as_mut() {
+        meta_data
+            .resources
+            .retain(|resource| prefixes.contains(&resource.namespace_prefix));
+    }
+}
+
+/// The resources found by [`auto_resources`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AutoResources {
+    /// The preset resources for the prefixes used in the phenopacket, ordered by prefix.
+    pub resources: Vec<Resource>,
+    /// The prefixes used in the phenopacket that have no preset resource, ordered by prefix.
+    pub unknown_prefixes: Vec<String>,
+}
+
+/// Get the preset resources for the prefixes of the ontology class and gene ids
+/// used in the phenopacket, with the `versions` keyed by the prefix, such as `HP`.
+///
+/// The resources of the prefixes missing from `versions` get an empty version.
+/// The prefixes without a preset are reported in [`AutoResources::unknown_prefixes`].
+///
+/// # Example
+///
+/// ```
+/// use phenopacket_builder::edit::auto_resources;
+/// use phenopacket_builder::observed_feature;
+/// use phenopackets::schema::v2::Phenopacket;
+/// use std::collections::HashMap;
+///
+/// let pp = Phenopacket {
+///     phenotypic_features: vec![observed_feature("HP:0001250", "Seizure")],
+///     ..Default::default()
+/// };
+///
+/// let found = auto_resources(&pp, &HashMap::from([("HP", "2024-08-13")]));
+///
+/// assert_eq!(&found.resources[0].namespace_prefix, "HP");
+/// assert_eq!(&found.resources[0].version, "2024-08-13");
+/// assert!(found.unknown_prefixes.is_empty());
+/// ```
+pub fn auto_resources(phenopacket: &Phenopacket, versions: &HashMap<&str, &str>) -> AutoResources {
+    let mut prefixes = BTreeSet::new();
+    visit_term_curies(phenopacket, |_, id| {
+        if let Some((prefix, _)) = id.split_once(':') {
+            prefixes.insert(prefix.to_string());
+        }
+    });
+
+    let mut found = AutoResources::default();
+    for prefix in prefixes {
+        let version = versions.get(prefix.as_str()).copied().unwrap_or_default();
+        match ResourceBuilder::from_prefix(&prefix, version) {
+            Some(preset) => found.resources.push(preset.build()),
+            None => found.unknown_prefixes.push(prefix),
+        }
+    }
+    found
+}
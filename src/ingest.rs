@@ -0,0 +1,108 @@
+//! Helpers for ingesting tabular data, such as spreadsheets, into phenopacket building blocks.
+
+use crate::v2::IntoTimeElement;
+use crate::{oc, Build, Buildable};
+use phenopackets::schema::v2::core::{Age, Measurement, Quantity, Value};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+/// A row with a quantitative measurement, such as a lab value.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct MeasurementRow {
+    pub assay_id: String,
+    pub assay_label: String,
+    pub value: f64,
+    pub unit_id: String,
+    pub unit_label: String,
+    /// The time of the observation, either an ISO8601 timestamp, such as `2021-11-03`,
+    /// or the age of the subject as an ISO8601 duration, such as `P3Y4M`.
+    pub time_iso8601: Option<String>,
+}
+
+/// An error returned by [`measurements_from_rows`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct MeasurementRowError {
+    /// The index of the offending row.
+    pub row: usize,
+    /// The time of the observation that is not a valid ISO8601 timestamp or duration.
+    pub time_iso8601: String,
+}
+
+impl Display for MeasurementRowError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "row {}: {} is not an ISO8601 timestamp or duration",
+            self.row, self.time_iso8601
+        )
+    }
+}
+
+impl Error for MeasurementRowError {}
+
+/// Map each row to a quantitative measurement, observed at the time of the row, if any.
+///
+/// # Errors
+///
+/// Returns an error for the first row whose time is neither an ISO8601 timestamp
+/// nor an ISO8601 duration.
+///
+/// # Example
+///
+/// ```
+/// use phenopacket_builder::ingest::{measurements_from_rows, MeasurementRow};
+///
+/// let rows = vec![MeasurementRow {
+///     assay_id: "LOINC:2345-7".into(),
+///     assay_label: "Glucose [Mass/volume] in Serum or Plasma".into(),
+///     value: 110.,
+///     unit_id: "UCUM:mg/dL".into(),
+///     unit_label: "mg/dL".into(),
+///     time_iso8601: Some("2021-11-03".into()),
+/// }];
+///
+/// let measurements = measurements_from_rows(rows).expect("well formatted rows");
+///
+/// assert_eq!(&measurements[0].assay.as_ref().unwrap().id, "LOINC:2345-7");
+/// assert!(measurements[0].time_observed.is_some());
+/// ```
+pub fn measurements_from_rows(
+    rows: impl IntoIterator<Item = MeasurementRow>,
+) -> Result<Vec<Measurement>, MeasurementRowError> {
+    rows.into_iter()
+        .enumerate()
+        .map(|(i, row)| {
+            let quantity: Quantity = Quantity::builder()
+                .unit(oc(row.unit_id, row.unit_label))
+                .value(row.value)
+                .build();
+            let measurement = Measurement::builder()
+                .assay(oc(row.assay_id, row.assay_label))
+                .value(Value::builder().quantity(quantity));
+            let measurement = match row.time_iso8601 {
+                Some(time) if time.starts_with('P') => {
+                    let age: Age = Age::builder()
+                        .try_iso8601duration(&time)
+                        .map_err(|_| MeasurementRowError {
+                            row: i,
+                            time_iso8601: time.clone(),
+                        })?
+                        .build();
+                    measurement.time_observed(age.into_time_element())
+                }
+                Some(time) => {
+                    let timestamp: prost_types::Timestamp = prost_types::Timestamp::builder()
+                        .iso8601timestamp(&time)
+                        .map_err(|_| MeasurementRowError {
+                            row: i,
+                            time_iso8601: time.clone(),
+                        })?
+                        .build();
+                    measurement.time_observed(timestamp.into_time_element())
+                }
+                None => measurement,
+            };
+            Ok(measurement.build())
+        })
+        .collect()
+}
@@ -0,0 +1,615 @@
+//! Reading and writing phenopackets.
+
+use phenopackets::schema::v2::core::{
+    Biosample, Disease, File, Individual, Interpretation, Measurement, MedicalAction, MetaData,
+    OntologyClass, PhenotypicFeature, Procedure, TimeElement,
+};
+use phenopackets::schema::v2::Phenopacket;
+use prost::Message;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{Display, Formatter};
+use std::io::{Read, Write};
+
+/// An error that occurred while reading a phenopacket.
+#[derive(Debug)]
+pub enum ParseError {
+    /// The input is not valid Phenopacket Schema JSON.
+    #[cfg(feature = "json")]
+    Json(serde_json::Error),
+    /// The field at the `path`, such as `subject.sex`, has an invalid value.
+    #[cfg(feature = "json")]
+    InvalidField {
+        path: String,
+        source: serde_json::Error,
+    },
+    /// The input is not valid Phenopacket Schema YAML.
+    #[cfg(feature = "yaml")]
+    Yaml(serde_norway::Error),
+    /// The input is not a valid Protobuf encoded phenopacket.
+    Protobuf(prost::DecodeError),
+    /// The input could not be read.
+    Io(std::io::Error),
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            #[cfg(feature = "json")]
+            ParseError::Json(e) => write!(f, "invalid phenopacket JSON: {e}"),
+            #[cfg(feature = "json")]
+            ParseError::InvalidField { path, source } => {
+                write!(f, "invalid phenopacket JSON at {path}: {source}")
+            }
+            #[cfg(feature = "yaml")]
+            ParseError::Yaml(e) => write!(f, "invalid phenopacket YAML: {e}"),
+            ParseError::Protobuf(e) => write!(f, "invalid phenopacket Protobuf: {e}"),
+            ParseError::Io(e) => write!(f, "could not read phenopacket: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            #[cfg(feature = "json")]
+            ParseError::Json(e) | ParseError::InvalidField { source: e, .. } => Some(e),
+            #[cfg(feature = "yaml")]
+            ParseError::Yaml(e) => Some(e),
+            ParseError::Protobuf(e) => Some(e),
+            ParseError::Io(e) => Some(e),
+        }
+    }
+}
+
+#[cfg(feature = "json")]
+impl From<serde_json::Error> for ParseError {
+    fn from(value: serde_json::Error) -> Self {
+        ParseError::Json(value)
+    }
+}
+
+#[cfg(feature = "yaml")]
+impl From<serde_norway::Error> for ParseError {
+    fn from(value: serde_norway::Error) -> Self {
+        ParseError::Yaml(value)
+    }
+}
+
+impl From<prost::DecodeError> for ParseError {
+    fn from(value: prost::DecodeError) -> Self {
+        ParseError::Protobuf(value)
+    }
+}
+
+impl From<std::io::Error> for ParseError {
+    fn from(value: std::io::Error) -> Self {
+        ParseError::Io(value)
+    }
+}
+
+/// An error that occurred while writing a phenopacket.
+#[derive(Debug)]
+pub enum WriteError {
+    /// The phenopacket could not be serialized into JSON or written to the output.
+    #[cfg(feature = "json")]
+    Json(serde_json::Error),
+    /// The phenopacket could not be serialized into YAML.
+    #[cfg(feature = "yaml")]
+    Yaml(serde_norway::Error),
+    /// The phenopacket could not be written to the output.
+    Io(std::io::Error),
+}
+
+impl Display for WriteError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            #[cfg(feature = "json")]
+            WriteError::Json(e) => write!(f, "could not write phenopacket JSON: {e}"),
+            #[cfg(feature = "yaml")]
+            WriteError::Yaml(e) => write!(f, "could not write phenopacket YAML: {e}"),
+            WriteError::Io(e) => write!(f, "could not write phenopacket: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for WriteError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            #[cfg(feature = "json")]
+            WriteError::Json(e) => Some(e),
+            #[cfg(feature = "yaml")]
+            WriteError::Yaml(e) => Some(e),
+            WriteError::Io(e) => Some(e),
+        }
+    }
+}
+
+#[cfg(feature = "json")]
+impl From<serde_json::Error> for WriteError {
+    fn from(value: serde_json::Error) -> Self {
+        WriteError::Json(value)
+    }
+}
+
+#[cfg(feature = "yaml")]
+impl From<serde_norway::Error> for WriteError {
+    fn from(value: serde_norway::Error) -> Self {
+        WriteError::Yaml(value)
+    }
+}
+
+impl From<std::io::Error> for WriteError {
+    fn from(value: std::io::Error) -> Self {
+        WriteError::Io(value)
+    }
+}
+
+/// Write the phenopacket in the Protobuf binary format, such as into a `.pb` file.
+///
+/// # Example
+///
+/// ```
+/// use phenopacket_builder::io::{read_pb, write_pb};
+/// use phenopackets::schema::v2::Phenopacket;
+///
+/// let pp = Phenopacket { id: "pp-id".into(), ..Default::default() };
+/// let mut buffer = Vec::new();
+///
+/// write_pb(&pp, &mut buffer).expect("writing into a vector should not fail");
+///
+/// assert_eq!(read_pb(buffer.as_slice()).unwrap(), pp);
+/// ```
+pub fn write_pb<W: Write>(phenopacket: &Phenopacket, mut writer: W) -> Result<(), WriteError> {
+    Ok(writer.write_all(&to_bytes(phenopacket))?)
+}
+
+/// Read a phenopacket in the Protobuf binary format, such as from a `.pb` file.
+pub fn read_pb<R: Read>(mut reader: R) -> Result<Phenopacket, ParseError> {
+    let mut buffer = Vec::new();
+    reader.read_to_end(&mut buffer)?;
+    from_bytes(&buffer)
+}
+
+/// Encode the phenopacket in the Protobuf binary format.
+pub fn to_bytes(phenopacket: &Phenopacket) -> Vec<u8> {
+    phenopacket.encode_to_vec()
+}
+
+/// Encode the phenopacket in the Protobuf binary format,
+/// writing the entries of the map fields in the order of their keys.
+///
+/// Prost writes the map fields, i.e. the individual identifiers and the attributes of a [`File`],
+/// in the arbitrary order of a `HashMap`, so [`to_bytes`] may encode equal phenopackets
+/// into different bytes. Together with
+/// [`PhenopacketBuilder::deterministic`](crate::v2::PhenopacketBuilder::deterministic),
+/// this encodes equivalent phenopackets into identical bytes.
+/// The bytes decode with [`from_bytes`] as usual.
+///
+/// # Example
+///
+/// ```
+/// use phenopacket_builder::io::{from_bytes, to_deterministic_bytes};
+/// use phenopackets::schema::v2::core::File;
+/// use phenopackets::schema::v2::Phenopacket;
+///
+/// let file = |attributes: &[(&str, &str)]| File {
+///     uri: "file:///data/genomes/proband.vcf.gz".into(),
+///     file_attributes: attributes.iter().map(|&(k, v)| (k.into(), v.into())).collect(),
+///     ..Default::default()
+/// };
+/// let pp = |file| Phenopacket { id: "pp-id".into(), files: vec![file], ..Default::default() };
+///
+/// let first = pp(file(&[("fileFormat", "vcf"), ("genomeAssembly", "GRCh38")]));
+/// let second = pp(file(&[("genomeAssembly", "GRCh38"), ("fileFormat", "vcf")]));
+///
+/// assert_eq!(to_deterministic_bytes(&first), to_deterministic_bytes(&second));
+/// assert_eq!(from_bytes(&to_deterministic_bytes(&first)).unwrap(), first);
+/// ```
+pub fn to_deterministic_bytes(phenopacket: &Phenopacket) -> Vec<u8> {
+    SortedPhenopacket::from(phenopacket).encode_to_vec()
+}
+
+/// Decode a phenopacket from the Protobuf binary format.
+pub fn from_bytes(bytes: &[u8]) -> Result<Phenopacket, ParseError> {
+    Ok(Phenopacket::decode(bytes)?)
+}
+
+/// Read phenopackets from a JSON array of phenopackets in the canonical JSON format.
+///
+/// The phenopackets are read like with [`from_json`], and the path of an offending field
+/// starts with the index of its phenopacket, such as `[1].subject.sex`.
+///
+/// # Errors
+///
+/// Returns [`ParseError::Json`] if the input is not valid JSON,
+/// and [`ParseError::InvalidField`] with the path to the offending field
+/// if the JSON is not an array of valid phenopackets.
+///
+/// # Example
+///
+/// ```
+/// use phenopacket_builder::io::read_phenopackets_json_array;
+///
+/// let phenopackets = read_phenopackets_json_array(r#"[{"id": "a"}, {"id": "b"}]"#)
+///                     .expect("well formatted JSON");
+///
+/// assert_eq!(phenopackets.len(), 2);
+/// assert_eq!(&phenopackets[1].id, "b");
+/// ```
+#[cfg(feature = "json")]
+pub fn read_phenopackets_json_array(value: &str) -> Result<Vec<Phenopacket>, ParseError> {
+    let mut value: serde_json::Value = serde_json::from_str(value)?;
+    enum_numbers_to_names(&mut value);
+    serde_path_to_error::deserialize(value).map_err(|e| ParseError::InvalidField {
+        path: e.path().to_string(),
+        source: e.into_inner(),
+    })
+}
+
+/// Read a phenopacket in the canonical JSON format.
+///
+/// Besides the names, such as `"MALE"`, the enum fields accept the integer values, such as `2`.
+/// An unknown enum value is an error rather than the default value.
+///
+/// # Errors
+///
+/// Returns [`ParseError::Json`] if the input is not valid JSON,
+/// and [`ParseError::InvalidField`] with the path to the offending field
+/// if the JSON is not a valid phenopacket.
+///
+/// # Example
+///
+/// ```
+/// use phenopacket_builder::io::{from_json, ParseError};
+/// use phenopackets::schema::v2::core::Sex;
+///
+/// let pp = from_json(r#"{"id": "pp-id", "subject": {"id": "II-1", "sex": 1}}"#)
+///            .expect("well formatted JSON");
+/// assert_eq!(pp.subject.unwrap().sex(), Sex::Female);
+///
+/// let error = from_json(r#"{"id": "pp-id", "subject": {"id": "II-1", "sex": "ROBOT"}}"#)
+///               .expect_err("the sex is unknown");
+/// assert!(matches!(error, ParseError::InvalidField { path, .. } if path == "subject.sex"));
+/// ```
+#[cfg(feature = "json")]
+pub fn from_json(value: &str) -> Result<Phenopacket, ParseError> {
+    let mut value: serde_json::Value = serde_json::from_str(value)?;
+    enum_numbers_to_names(&mut value);
+    serde_path_to_error::deserialize(value).map_err(|e| ParseError::InvalidField {
+        path: e.path().to_string(),
+        source: e.into_inner(),
+    })
+}
+
+/// Replace the integer values of the enum fields with the enum names,
+/// leaving the unknown values for the deserializer to report.
+#[cfg(feature = "json")]
+fn enum_numbers_to_names(value: &mut serde_json::Value) {
+    use phenopackets::ga4gh::vrsatile::v1::MoleculeContext;
+    use phenopackets::schema::v2::core::genomic_interpretation::InterpretationStatus;
+    use phenopackets::schema::v2::core::interpretation::ProgressStatus;
+    use phenopackets::schema::v2::core::pedigree::person::AffectedStatus;
+    use phenopackets::schema::v2::core::therapeutic_regimen::RegimenStatus;
+    use phenopackets::schema::v2::core::vital_status::Status;
+    use phenopackets::schema::v2::core::{
+        AcmgPathogenicityClassification, DrugType, KaryotypicSex, Sex, TherapeuticActionability,
+    };
+    use serde_json::Value;
+
+    fn name(key: &str, number: i32) -> Option<&'static str> {
+        match key {
+            "sex" => Sex::try_from(number).ok().map(|e| e.as_str_name()),
+            "karyotypicSex" => KaryotypicSex::try_from(number)
+                .ok()
+                .map(|e| e.as_str_name()),
+            "status" => Status::try_from(number).ok().map(|e| e.as_str_name()),
+            "progressStatus" => ProgressStatus::try_from(number)
+                .ok()
+                .map(|e| e.as_str_name()),
+            "interpretationStatus" => InterpretationStatus::try_from(number)
+                .ok()
+                .map(|e| e.as_str_name()),
+            "acmgPathogenicityClassification" => AcmgPathogenicityClassification::try_from(number)
+                .ok()
+                .map(|e| e.as_str_name()),
+            "therapeuticActionability" => TherapeuticActionability::try_from(number)
+                .ok()
+                .map(|e| e.as_str_name()),
+            "regimenStatus" => RegimenStatus::try_from(number)
+                .ok()
+                .map(|e| e.as_str_name()),
+            "drugType" => DrugType::try_from(number).ok().map(|e| e.as_str_name()),
+            "affectedStatus" => AffectedStatus::try_from(number)
+                .ok()
+                .map(|e| e.as_str_name()),
+            "moleculeContext" => MoleculeContext::try_from(number)
+                .ok()
+                .map(|e| e.as_str_name()),
+            _ => None,
+        }
+    }
+
+    match value {
+        Value::Object(fields) => {
+            for (key, field) in fields.iter_mut() {
+                let number = field.as_i64().and_then(|n| i32::try_from(n).ok());
+                match number.and_then(|n| name(key, n)) {
+                    Some(name) => *field = Value::String(name.into()),
+                    None => enum_numbers_to_names(field),
+                }
+            }
+        }
+        Value::Array(values) => values.iter_mut().for_each(enum_numbers_to_names),
+        _ => {}
+    }
+}
+
+/// Write the phenopacket in the canonical JSON format.
+///
+/// # Example
+///
+/// ```
+/// use phenopacket_builder::io::write_json;
+/// use phenopackets::schema::v2::Phenopacket;
+///
+/// let pp = Phenopacket { id: "pp-id".into(), ..Default::default() };
+/// let mut buffer = Vec::new();
+///
+/// write_json(&pp, &mut buffer).expect("writing into a vector should not fail");
+///
+/// assert_eq!(&buffer, br#"{"id":"pp-id"}"#);
+/// ```
+#[cfg(feature = "json")]
+pub fn write_json(phenopacket: &Phenopacket, writer: impl Write) -> Result<(), WriteError> {
+    Ok(serde_json::to_writer(writer, phenopacket)?)
+}
+
+/// Serialize the phenopacket into a compact string in the canonical JSON format.
+///
+/// The format follows the Protobuf JSON mapping used by GA4GH:
+/// the field names are in camelCase, the enums are rendered by their names, such as `"MALE"`,
+/// and the timestamps are in RFC3339 format.
+///
+/// # Example
+///
+/// ```
+/// use phenopacket_builder::io::to_json;
+/// use phenopackets::schema::v2::core::{Individual, Sex};
+/// use phenopackets::schema::v2::Phenopacket;
+///
+/// let pp = Phenopacket {
+///     id: "pp-id".into(),
+///     subject: Some(Individual { id: "subject-id".into(), sex: Sex::Male.into(), ..Default::default() }),
+///     ..Default::default()
+/// };
+///
+/// let json = to_json(&pp).expect("serialization should not fail");
+///
+/// assert!(json.starts_with(r#"{"id":"pp-id","subject":{"id":"subject-id","sex":"MALE""#));
+/// ```
+#[cfg(feature = "json")]
+pub fn to_json(phenopacket: &Phenopacket) -> Result<String, WriteError> {
+    Ok(serde_json::to_string(phenopacket)?)
+}
+
+/// Serialize the phenopacket into an indented string in the canonical JSON format.
+///
+/// See [`to_json`] for the details of the format.
+#[cfg(feature = "json")]
+pub fn to_json_pretty(phenopacket: &Phenopacket) -> Result<String, WriteError> {
+    Ok(serde_json::to_string_pretty(phenopacket)?)
+}
+
+/// Serialize the phenopacket into YAML.
+///
+/// The field names and the values follow the same mapping as the JSON format,
+/// such as camelCase field names and enum names, so the two formats are interchangeable.
+///
+/// # Example
+///
+/// ```
+/// use phenopacket_builder::io::to_yaml;
+/// use phenopackets::schema::v2::Phenopacket;
+///
+/// let pp = Phenopacket { id: "pp-id".into(), ..Default::default() };
+///
+/// assert_eq!(&to_yaml(&pp).unwrap(), "id: pp-id\n");
+/// ```
+#[cfg(feature = "yaml")]
+pub fn to_yaml(phenopacket: &Phenopacket) -> Result<String, WriteError> {
+    Ok(serde_norway::to_string(phenopacket)?)
+}
+
+/// Read a phenopacket from YAML.
+///
+/// See [`to_yaml`] for the details of the format.
+#[cfg(feature = "yaml")]
+pub fn from_yaml(value: &str) -> Result<Phenopacket, ParseError> {
+    Ok(serde_norway::from_str(value)?)
+}
+
+// The messages that hold a `File`, with the map fields in a `BTreeMap`,
+// for `to_deterministic_bytes`. The tags are those of the `phenopackets` messages,
+// so the messages have the same wire format. The conversions destructure the messages,
+// so that a field added to the schema fails the build instead of being dropped.
+
+#[derive(Clone, PartialEq, Message)]
+struct SortedPhenopacket {
+    #[prost(string, tag = "1")]
+    id: String,
+    #[prost(message, optional, tag = "2")]
+    subject: Option<Individual>,
+    #[prost(message, repeated, tag = "3")]
+    phenotypic_features: Vec<PhenotypicFeature>,
+    #[prost(message, repeated, tag = "4")]
+    measurements: Vec<Measurement>,
+    #[prost(message, repeated, tag = "5")]
+    biosamples: Vec<SortedBiosample>,
+    #[prost(message, repeated, tag = "6")]
+    interpretations: Vec<Interpretation>,
+    #[prost(message, repeated, tag = "7")]
+    diseases: Vec<Disease>,
+    #[prost(message, repeated, tag = "9")]
+    medical_actions: Vec<MedicalAction>,
+    #[prost(message, repeated, tag = "10")]
+    files: Vec<SortedFile>,
+    #[prost(message, optional, tag = "11")]
+    meta_data: Option<MetaData>,
+}
+
+impl From<&Phenopacket> for SortedPhenopacket {
+    fn from(phenopacket: &Phenopacket) -> Self {
+        let Phenopacket {
+            id,
+            subject,
+            phenotypic_features,
+            measurements,
+            biosamples,
+            interpretations,
+            diseases,
+            medical_actions,
+            files,
+            meta_data,
+        } = phenopacket;
+        SortedPhenopacket {
+            id: id.clone(),
+            subject: subject.clone(),
+            phenotypic_features: phenotypic_features.clone(),
+            measurements: measurements.clone(),
+            biosamples: biosamples.iter().map(Into::into).collect(),
+            interpretations: interpretations.clone(),
+            diseases: diseases.clone(),
+            medical_actions: medical_actions.clone(),
+            files: files.iter().map(Into::into).collect(),
+            meta_data: meta_data.clone(),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct SortedBiosample {
+    #[prost(string, tag = "1")]
+    id: String,
+    #[prost(string, tag = "2")]
+    individual_id: String,
+    #[prost(string, tag = "3")]
+    derived_from_id: String,
+    #[prost(string, tag = "4")]
+    description: String,
+    #[prost(message, optional, tag = "5")]
+    sampled_tissue: Option<OntologyClass>,
+    #[prost(message, optional, tag = "6")]
+    sample_type: Option<OntologyClass>,
+    #[prost(message, repeated, tag = "7")]
+    phenotypic_features: Vec<PhenotypicFeature>,
+    #[prost(message, repeated, tag = "8")]
+    measurements: Vec<Measurement>,
+    #[prost(message, optional, tag = "9")]
+    taxonomy: Option<OntologyClass>,
+    #[prost(message, optional, tag = "10")]
+    time_of_collection: Option<TimeElement>,
+    #[prost(message, optional, tag = "11")]
+    histological_diagnosis: Option<OntologyClass>,
+    #[prost(message, optional, tag = "12")]
+    tumor_progression: Option<OntologyClass>,
+    #[prost(message, optional, tag = "13")]
+    tumor_grade: Option<OntologyClass>,
+    #[prost(message, optional, tag = "14")]
+    pathological_stage: Option<OntologyClass>,
+    #[prost(message, repeated, tag = "15")]
+    pathological_tnm_finding: Vec<OntologyClass>,
+    #[prost(message, repeated, tag = "16")]
+    diagnostic_markers: Vec<OntologyClass>,
+    #[prost(message, optional, tag = "17")]
+    procedure: Option<Procedure>,
+    #[prost(message, repeated, tag = "18")]
+    files: Vec<SortedFile>,
+    #[prost(message, optional, tag = "19")]
+    material_sample: Option<OntologyClass>,
+    #[prost(message, optional, tag = "20")]
+    sample_processing: Option<OntologyClass>,
+    #[prost(message, optional, tag = "21")]
+    sample_storage: Option<OntologyClass>,
+}
+
+impl From<&Biosample> for SortedBiosample {
+    fn from(biosample: &Biosample) -> Self {
+        let Biosample {
+            id,
+            individual_id,
+            derived_from_id,
+            description,
+            sampled_tissue,
+            sample_type,
+            phenotypic_features,
+            measurements,
+            taxonomy,
+            time_of_collection,
+            histological_diagnosis,
+            tumor_progression,
+            tumor_grade,
+            pathological_stage,
+            pathological_tnm_finding,
+            diagnostic_markers,
+            procedure,
+            files,
+            material_sample,
+            sample_processing,
+            sample_storage,
+        } = biosample;
+        SortedBiosample {
+            id: id.clone(),
+            individual_id: individual_id.clone(),
+            derived_from_id: derived_from_id.clone(),
+            description: description.clone(),
+            sampled_tissue: sampled_tissue.clone(),
+            sample_type: sample_type.clone(),
+            phenotypic_features: phenotypic_features.clone(),
+            measurements: measurements.clone(),
+            taxonomy: taxonomy.clone(),
+            time_of_collection: time_of_collection.clone(),
+            histological_diagnosis: histological_diagnosis.clone(),
+            tumor_progression: tumor_progression.clone(),
+            tumor_grade: tumor_grade.clone(),
+            pathological_stage: pathological_stage.clone(),
+            pathological_tnm_finding: pathological_tnm_finding.clone(),
+            diagnostic_markers: diagnostic_markers.clone(),
+            procedure: procedure.clone(),
+            files: files.iter().map(Into::into).collect(),
+            material_sample: material_sample.clone(),
+            sample_processing: sample_processing.clone(),
+            sample_storage: sample_storage.clone(),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct SortedFile {
+    #[prost(string, tag = "1")]
+    uri: String,
+    #[prost(btree_map = "string, string", tag = "2")]
+    individual_to_file_identifiers: BTreeMap<String, String>,
+    #[prost(btree_map = "string, string", tag = "3")]
+    file_attributes: BTreeMap<String, String>,
+}
+
+impl From<&File> for SortedFile {
+    fn from(file: &File) -> Self {
+        let File {
+            uri,
+            individual_to_file_identifiers,
+            file_attributes,
+        } = file;
+        let sorted = |entries: &HashMap<String, String>| {
+            entries
+                .iter()
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect()
+        };
+        SortedFile {
+            uri: uri.clone(),
+            individual_to_file_identifiers: sorted(individual_to_file_identifiers),
+            file_attributes: sorted(file_attributes),
+        }
+    }
+}
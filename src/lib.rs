@@ -8,10 +8,46 @@
 //! See the
 //! [use cases](https://github.com/P2GX/phenopacket-builder/blob/master/tests/use_cases.rs)
 //! for examples.
+//!
+//! # Feature flags
+//!
+//! No features are turned on by default.
+//!
+//! - `chrono`: Enables creating timestamps from [`chrono`](https://docs.rs/chrono) date times.
+//! - `derive`: Enables `#[derive(Buildable)]` for generating typestate builders,
+//!   and the builders in [`derived`] generated with it.
+//! - `json`: Enables reading and writing phenopackets in JSON format.
+//! - `tracing`: Emits [`tracing`](https://docs.rs/tracing) events when the builders
+//!   use the default value of an optional field that has not been set.
+//! - `yaml`: Enables reading and writing phenopackets in YAML format.
+//!
+//! # Compatibility
+//!
+//! The builders fill the fields they know about and leave the rest to `Default::default()`.
+//! A field added to the schema in a new `phenopackets` release therefore does not break the build,
+//! but it stays at its default value until the builder gets a setter for it.
+
+extern crate self as phenopacket_builder;
 
-use phenopackets::schema::v2::core::OntologyClass;
+use phenopackets::schema::v2::core::{OntologyClass, PhenotypicFeature};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
 
-mod v2;
+#[cfg(feature = "derive")]
+pub mod derived;
+pub mod edit;
+pub mod ingest;
+pub mod io;
+mod redact;
+pub mod report;
+pub mod time;
+mod trace;
+pub mod v2;
+pub mod validate;
+mod visit;
+
+#[cfg(feature = "derive")]
+pub use phenopacket_builder_derive::Buildable;
 
 #[derive(Copy, Clone, Debug, PartialEq, Hash, Eq)]
 pub struct Set;
@@ -25,10 +61,89 @@ pub trait Buildable {
     }
 }
 
+/// Types with a builder that tracks the required fields at runtime rather than in the typestate,
+/// for building values from input whose shape is not known at compile time.
+///
+/// # Example
+///
+/// ```
+/// use phenopacket_builder::{BuildError, DynBuildable};
+/// use phenopackets::schema::v2::core::OntologyClass;
+///
+/// let result = OntologyClass::builder_dyn().label("Seizure").build();
+///
+/// assert_eq!(
+///     result,
+///     Err(BuildError::MissingFields {
+///         type_name: "OntologyClass",
+///         fields: vec!["id"],
+///     })
+/// );
+/// ```
+pub trait DynBuildable {
+    type DynBuilder: Default;
+    fn builder_dyn() -> Self::DynBuilder {
+        Self::DynBuilder::default()
+    }
+}
+
 pub trait Build<T> {
     fn build(self) -> T;
 }
 
+/// A fallible counterpart of [`Build`], for builders assembled from runtime data,
+/// where the typestate cannot tell if the required fields have been set.
+///
+/// # Example
+///
+/// ```
+/// use phenopacket_builder::{BuildError, Buildable, TryBuild};
+/// use phenopackets::schema::v2::core::Individual;
+///
+/// let result: Result<Individual, _> = Individual::builder().male().try_build();
+///
+/// assert_eq!(result, Err(BuildError::MissingField("id")));
+/// ```
+pub trait TryBuild<T> {
+    fn try_build(self) -> Result<T, BuildError>;
+}
+
+/// An error returned by the fallible `try_build` methods of the builders.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BuildError {
+    /// The required field has not been set, or has been set to a blank value.
+    MissingField(&'static str),
+    /// The id is not a CURIE, such as `HP:0001250`.
+    MalformedCurie(String),
+    /// The label of the term with the id is empty or consists of whitespace only.
+    BlankLabel(String),
+    /// The ISO8601 duration of an age built with `non_negative` has a negative component.
+    NegativeDuration(String),
+    /// The required fields of a dynamic builder have not been set.
+    MissingFields {
+        type_name: &'static str,
+        fields: Vec<&'static str>,
+    },
+}
+
+impl Display for BuildError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BuildError::MissingField(field) => write!(f, "{field} must have been set"),
+            BuildError::MalformedCurie(id) => write!(f, "{id} is not a CURIE"),
+            BuildError::BlankLabel(id) => write!(f, "{id} has a blank label"),
+            BuildError::NegativeDuration(duration) => {
+                write!(f, "iso8601duration {duration} must not be negative")
+            }
+            BuildError::MissingFields { type_name, fields } => {
+                write!(f, "{type_name} is missing {}", fields.join(", "))
+            }
+        }
+    }
+}
+
+impl Error for BuildError {}
+
 /// To allow submitting `T` where `Build<T>` is expected.
 ///
 /// This is used across the builders.
@@ -55,6 +170,11 @@ where
 
 /// A shortcut for creating an [`OntologyClass`] from its `id` and `label`.
 ///
+/// Use `oc` where a `(id, label)` tuple would be handy, since tuples cannot be passed
+/// as `impl Build<OntologyClass>`: `From<(S1, S2)>` cannot be implemented for the foreign
+/// [`OntologyClass`], and a `Build` implementation for tuples would overlap with
+/// the blanket implementation for `Into`.
+///
 /// # Example
 ///
 /// ```
@@ -65,9 +185,70 @@ where
 /// assert_eq!(&seizure.id, "HP:0001250");
 /// assert_eq!(&seizure.label, "Seizure");
 /// ```
+#[allow(clippy::needless_update)]
 pub fn oc(id: impl Into<String>, label: impl Into<String>) -> OntologyClass {
     OntologyClass {
         id: id.into(),
         label: label.into(),
+        ..Default::default()
+    }
+}
+
+/// A shortcut for creating an [`OntologyClass`] from its `id` alone, e.g. for a term
+/// whose label is filled in later from the ontology.
+///
+/// The label is left empty on purpose, which is reported by [`validate::validate`]
+/// as [`validate::ValidationIssue::EmptyTermLabel`] until it is filled in.
+///
+/// # Example
+///
+/// ```
+/// use phenopacket_builder::oc_id;
+///
+/// let seizure = oc_id("HP:0001250");
+///
+/// assert_eq!(&seizure.id, "HP:0001250");
+/// assert!(seizure.label.is_empty());
+/// ```
+pub fn oc_id(id: impl Into<String>) -> OntologyClass {
+    oc(id, "")
+}
+
+/// A shortcut for creating an observed [`PhenotypicFeature`] from the `id` and `label` of its type.
+///
+/// # Example
+///
+/// ```
+/// use phenopacket_builder::{oc, observed_feature};
+///
+/// let seizure = observed_feature("HP:0001250", "Seizure");
+///
+/// assert_eq!(seizure.r#type, Some(oc("HP:0001250", "Seizure")));
+/// assert!(!seizure.excluded);
+/// ```
+pub fn observed_feature(id: impl Into<String>, label: impl Into<String>) -> PhenotypicFeature {
+    PhenotypicFeature {
+        r#type: Some(oc(id, label)),
+        ..Default::default()
+    }
+}
+
+/// A shortcut for creating an excluded [`PhenotypicFeature`] from the `id` and `label` of its type.
+///
+/// # Example
+///
+/// ```
+/// use phenopacket_builder::{oc, excluded_feature};
+///
+/// let seizure = excluded_feature("HP:0001250", "Seizure");
+///
+/// assert_eq!(seizure.r#type, Some(oc("HP:0001250", "Seizure")));
+/// assert!(seizure.excluded);
+/// ```
+pub fn excluded_feature(id: impl Into<String>, label: impl Into<String>) -> PhenotypicFeature {
+    PhenotypicFeature {
+        r#type: Some(oc(id, label)),
+        excluded: true,
+        ..Default::default()
     }
 }
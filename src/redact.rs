@@ -0,0 +1,100 @@
+//! Helpers for masking personally identifiable information in [`Debug`] output.
+
+use phenopackets::schema::v2::core::{
+    Biosample, File, Individual, Interpretation, KaryotypicSex, Sex,
+};
+use std::fmt::{Debug, Formatter};
+
+/// A placeholder formatted as `<redacted>`.
+#[derive(Copy, Clone)]
+pub(crate) struct Redacted;
+
+impl Debug for Redacted {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("<redacted>")
+    }
+}
+
+pub(crate) fn redact_option<T>(value: &Option<T>) -> Option<Redacted> {
+    value.as_ref().map(|_| Redacted)
+}
+
+pub(crate) fn redact_all<T>(values: &[T]) -> Vec<Redacted> {
+    values.iter().map(|_| Redacted).collect()
+}
+
+/// Formats an [`Individual`] with `id`, `alternate_ids`, and `date_of_birth` masked.
+pub(crate) struct RedactedIndividual<'a>(pub(crate) &'a Individual);
+
+impl Debug for RedactedIndividual<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let individual = self.0;
+        f.debug_struct("Individual")
+            .field("id", &Redacted)
+            .field("alternate_ids", &redact_all(&individual.alternate_ids))
+            .field("date_of_birth", &redact_option(&individual.date_of_birth))
+            .field("time_at_last_encounter", &individual.time_at_last_encounter)
+            .field("vital_status", &individual.vital_status)
+            .field("sex", &Sex::try_from(individual.sex).unwrap_or_default())
+            .field(
+                "karyotypic_sex",
+                &KaryotypicSex::try_from(individual.karyotypic_sex).unwrap_or_default(),
+            )
+            .field("gender", &individual.gender)
+            .field("taxonomy", &individual.taxonomy)
+            .finish()
+    }
+}
+
+const REDACTED: &str = "<redacted>";
+
+/// Get copies of the biosamples with `individual_id` and the file individual identifiers masked.
+pub(crate) fn redact_biosamples(biosamples: &[Biosample]) -> Vec<Biosample> {
+    biosamples
+        .iter()
+        .map(|biosample| Biosample {
+            individual_id: redact_id(&biosample.individual_id),
+            files: redact_files(&biosample.files),
+            ..biosample.clone()
+        })
+        .collect()
+}
+
+/// Get copies of the interpretations with the `subject_or_biosample_id`s masked.
+pub(crate) fn redact_interpretations(interpretations: &[Interpretation]) -> Vec<Interpretation> {
+    let mut interpretations = interpretations.to_vec();
+    for diagnosis in interpretations
+        .iter_mut()
+        .filter_map(|interpretation| interpretation.diagnosis.as_mut())
+    {
+        for genomic_interpretation in diagnosis.genomic_interpretations.iter_mut() {
+            genomic_interpretation.subject_or_biosample_id =
+                redact_id(&genomic_interpretation.subject_or_biosample_id);
+        }
+    }
+    interpretations
+}
+
+/// Get copies of the files with the individual identifiers masked, keeping the file identifiers.
+pub(crate) fn redact_files(files: &[File]) -> Vec<File> {
+    files
+        .iter()
+        .map(|file| File {
+            individual_to_file_identifiers: file
+                .individual_to_file_identifiers
+                .values()
+                .enumerate()
+                .map(|(i, identifier)| (format!("{REDACTED}-{i}"), identifier.clone()))
+                .collect(),
+            ..file.clone()
+        })
+        .collect()
+}
+
+fn redact_id(id: &str) -> String {
+    if id.is_empty() {
+        String::new()
+    } else {
+        REDACTED.to_string()
+    }
+}
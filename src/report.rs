@@ -0,0 +1,232 @@
+//! Reports for auditing the content of assembled phenopackets.
+
+use crate::time::time_element_to_human;
+use phenopackets::schema::v2::core::measurement::MeasurementValue;
+use phenopackets::schema::v2::core::{value, Measurement, OntologyClass, Quantity, Sex};
+use phenopackets::schema::v2::Phenopacket;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+/// Get the assay id of each measurement of the phenopacket, paired with the id of its unit.
+///
+/// The unit is `None` for complex values, ontology class values,
+/// and quantities with no unit.
+///
+/// # Example
+///
+/// ```
+/// use phenopacket_builder::oc;
+/// use phenopacket_builder::report::measurement_units;
+/// use phenopackets::schema::v2::core::measurement::MeasurementValue;
+/// use phenopackets::schema::v2::core::{value, Measurement, Quantity, Value};
+/// use phenopackets::schema::v2::Phenopacket;
+///
+/// let pp = Phenopacket {
+///     measurements: vec![Measurement {
+///         assay: Some(oc("LOINC:2345-7", "Glucose [Mass/volume] in Serum or Plasma")),
+///         measurement_value: Some(MeasurementValue::Value(Value {
+///             value: Some(value::Value::Quantity(Quantity {
+///                 unit: Some(oc("UCUM:mg/dL", "mg/dL")),
+///                 value: 110.,
+///                 reference_range: None,
+///             })),
+///         })),
+///         ..Default::default()
+///     }],
+///     ..Default::default()
+/// };
+///
+/// assert_eq!(
+///     measurement_units(&pp),
+///     vec![("LOINC:2345-7".to_string(), Some("UCUM:mg/dL".to_string()))],
+/// );
+/// ```
+pub fn measurement_units(phenopacket: &Phenopacket) -> Vec<(String, Option<String>)> {
+    phenopacket
+        .measurements
+        .iter()
+        .map(|measurement| {
+            let assay = measurement
+                .assay
+                .as_ref()
+                .map(|assay| assay.id.clone())
+                .unwrap_or_default();
+            let unit = match &measurement.measurement_value {
+                Some(MeasurementValue::Value(v)) => match &v.value {
+                    Some(value::Value::Quantity(q)) => q.unit.as_ref().map(|unit| unit.id.clone()),
+                    _ => None,
+                },
+                _ => None,
+            };
+            (assay, unit)
+        })
+        .collect()
+}
+
+/// Group the measurements of the phenopacket by their assay id,
+/// e.g. to follow a lab value over time.
+///
+/// The measurements of each assay keep their order in the phenopacket.
+/// Measurements with no assay are grouped under an empty id.
+pub fn measurements_by_assay(phenopacket: &Phenopacket) -> BTreeMap<String, Vec<&Measurement>> {
+    let mut groups: BTreeMap<String, Vec<&Measurement>> = BTreeMap::new();
+    for measurement in &phenopacket.measurements {
+        let assay = measurement
+            .assay
+            .as_ref()
+            .map(|assay| assay.id.clone())
+            .unwrap_or_default();
+        groups.entry(assay).or_default().push(measurement);
+    }
+    groups
+}
+
+/// Summarize the phenopacket as a Markdown document for clinical reviewers.
+///
+/// The document has a section for the subject, the phenotypic features, and the diseases,
+/// and a table of the measurements. Empty sections are left out.
+/// The elements are listed in their order in the phenopacket.
+///
+/// # Example
+///
+/// ```
+/// use phenopacket_builder::oc;
+/// use phenopacket_builder::report::summary_markdown;
+/// use phenopackets::schema::v2::core::PhenotypicFeature;
+/// use phenopackets::schema::v2::Phenopacket;
+///
+/// let pp = Phenopacket {
+///     id: "pp-id".into(),
+///     phenotypic_features: vec![PhenotypicFeature {
+///         r#type: Some(oc("HP:0001250", "Seizure")),
+///         ..Default::default()
+///     }],
+///     ..Default::default()
+/// };
+///
+/// assert_eq!(
+///     summary_markdown(&pp),
+///     "# Phenopacket pp-id\n\n## Phenotypic Features\n\n- Seizure (HP:0001250)\n",
+/// );
+/// ```
+pub fn summary_markdown(phenopacket: &Phenopacket) -> String {
+    let mut sections = vec![format!("# Phenopacket {}\n", phenopacket.id)];
+
+    if let Some(subject) = &phenopacket.subject {
+        let mut section = String::from("## Subject\n\n");
+        let _ = writeln!(section, "- ID: {}", subject.id);
+        let sex = Sex::try_from(subject.sex).unwrap_or_default();
+        let _ = writeln!(section, "- Sex: {}", sex.as_str_name());
+        if let Some(time_at_last_encounter) = &subject.time_at_last_encounter {
+            let _ = writeln!(
+                section,
+                "- Time at last encounter: {}",
+                time_element_to_human(time_at_last_encounter)
+            );
+        }
+        if let Some(vital_status) = &subject.vital_status {
+            let _ = writeln!(
+                section,
+                "- Vital status: {}",
+                vital_status.status().as_str_name()
+            );
+        }
+        sections.push(section);
+    }
+
+    if !phenopacket.phenotypic_features.is_empty() {
+        let mut section = String::from("## Phenotypic Features\n\n");
+        for feature in &phenopacket.phenotypic_features {
+            let _ = write!(section, "- {}", term_to_markdown(feature.r#type.as_ref()));
+            if feature.excluded {
+                section.push_str(", excluded");
+            }
+            if let Some(onset) = &feature.onset {
+                let _ = write!(section, ", onset {}", time_element_to_human(onset));
+            }
+            section.push('\n');
+        }
+        sections.push(section);
+    }
+
+    if !phenopacket.diseases.is_empty() {
+        let mut section = String::from("## Diseases\n\n");
+        for disease in &phenopacket.diseases {
+            let _ = write!(section, "- {}", term_to_markdown(disease.term.as_ref()));
+            if disease.excluded {
+                section.push_str(", excluded");
+            }
+            if let Some(onset) = &disease.onset {
+                let _ = write!(section, ", onset {}", time_element_to_human(onset));
+            }
+            section.push('\n');
+        }
+        sections.push(section);
+    }
+
+    if !phenopacket.measurements.is_empty() {
+        let mut section = String::from(
+            "## Measurements\n\n| Assay | Value | Time observed |\n| --- | --- | --- |\n",
+        );
+        for measurement in &phenopacket.measurements {
+            let value = match &measurement.measurement_value {
+                Some(MeasurementValue::Value(v)) => value_to_markdown(v.value.as_ref()),
+                Some(MeasurementValue::ComplexValue(complex)) => complex
+                    .typed_quantities
+                    .iter()
+                    .map(|tq| {
+                        let r#type = tq.r#type.as_ref().map(|t| t.label.as_str()).unwrap_or("");
+                        format!("{}: {}", r#type, quantity_to_markdown(tq.quantity.as_ref()))
+                    })
+                    .collect::<Vec<_>>()
+                    .join("; "),
+                None => String::new(),
+            };
+            let time_observed = measurement
+                .time_observed
+                .as_ref()
+                .map(time_element_to_human)
+                .unwrap_or_default();
+            let _ = writeln!(
+                section,
+                "| {} | {} | {} |",
+                escape_cell(&term_to_markdown(measurement.assay.as_ref())),
+                escape_cell(&value),
+                escape_cell(&time_observed),
+            );
+        }
+        sections.push(section);
+    }
+
+    sections.join("\n")
+}
+
+fn term_to_markdown(term: Option<&OntologyClass>) -> String {
+    match term {
+        Some(term) => format!("{} ({})", term.label, term.id),
+        None => String::new(),
+    }
+}
+
+fn value_to_markdown(value: Option<&value::Value>) -> String {
+    match value {
+        Some(value::Value::Quantity(quantity)) => quantity_to_markdown(Some(quantity)),
+        Some(value::Value::OntologyClass(oc)) => term_to_markdown(Some(oc)),
+        None => String::new(),
+    }
+}
+
+fn quantity_to_markdown(quantity: Option<&Quantity>) -> String {
+    match quantity {
+        Some(quantity) => match &quantity.unit {
+            Some(unit) => format!("{} {}", quantity.value, unit.label),
+            None => quantity.value.to_string(),
+        },
+        None => String::new(),
+    }
+}
+
+/// Escape the pipes, which would otherwise end the table cell.
+fn escape_cell(value: &str) -> String {
+    value.replace('|', "\\|")
+}
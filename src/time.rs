@@ -0,0 +1,290 @@
+//! Utilities for working with the temporal building blocks of Phenopacket Schema.
+
+use phenopackets::schema::v2::core::time_element::Element;
+use phenopackets::schema::v2::core::TimeElement;
+use prost_types::Timestamp;
+use std::cmp::Ordering;
+
+const SECONDS_PER_DAY: i64 = 86_400;
+
+/// Compare two time elements, using the date of birth to place ages on the timeline.
+///
+/// Ages are converted to timestamps by adding them to `dob`,
+/// which allows comparing e.g. an onset recorded as an age with an onset recorded as a timestamp.
+/// Age ranges and time intervals are represented by their start.
+///
+/// Returns `None` if any of the elements cannot be placed on the timeline,
+/// such as a gestational age, an ontology class, or an age that is not a valid ISO8601 duration.
+///
+/// # Example
+///
+/// ```
+/// use std::cmp::Ordering;
+/// use phenopacket_builder::{Build, Buildable};
+/// use phenopacket_builder::time::compare_time_elements_with_dob;
+/// use phenopackets::schema::v2::core::TimeElement;
+/// use prost_types::Timestamp;
+///
+/// let dob = Timestamp::builder().iso8601timestamp("2018-03-01").unwrap().build();
+///
+/// let age: TimeElement = TimeElement::builder().age_iso8601duration("P1Y").build();
+/// let ts: TimeElement = TimeElement::builder()
+///                         .timestamp(Timestamp::builder().iso8601timestamp("2019-01-01").unwrap())
+///                         .build();
+///
+/// assert_eq!(compare_time_elements_with_dob(&age, &ts, &dob), Some(Ordering::Greater));
+/// ```
+pub fn compare_time_elements_with_dob(
+    a: &TimeElement,
+    b: &TimeElement,
+    dob: &Timestamp,
+) -> Option<Ordering> {
+    let a = time_element_to_timestamp(a, dob)?;
+    let b = time_element_to_timestamp(b, dob)?;
+
+    Some((a.seconds, a.nanos).cmp(&(b.seconds, b.nanos)))
+}
+
+fn time_element_to_timestamp(time_element: &TimeElement, dob: &Timestamp) -> Option<Timestamp> {
+    match time_element.element.as_ref()? {
+        Element::Age(age) => Some(add_duration(
+            dob,
+            &parse_iso8601_duration(&age.iso8601duration)?,
+        )),
+        Element::AgeRange(age_range) => Some(add_duration(
+            dob,
+            &parse_iso8601_duration(&age_range.start.as_ref()?.iso8601duration)?,
+        )),
+        Element::Timestamp(timestamp) => Some(*timestamp),
+        Element::Interval(interval) => interval.start,
+        Element::GestationalAge(_) | Element::OntologyClass(_) => None,
+    }
+}
+
+/// Render the time element as a compact string for humans, such as `3y 4m` or `25w 5d`.
+///
+/// Ages are rendered by their components, gestational ages in weeks and days,
+/// timestamps in ISO8601 format, and ontology classes by their label.
+/// Age ranges and time intervals are rendered as their start and end separated by ` - `.
+///
+/// # Example
+///
+/// ```
+/// use phenopacket_builder::{Build, Buildable};
+/// use phenopacket_builder::time::time_element_to_human;
+/// use phenopackets::schema::v2::core::TimeElement;
+///
+/// let age: TimeElement = TimeElement::builder().age_iso8601duration("P3Y4M").build();
+///
+/// assert_eq!(time_element_to_human(&age), "3y 4m");
+/// ```
+pub fn time_element_to_human(time_element: &TimeElement) -> String {
+    match &time_element.element {
+        Some(Element::Age(age)) => age_to_human(&age.iso8601duration),
+        Some(Element::AgeRange(age_range)) => format!(
+            "{} - {}",
+            age_range
+                .start
+                .as_ref()
+                .map(|age| age_to_human(&age.iso8601duration))
+                .unwrap_or_default(),
+            age_range
+                .end
+                .as_ref()
+                .map(|age| age_to_human(&age.iso8601duration))
+                .unwrap_or_default(),
+        ),
+        Some(Element::GestationalAge(gestational_age)) => match gestational_age.days {
+            0 => format!("{}w", gestational_age.weeks),
+            days => format!("{}w {days}d", gestational_age.weeks),
+        },
+        Some(Element::Timestamp(timestamp)) => timestamp.to_string(),
+        Some(Element::Interval(interval)) => format!(
+            "{} - {}",
+            interval.start.map(|ts| ts.to_string()).unwrap_or_default(),
+            interval.end.map(|ts| ts.to_string()).unwrap_or_default(),
+        ),
+        Some(Element::OntologyClass(oc)) => oc.label.clone(),
+        None => String::new(),
+    }
+}
+
+/// Render an ISO8601 duration by its non-zero components, e.g. `P3Y4M` as `3y 4m`.
+///
+/// Malformed durations are returned unchanged.
+fn age_to_human(iso8601duration: &str) -> String {
+    let Some(duration) = parse_iso8601_duration(iso8601duration) else {
+        return iso8601duration.to_string();
+    };
+    let components = [
+        (duration.years, "y"),
+        (duration.months, "m"),
+        (duration.weeks, "w"),
+        (duration.days, "d"),
+        (duration.hours, "h"),
+        (duration.minutes, "min"),
+        (duration.seconds, "s"),
+    ];
+    let human: Vec<_> = components
+        .iter()
+        .filter(|(value, _)| *value != 0)
+        .map(|(value, unit)| format!("{value}{unit}"))
+        .collect();
+
+    if human.is_empty() {
+        "0d".to_string()
+    } else {
+        human.join(" ")
+    }
+}
+
+/// Components of an ISO8601 duration, such as `P1Y2M3DT4H5M6S`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Iso8601Duration {
+    pub(crate) years: i32,
+    pub(crate) months: i32,
+    pub(crate) weeks: i32,
+    pub(crate) days: i32,
+    pub(crate) hours: i32,
+    pub(crate) minutes: i32,
+    pub(crate) seconds: i32,
+}
+
+impl Iso8601Duration {
+    pub(crate) fn has_negative_component(&self) -> bool {
+        [
+            self.years,
+            self.months,
+            self.weeks,
+            self.days,
+            self.hours,
+            self.minutes,
+            self.seconds,
+        ]
+        .iter()
+        .any(|&value| value < 0)
+    }
+}
+
+/// Parse an ISO8601 duration with integral components, such as `P3Y4M` or `PT12H`.
+///
+/// Returns `None` if the duration is malformed.
+pub(crate) fn parse_iso8601_duration(value: &str) -> Option<Iso8601Duration> {
+    let mut chars = value.strip_prefix('P')?.chars().peekable();
+    let mut duration = Iso8601Duration::default();
+    // The designators that can still follow, to enforce the order of the components.
+    let mut designators: &[char] = &['Y', 'M', 'W', 'D', 'T'];
+    let mut in_time = false;
+    let mut has_component = false;
+
+    while chars.peek().is_some() {
+        if chars.peek() == Some(&'T') {
+            chars.next();
+            if in_time || !designators.contains(&'T') {
+                return None;
+            }
+            in_time = true;
+            designators = &['H', 'M', 'S'];
+            // The time designator must be followed by at least one component.
+            chars.peek()?;
+            continue;
+        }
+
+        let mut number = String::new();
+        if chars.peek() == Some(&'-') {
+            number.push('-');
+            chars.next();
+        }
+        while let Some(c) = chars.next_if(char::is_ascii_digit) {
+            number.push(c);
+        }
+        let value: i32 = number.parse().ok()?;
+
+        let designator = chars.next()?;
+        let position = designators.iter().position(|&d| d == designator)?;
+        designators = &designators[position + 1..];
+        match (in_time, designator) {
+            (false, 'Y') => duration.years = value,
+            (false, 'M') => duration.months = value,
+            (false, 'W') => duration.weeks = value,
+            (false, 'D') => duration.days = value,
+            (true, 'H') => duration.hours = value,
+            (true, 'M') => duration.minutes = value,
+            (true, 'S') => duration.seconds = value,
+            _ => return None,
+        }
+        has_component = true;
+    }
+
+    has_component.then_some(duration)
+}
+
+/// Add the duration to the timestamp.
+///
+/// Years and months are added to the calendar date,
+/// clamping the day to the length of the resulting month (e.g. Jan 31st + 1 month is Feb 28th).
+pub(crate) fn add_duration(timestamp: &Timestamp, duration: &Iso8601Duration) -> Timestamp {
+    let days = timestamp.seconds.div_euclid(SECONDS_PER_DAY);
+    let seconds_of_day = timestamp.seconds.rem_euclid(SECONDS_PER_DAY);
+    let (year, month, day) = civil_from_days(days);
+
+    let months =
+        year * 12 + (month - 1) + i64::from(duration.years) * 12 + i64::from(duration.months);
+    let year = months.div_euclid(12);
+    let month = months.rem_euclid(12) + 1;
+    let day = day.min(days_in_month(year, month));
+
+    let days = days_from_civil(year, month, day)
+        + i64::from(duration.weeks) * 7
+        + i64::from(duration.days);
+    let seconds = days * SECONDS_PER_DAY
+        + seconds_of_day
+        + i64::from(duration.hours) * 3_600
+        + i64::from(duration.minutes) * 60
+        + i64::from(duration.seconds);
+
+    Timestamp {
+        seconds,
+        nanos: timestamp.nanos,
+    }
+}
+
+pub(crate) fn is_leap_year(year: i64) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+pub(crate) fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Get the number of days since the Unix epoch for a proleptic Gregorian date.
+pub(crate) fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Get the proleptic Gregorian `(year, month, day)` for the number of days since the Unix epoch.
+pub(crate) fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    (year, month, day)
+}
@@ -0,0 +1,24 @@
+//! Diagnostics of the builders, emitted as [`tracing`](https://docs.rs/tracing) events
+//! with the `tracing` feature.
+
+/// Get the value of an optional field, or the default value if the field has not been set.
+///
+/// With the `tracing` feature, a `DEBUG` event with the `builder` and `field` names
+/// is emitted when the field defaults.
+#[inline]
+pub(crate) fn or_default<T: Default>(
+    value: Option<T>,
+    builder: &'static str,
+    field: &'static str,
+) -> T {
+    match value {
+        Some(value) => value,
+        None => {
+            #[cfg(feature = "tracing")]
+            tracing::debug!(builder, field, "optional field not set, using the default");
+            #[cfg(not(feature = "tracing"))]
+            let _ = (builder, field);
+            T::default()
+        }
+    }
+}
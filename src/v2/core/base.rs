@@ -1,8 +1,13 @@
-use crate::{Build, Buildable, Set, Unset};
+use crate::time::parse_iso8601_duration;
+use crate::trace::or_default;
+use crate::v2::TimestampParseError;
+use crate::{oc, Build, BuildError, Buildable, DynBuildable, Set, TryBuild, Unset};
 use phenopackets::schema::v2::core::time_element::Element;
 use phenopackets::schema::v2::core::{
-    Age, ExternalReference, GestationalAge, OntologyClass, TimeElement,
+    Age, ExternalReference, GestationalAge, OntologyClass, TimeElement, TimeInterval,
 };
+use std::error::Error;
+use std::fmt::{Display, Formatter};
 use std::marker::PhantomData;
 
 #[derive(Debug, Default, Clone, PartialEq)]
@@ -24,21 +29,354 @@ impl OntologyClassBuilder<Unset> {
             data: PhantomData,
         }
     }
+
+    /// Set the `id` and the `label`, checking the values first.
+    ///
+    /// Use [`OntologyClassBuilder::id_label`] to set the values without the checks.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CurieError::MissingColon`], [`CurieError::EmptyPrefix`],
+    /// or [`CurieError::EmptyLocalPart`] if the id is not of the form `PREFIX:LOCAL`,
+    /// [`CurieError::Malformed`] if the id is otherwise not a CURIE
+    /// by the rules of [`OntologyClassBuilder::try_build`],
+    /// and [`CurieError::BlankLabel`] if the label is empty or consists of whitespace only.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use phenopacket_builder::Buildable;
+    /// use phenopacket_builder::v2::CurieError;
+    /// use phenopackets::schema::v2::core::OntologyClass;
+    ///
+    /// assert!(OntologyClass::builder().try_id_label("HP:0001250", "Seizure").is_ok());
+    /// assert_eq!(
+    ///     OntologyClass::builder().try_id_label("HP_0001250", "Seizure"),
+    ///     Err(CurieError::MissingColon("HP_0001250".into())),
+    /// );
+    /// assert_eq!(
+    ///     OntologyClass::builder().try_id_label("HP:0001250", "   "),
+    ///     Err(CurieError::BlankLabel),
+    /// );
+    /// ```
+    pub fn try_id_label(
+        self,
+        id: impl Into<String>,
+        label: impl Into<String>,
+    ) -> Result<OntologyClassBuilder<Set>, CurieError> {
+        let id = id.into();
+        match id.split_once(':') {
+            None => return Err(CurieError::MissingColon(id)),
+            Some(("", _)) => return Err(CurieError::EmptyPrefix(id)),
+            Some((_, "")) => return Err(CurieError::EmptyLocalPart(id)),
+            Some(_) if !is_curie(&id) => return Err(CurieError::Malformed(id)),
+            Some(_) => {}
+        }
+        let label = label.into();
+        if label.trim().is_empty() {
+            return Err(CurieError::BlankLabel);
+        }
+        Ok(self.id_label(id, label))
+    }
+
+    /// Set the `id` of a term whose label is not known, e.g. to be resolved later.
+    ///
+    /// The label is left empty, which is reported by [`crate::validate::validate`]
+    /// as [`crate::validate::ValidationIssue::EmptyTermLabel`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use phenopacket_builder::{Build, Buildable};
+    /// use phenopackets::schema::v2::core::OntologyClass;
+    ///
+    /// let oc: OntologyClass = OntologyClass::builder()
+    ///                           .unknown("HP:0001250")
+    ///                           .build();
+    ///
+    /// assert_eq!(&oc.id, "HP:0001250");
+    /// assert_eq!(&oc.label, "");
+    /// ```
+    pub fn unknown(self, id: impl Into<String>) -> OntologyClassBuilder<Set> {
+        self.id_label(id, "")
+    }
+
+    fn prefixed(
+        self,
+        prefix: &str,
+        local_id: impl AsRef<str>,
+        label: impl Into<String>,
+    ) -> OntologyClassBuilder<Set> {
+        self.id_label(format!("{prefix}:{}", local_id.as_ref()), label)
+    }
+
+    /// Set a Human Phenotype Ontology term from its local id, such as `0001250`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use phenopacket_builder::{Build, Buildable};
+    /// use phenopackets::schema::v2::core::OntologyClass;
+    ///
+    /// let oc: OntologyClass = OntologyClass::builder()
+    ///                           .hp("0001250", "Seizure")
+    ///                           .build();
+    ///
+    /// assert_eq!(&oc.id, "HP:0001250");
+    /// ```
+    pub fn hp(
+        self,
+        local_id: impl AsRef<str>,
+        label: impl Into<String>,
+    ) -> OntologyClassBuilder<Set> {
+        self.prefixed("HP", local_id, label)
+    }
+
+    /// Set a Mondo Disease Ontology term from its local id, such as `0007947`.
+    pub fn mondo(
+        self,
+        local_id: impl AsRef<str>,
+        label: impl Into<String>,
+    ) -> OntologyClassBuilder<Set> {
+        self.prefixed("MONDO", local_id, label)
+    }
+
+    /// Set an NCI Thesaurus term from its local id, such as `C3117`.
+    pub fn ncit(
+        self,
+        local_id: impl AsRef<str>,
+        label: impl Into<String>,
+    ) -> OntologyClassBuilder<Set> {
+        self.prefixed("NCIT", local_id, label)
+    }
+
+    /// Set an Uberon term from its local id, such as `0002107`.
+    pub fn uberon(
+        self,
+        local_id: impl AsRef<str>,
+        label: impl Into<String>,
+    ) -> OntologyClassBuilder<Set> {
+        self.prefixed("UBERON", local_id, label)
+    }
+
+    /// Set a GENO term from its local id, such as `0000135`.
+    pub fn geno(
+        self,
+        local_id: impl AsRef<str>,
+        label: impl Into<String>,
+    ) -> OntologyClassBuilder<Set> {
+        self.prefixed("GENO", local_id, label)
+    }
+}
+
+impl<T> OntologyClassBuilder<T> {
+    /// Build the ontology class, checking that the `id` is a CURIE and the `label` is not blank,
+    /// even if the builder is not in the `Set` state.
+    ///
+    /// This is stricter than [`Build::build`], which takes the `id` and the `label` as they are,
+    /// e.g. the empty label set by [`OntologyClassBuilder::unknown`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BuildError::MissingField`] naming the missing `id` or `label`,
+    /// [`BuildError::MalformedCurie`] if the `id` is not a CURIE,
+    /// and [`BuildError::BlankLabel`] if the `label` is blank.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use phenopacket_builder::{BuildError, Buildable};
+    /// use phenopackets::schema::v2::core::OntologyClass;
+    ///
+    /// assert_eq!(
+    ///     OntologyClass::builder().try_build(),
+    ///     Err(BuildError::MissingField("id")),
+    /// );
+    /// assert!(OntologyClass::builder().id_label("HP:0001250", "Seizure").try_build().is_ok());
+    /// ```
+    #[allow(clippy::needless_update)]
+    pub fn try_build(self) -> Result<OntologyClass, BuildError> {
+        let id = self
+            .id
+            .filter(|id| !id.trim().is_empty())
+            .ok_or(BuildError::MissingField("id"))?;
+        if !is_curie(&id) {
+            return Err(BuildError::MalformedCurie(id));
+        }
+        let label = self.label.ok_or(BuildError::MissingField("label"))?;
+        if label.trim().is_empty() {
+            return Err(BuildError::BlankLabel(id));
+        }
+        Ok(OntologyClass {
+            id,
+            label,
+            ..Default::default()
+        })
+    }
+}
+
+/// An error returned by [`OntologyClassBuilder::try_id_label`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CurieError {
+    /// The id has no `:` between the prefix and the local part, such as `HP_0001250`.
+    MissingColon(String),
+    /// The id has no prefix, such as `:0001250`.
+    EmptyPrefix(String),
+    /// The id has no local part, such as `HP:`.
+    EmptyLocalPart(String),
+    /// The prefix does not start with a letter or has other characters than letters, digits,
+    /// `_`, `-`, or `.`, or the local part contains whitespace, such as `1HP:00 01`.
+    Malformed(String),
+    /// The label is empty or consists of whitespace only.
+    BlankLabel,
+}
+
+impl Display for CurieError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CurieError::MissingColon(id) => write!(f, "{id} has no colon"),
+            CurieError::EmptyPrefix(id) => write!(f, "{id} has an empty prefix"),
+            CurieError::EmptyLocalPart(id) => write!(f, "{id} has an empty local part"),
+            CurieError::Malformed(id) => write!(f, "{id} is not a CURIE"),
+            CurieError::BlankLabel => f.write_str("the label must not be blank"),
+        }
+    }
+}
+
+impl Error for CurieError {}
+
+/// The HPO onset terms set by the `onset_*` presets of the builders.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum HpoOnset {
+    Congenital,
+    Neonatal,
+    Infantile,
+    Childhood,
+    Adult,
+}
+
+impl From<HpoOnset> for TimeElement {
+    fn from(onset: HpoOnset) -> Self {
+        let (id, label) = match onset {
+            HpoOnset::Congenital => ("HP:0003577", "Congenital onset"),
+            HpoOnset::Neonatal => ("HP:0003623", "Neonatal onset"),
+            HpoOnset::Infantile => ("HP:0003593", "Infantile onset"),
+            HpoOnset::Childhood => ("HP:0011463", "Childhood onset"),
+            HpoOnset::Adult => ("HP:0003581", "Adult onset"),
+        };
+        TimeElement {
+            element: Some(Element::OntologyClass(oc(id, label))),
+        }
+    }
+}
+
+/// Check that the `id` is a CURIE, such as `HP:0001250`.
+///
+/// The prefix must start with a letter and consist of letters, digits, `_`, `-`, or `.`.
+/// The local part must not be empty or contain whitespace.
+pub(crate) fn is_curie(id: &str) -> bool {
+    let Some((prefix, local)) = id.split_once(':') else {
+        return false;
+    };
+    prefix.starts_with(|c: char| c.is_ascii_alphabetic())
+        && prefix
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+        && !local.is_empty()
+        && !local.contains(char::is_whitespace)
 }
 
 impl Buildable for OntologyClass {
     type Builder = OntologyClassBuilder;
 }
 
+/// Create a builder pre-populated with the `id` and the `label` of an existing [`OntologyClass`].
+impl From<OntologyClass> for OntologyClassBuilder<Set> {
+    fn from(oc: OntologyClass) -> Self {
+        OntologyClassBuilder {
+            id: Some(oc.id),
+            label: Some(oc.label),
+            data: PhantomData,
+        }
+    }
+}
+
+impl<T> TryBuild<OntologyClass> for OntologyClassBuilder<T> {
+    /// See [`OntologyClassBuilder::try_build`].
+    fn try_build(self) -> Result<OntologyClass, BuildError> {
+        OntologyClassBuilder::try_build(self)
+    }
+}
+
 impl Build<OntologyClass> for OntologyClassBuilder<Set> {
+    #[allow(clippy::needless_update)]
     fn build(self) -> OntologyClass {
         OntologyClass {
             id: self.id.expect("id must have been set"),
             label: self.label.expect("label must have been set"),
+            ..Default::default()
         }
     }
 }
 
+/// A builder of [`OntologyClass`] that checks the required fields when building,
+/// rather than in the typestate.
+///
+/// Use [`DynBuildable::builder_dyn`] to create the builder.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct DynOntologyClassBuilder {
+    id: Option<String>,
+    label: Option<String>,
+}
+
+impl DynOntologyClassBuilder {
+    pub fn id(mut self, id: impl Into<String>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Build the [`OntologyClass`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BuildError::MissingFields`] listing all required fields that have not been set.
+    #[allow(clippy::needless_update)]
+    pub fn build(self) -> Result<OntologyClass, BuildError> {
+        match (self.id, self.label) {
+            (Some(id), Some(label)) => Ok(OntologyClass {
+                id,
+                label,
+                ..Default::default()
+            }),
+            (id, label) => {
+                let fields = [("id", id.is_none()), ("label", label.is_none())]
+                    .into_iter()
+                    .filter_map(|(field, missing)| missing.then_some(field))
+                    .collect();
+                Err(BuildError::MissingFields {
+                    type_name: "OntologyClass",
+                    fields,
+                })
+            }
+        }
+    }
+}
+
+impl TryBuild<OntologyClass> for DynOntologyClassBuilder {
+    fn try_build(self) -> Result<OntologyClass, BuildError> {
+        self.build()
+    }
+}
+
+impl DynBuildable for OntologyClass {
+    type DynBuilder = DynOntologyClassBuilder;
+}
+
 #[derive(Debug, Default, Clone, PartialEq)]
 pub struct TimeElementBuilder<T = Unset> {
     element: Option<Element>,
@@ -82,16 +420,19 @@ impl<T> TimeElementBuilder<T> {
         }
     }
 
+    #[allow(clippy::needless_update)]
     pub fn gestational_age_weeks(self, weeks: impl Into<i32>) -> TimeElementBuilder<Set> {
         TimeElementBuilder {
             element: Some(Element::GestationalAge(GestationalAge {
                 weeks: weeks.into(),
                 days: Default::default(),
+                ..Default::default()
             })),
             data: PhantomData,
         }
     }
 
+    #[allow(clippy::needless_update)]
     pub fn gestational_age_weeks_days(
         self,
         weeks: impl Into<i32>,
@@ -101,11 +442,56 @@ impl<T> TimeElementBuilder<T> {
             element: Some(Element::GestationalAge(GestationalAge {
                 weeks: weeks.into(),
                 days: days.into(),
+                ..Default::default()
             })),
             data: PhantomData,
         }
     }
 
+    /// Set the gestational age from an ISO8601 duration with weeks and days only, such as `P25W5D`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GestationalAgeError::Malformed`] if the value is not an ISO8601 duration
+    /// or has components other than weeks and days,
+    /// [`GestationalAgeError::Negative`] if any component is negative,
+    /// and [`GestationalAgeError::DaysOutOfRange`] if the days are not in `0..=6`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use phenopacket_builder::{Build, Buildable};
+    /// use phenopackets::schema::v2::core::{GestationalAge, TimeElement};
+    /// use phenopackets::schema::v2::core::time_element::Element;
+    ///
+    /// let te: TimeElement = TimeElement::builder()
+    ///                         .gestational_age_iso8601("P25W5D")
+    ///                         .expect("well formatted duration")
+    ///                         .build();
+    ///
+    /// assert_eq!(
+    ///     te.element,
+    ///     Some(Element::GestationalAge(GestationalAge { weeks: 25, days: 5 }))
+    /// );
+    /// ```
+    pub fn gestational_age_iso8601(
+        self,
+        iso8601duration: impl AsRef<str>,
+    ) -> Result<TimeElementBuilder<Set>, GestationalAgeError> {
+        let duration = parse_iso8601_duration(iso8601duration.as_ref())
+            .filter(|d| {
+                d.years == 0 && d.months == 0 && d.hours == 0 && d.minutes == 0 && d.seconds == 0
+            })
+            .ok_or(GestationalAgeError::Malformed)?;
+        if duration.has_negative_component() {
+            return Err(GestationalAgeError::Negative);
+        }
+        if duration.days > 6 {
+            return Err(GestationalAgeError::DaysOutOfRange(duration.days));
+        }
+        Ok(self.gestational_age_weeks_days(duration.weeks, duration.days))
+    }
+
     /// Set the age.
     ///
     /// # Examples
@@ -162,6 +548,7 @@ impl<T> TimeElementBuilder<T> {
     ///     ))
     /// );
     /// ```
+    #[allow(clippy::needless_update)]
     pub fn age_iso8601duration(
         self,
         iso8601duration: impl Into<String>,
@@ -169,6 +556,7 @@ impl<T> TimeElementBuilder<T> {
         TimeElementBuilder {
             element: Some(Element::Age(Age {
                 iso8601duration: iso8601duration.into(),
+                ..Default::default()
             })),
             data: PhantomData,
         }
@@ -188,24 +576,226 @@ impl<T> TimeElementBuilder<T> {
         }
     }
 
-    // TODO: add support for timestamp and interval
+    pub fn interval(self, interval: impl Build<TimeInterval>) -> TimeElementBuilder<Set> {
+        TimeElementBuilder {
+            element: Some(Element::Interval(interval.build())),
+            data: PhantomData,
+        }
+    }
+
+    /// Set the time interval from its `start` and `end` ISO8601 timestamps,
+    /// such as `2021-11-03` or `2021-11-03T10:15:00Z`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `start` or `end` is not a valid ISO8601 timestamp.
+    ///
+    /// # Example
+    ///
+    /// Record a hospitalization from Nov 3rd to Nov 10th, 2021:
+    ///
+    /// ```
+    /// use phenopacket_builder::{Build, Buildable};
+    /// use phenopackets::schema::v2::core::time_element::Element;
+    /// use phenopackets::schema::v2::core::TimeElement;
+    ///
+    /// let hospitalization: TimeElement = TimeElement::builder()
+    ///                                      .interval_iso8601("2021-11-03", "2021-11-10")
+    ///                                      .expect("well formatted timestamps")
+    ///                                      .build();
+    ///
+    /// let Some(Element::Interval(interval)) = hospitalization.element else {
+    ///     panic!("expected an interval");
+    /// };
+    /// assert_eq!(&interval.start.unwrap().to_string(), "2021-11-03T00:00:00Z");
+    /// assert_eq!(&interval.end.unwrap().to_string(), "2021-11-10T00:00:00Z");
+    /// ```
+    pub fn interval_iso8601(
+        self,
+        start: impl AsRef<str>,
+        end: impl AsRef<str>,
+    ) -> Result<TimeElementBuilder<Set>, TimestampParseError> {
+        Ok(self.interval(
+            TimeInterval::builder()
+                .start(prost_types::Timestamp::builder().iso8601timestamp(start)?)
+                .end(prost_types::Timestamp::builder().iso8601timestamp(end)?),
+        ))
+    }
 }
 
 impl Buildable for TimeElement {
     type Builder = TimeElementBuilder;
 }
 
+/// Conversion of the values a [`TimeElement`] can hold into the time element.
+///
+/// The trait stands in for `From` implementations,
+/// which cannot be added for the Phenopacket Schema types outside the `phenopackets` crate.
+///
+/// # Example
+///
+/// ```
+/// use phenopacket_builder::{Build, Buildable};
+/// use phenopacket_builder::v2::IntoTimeElement;
+/// use phenopackets::schema::v2::core::time_element::Element;
+/// use phenopackets::schema::v2::core::Age;
+///
+/// let age: Age = Age::builder().iso8601duration("P70Y").build();
+///
+/// let te = age.clone().into_time_element();
+///
+/// assert_eq!(te.element, Some(Element::Age(age)));
+/// ```
+pub trait IntoTimeElement {
+    fn into_time_element(self) -> TimeElement;
+}
+
+impl IntoTimeElement for TimeElement {
+    fn into_time_element(self) -> TimeElement {
+        self
+    }
+}
+
+impl IntoTimeElement for TimeElementBuilder<Set> {
+    fn into_time_element(self) -> TimeElement {
+        self.build()
+    }
+}
+
+impl IntoTimeElement for Age {
+    #[allow(clippy::needless_update)]
+    fn into_time_element(self) -> TimeElement {
+        TimeElement {
+            element: Some(Element::Age(self)),
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl IntoTimeElement for chrono::DateTime<chrono::Utc> {
+    #[allow(clippy::needless_update)]
+    fn into_time_element(self) -> TimeElement {
+        TimeElement {
+            element: Some(Element::Timestamp(
+                prost_types::Timestamp::builder().date_time(self).build(),
+            )),
+            ..Default::default()
+        }
+    }
+}
+
+impl IntoTimeElement for GestationalAge {
+    #[allow(clippy::needless_update)]
+    fn into_time_element(self) -> TimeElement {
+        TimeElement {
+            element: Some(Element::GestationalAge(self)),
+            ..Default::default()
+        }
+    }
+}
+
+impl IntoTimeElement for prost_types::Timestamp {
+    #[allow(clippy::needless_update)]
+    fn into_time_element(self) -> TimeElement {
+        TimeElement {
+            element: Some(Element::Timestamp(self)),
+            ..Default::default()
+        }
+    }
+}
+
+impl IntoTimeElement for OntologyClass {
+    #[allow(clippy::needless_update)]
+    fn into_time_element(self) -> TimeElement {
+        TimeElement {
+            element: Some(Element::OntologyClass(self)),
+            ..Default::default()
+        }
+    }
+}
+
+impl IntoTimeElement for TimeInterval {
+    #[allow(clippy::needless_update)]
+    fn into_time_element(self) -> TimeElement {
+        TimeElement {
+            element: Some(Element::Interval(self)),
+            ..Default::default()
+        }
+    }
+}
+
+impl<T> TryBuild<TimeElement> for TimeElementBuilder<T> {
+    #[allow(clippy::needless_update)]
+    fn try_build(self) -> Result<TimeElement, BuildError> {
+        self.element
+            .map(|e| TimeElement {
+                element: Some(e),
+                ..Default::default()
+            })
+            .ok_or(BuildError::MissingField("element"))
+    }
+}
+
 impl Build<TimeElement> for TimeElementBuilder<Set> {
     fn build(self) -> TimeElement {
-        self.element
-            .map(|e| TimeElement { element: Some(e) })
-            .expect("element must have been set")
+        self.try_build().unwrap_or_else(|e| panic!("{e}"))
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct TimeIntervalBuilder<T = Unset, U = Unset> {
+    start: Option<prost_types::Timestamp>,
+    end: Option<prost_types::Timestamp>,
+    data: PhantomData<(T, U)>,
+}
+
+impl<U> TimeIntervalBuilder<Unset, U> {
+    pub fn start(self, start: impl Build<prost_types::Timestamp>) -> TimeIntervalBuilder<Set, U> {
+        TimeIntervalBuilder {
+            start: Some(start.build()),
+            end: self.end,
+            data: PhantomData,
+        }
+    }
+}
+
+impl<T> TimeIntervalBuilder<T, Unset> {
+    pub fn end(self, end: impl Build<prost_types::Timestamp>) -> TimeIntervalBuilder<T, Set> {
+        TimeIntervalBuilder {
+            start: self.start,
+            end: Some(end.build()),
+            data: PhantomData,
+        }
+    }
+}
+
+impl Buildable for TimeInterval {
+    type Builder = TimeIntervalBuilder;
+}
+
+impl<T, U> TryBuild<TimeInterval> for TimeIntervalBuilder<T, U> {
+    #[allow(clippy::needless_update)]
+    fn try_build(self) -> Result<TimeInterval, BuildError> {
+        Ok(TimeInterval {
+            start: Some(self.start.ok_or(BuildError::MissingField("start"))?),
+            end: Some(self.end.ok_or(BuildError::MissingField("end"))?),
+            ..Default::default()
+        })
+    }
+}
+
+impl Build<TimeInterval> for TimeIntervalBuilder<Set, Set> {
+    fn build(self) -> TimeInterval {
+        self.try_build().unwrap_or_else(|e| panic!("{e}"))
     }
 }
 
 #[derive(Debug, Default, Clone, PartialEq)]
 pub struct AgeBuilder<T = Unset> {
     iso8601duration: Option<String>,
+    components: Option<AgeComponents>,
+    non_negative: bool,
     data: PhantomData<T>,
 }
 
@@ -238,21 +828,226 @@ impl AgeBuilder<Unset> {
     pub fn iso8601duration(self, iso8601duration: impl Into<String>) -> AgeBuilder<Set> {
         AgeBuilder {
             iso8601duration: Some(iso8601duration.into()),
+            components: None,
+            non_negative: self.non_negative,
+            data: PhantomData,
+        }
+    }
+
+    /// Set the age value as ISO8601 duration, checking the value first.
+    ///
+    /// Use [`AgeBuilder::iso8601duration`] to set the value without the checks.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AgeError::Malformed`] if the value is not an ISO8601 duration
+    /// with integral components, and [`AgeError::Negative`] if any component is negative.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use phenopacket_builder::Buildable;
+    /// use phenopacket_builder::v2::AgeError;
+    /// use phenopackets::schema::v2::core::Age;
+    ///
+    /// assert!(Age::builder().try_iso8601duration("P1Y").is_ok());
+    /// assert_eq!(
+    ///     Age::builder().try_iso8601duration("P-1Y"),
+    ///     Err(AgeError::Negative),
+    /// );
+    /// ```
+    pub fn try_iso8601duration(
+        self,
+        iso8601duration: impl Into<String>,
+    ) -> Result<AgeBuilder<Set>, AgeError> {
+        let iso8601duration = iso8601duration.into();
+        let duration = parse_iso8601_duration(&iso8601duration).ok_or(AgeError::Malformed)?;
+        if duration.has_negative_component() {
+            return Err(AgeError::Negative);
+        }
+        Ok(self.iso8601duration(iso8601duration))
+    }
+}
+
+impl<T> AgeBuilder<T> {
+    /// Set the years of the age.
+    ///
+    /// The components set by [`AgeBuilder::years`], [`AgeBuilder::months`], [`AgeBuilder::days`],
+    /// and [`AgeBuilder::hours`] are combined into an ISO8601 duration when building the age,
+    /// omitting the zero components. An age with all components zero is `P0D`.
+    /// The components replace a duration set by [`AgeBuilder::iso8601duration`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use phenopacket_builder::{Build, Buildable};
+    /// use phenopackets::schema::v2::core::Age;
+    ///
+    /// let age: Age = Age::builder()
+    ///                 .years(3)
+    ///                 .months(4)
+    ///                 .build();
+    ///
+    /// assert_eq!(&age.iso8601duration, "P3Y4M");
+    /// ```
+    pub fn years(self, years: u32) -> AgeBuilder<Set> {
+        self.component(|c| c.years = years)
+    }
+
+    /// Set the months of the age. See [`AgeBuilder::years`] for details.
+    pub fn months(self, months: u32) -> AgeBuilder<Set> {
+        self.component(|c| c.months = months)
+    }
+
+    /// Set the days of the age. See [`AgeBuilder::years`] for details.
+    pub fn days(self, days: u32) -> AgeBuilder<Set> {
+        self.component(|c| c.days = days)
+    }
+
+    /// Set the hours of the age. See [`AgeBuilder::years`] for details.
+    pub fn hours(self, hours: u32) -> AgeBuilder<Set> {
+        self.component(|c| c.hours = hours)
+    }
+
+    fn component(self, set: impl FnOnce(&mut AgeComponents)) -> AgeBuilder<Set> {
+        let mut components = self.components.unwrap_or_default();
+        set(&mut components);
+        AgeBuilder {
+            iso8601duration: None,
+            components: Some(components),
+            non_negative: self.non_negative,
             data: PhantomData,
         }
     }
+
+    /// Reject ages with a negative component, such as `P-1Y`, when building the age.
+    ///
+    /// # Example
+    ///
+    /// ```should_panic
+    /// use phenopacket_builder::{Build, Buildable};
+    /// use phenopackets::schema::v2::core::Age;
+    ///
+    /// let age: Age = Age::builder()
+    ///                 .non_negative()
+    ///                 .iso8601duration("P-1Y")
+    ///                 .build();
+    /// ```
+    pub fn non_negative(mut self) -> Self {
+        self.non_negative = true;
+        self
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+struct AgeComponents {
+    years: u32,
+    months: u32,
+    days: u32,
+    hours: u32,
+}
+
+impl Display for AgeComponents {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if *self == AgeComponents::default() {
+            return f.write_str("P0D");
+        }
+        f.write_str("P")?;
+        for (value, designator) in [(self.years, 'Y'), (self.months, 'M'), (self.days, 'D')] {
+            if value != 0 {
+                write!(f, "{value}{designator}")?;
+            }
+        }
+        if self.hours != 0 {
+            write!(f, "T{}H", self.hours)?;
+        }
+        Ok(())
+    }
+}
+
+/// An error returned by [`AgeBuilder::try_iso8601duration`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AgeError {
+    /// The value is not an ISO8601 duration with integral components.
+    Malformed,
+    /// The duration has a negative component.
+    Negative,
+}
+
+impl Display for AgeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AgeError::Malformed => f.write_str("the value is not an ISO8601 duration"),
+            AgeError::Negative => f.write_str("the duration must not be negative"),
+        }
+    }
+}
+
+impl Error for AgeError {}
+
+impl<T> TryBuild<Age> for AgeBuilder<T> {
+    /// # Errors
+    ///
+    /// Returns [`BuildError::NegativeDuration`] if [`AgeBuilder::non_negative`] was set
+    /// and the duration has a negative component.
+    #[allow(clippy::needless_update)]
+    fn try_build(self) -> Result<Age, BuildError> {
+        let iso8601duration = match self.components {
+            Some(components) => components.to_string(),
+            None => self
+                .iso8601duration
+                .ok_or(BuildError::MissingField("iso8601duration"))?,
+        };
+        if self.non_negative
+            && parse_iso8601_duration(&iso8601duration)
+                .is_some_and(|duration| duration.has_negative_component())
+        {
+            return Err(BuildError::NegativeDuration(iso8601duration));
+        }
+        Ok(Age {
+            iso8601duration,
+            ..Default::default()
+        })
+    }
 }
 
 impl Build<Age> for AgeBuilder<Set> {
+    /// # Panics
+    ///
+    /// Panics if [`AgeBuilder::non_negative`] was set and the duration has a negative component.
     fn build(self) -> Age {
-        Age {
-            iso8601duration: self
-                .iso8601duration
-                .expect("iso8601duration must have been set"),
+        self.try_build().unwrap_or_else(|e| panic!("{e}"))
+    }
+}
+
+/// An error returned by [`TimeElementBuilder::gestational_age_iso8601`],
+/// [`GestationalAgeBuilder::try_weeks`], and [`GestationalAgeBuilder::try_days`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GestationalAgeError {
+    /// The value is not an ISO8601 duration with weeks and days only.
+    Malformed,
+    /// The duration has a negative component.
+    Negative,
+    /// The days are not in `0..=6`.
+    DaysOutOfRange(i32),
+}
+
+impl Display for GestationalAgeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GestationalAgeError::Malformed => {
+                f.write_str("the value is not an ISO8601 duration in weeks and days")
+            }
+            GestationalAgeError::Negative => f.write_str("the duration must not be negative"),
+            GestationalAgeError::DaysOutOfRange(days) => {
+                write!(f, "gestational days {days} must be in 0..=6")
+            }
         }
     }
 }
 
+impl Error for GestationalAgeError {}
+
 #[derive(Debug, Default, Clone, PartialEq)]
 pub struct GestationalAgeBuilder<T = Unset> {
     weeks: Option<i32>,
@@ -268,6 +1063,22 @@ impl GestationalAgeBuilder<Unset> {
             data: PhantomData,
         }
     }
+
+    /// Set the weeks, checking they are not negative.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GestationalAgeError::Negative`] if the weeks are negative.
+    pub fn try_weeks(
+        self,
+        weeks: impl Into<i32>,
+    ) -> Result<GestationalAgeBuilder<Set>, GestationalAgeError> {
+        let weeks = weeks.into();
+        if weeks < 0 {
+            return Err(GestationalAgeError::Negative);
+        }
+        Ok(self.weeks(weeks))
+    }
 }
 
 impl<T> GestationalAgeBuilder<T> {
@@ -275,18 +1086,59 @@ impl<T> GestationalAgeBuilder<T> {
         self.days = Some(days.into());
         self
     }
+
+    /// Set the days, checking they are in `0..=6`, since 7 days make another week.
+    ///
+    /// Use [`GestationalAgeBuilder::days`] to set the days without the check.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GestationalAgeError::Negative`] if the days are negative,
+    /// and [`GestationalAgeError::DaysOutOfRange`] if they are greater than 6.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use phenopacket_builder::Buildable;
+    /// use phenopacket_builder::v2::GestationalAgeError;
+    /// use phenopackets::schema::v2::core::GestationalAge;
+    ///
+    /// assert!(GestationalAge::builder().weeks(25).try_days(6).is_ok());
+    /// assert_eq!(
+    ///     GestationalAge::builder().weeks(25).try_days(7),
+    ///     Err(GestationalAgeError::DaysOutOfRange(7)),
+    /// );
+    /// ```
+    pub fn try_days(
+        self,
+        days: impl Into<i32>,
+    ) -> Result<GestationalAgeBuilder<T>, GestationalAgeError> {
+        match days.into() {
+            days @ 0..=6 => Ok(self.days(days)),
+            days if days < 0 => Err(GestationalAgeError::Negative),
+            days => Err(GestationalAgeError::DaysOutOfRange(days)),
+        }
+    }
 }
 
 impl Buildable for GestationalAge {
     type Builder = GestationalAgeBuilder;
 }
 
+impl<T> TryBuild<GestationalAge> for GestationalAgeBuilder<T> {
+    #[allow(clippy::needless_update)]
+    fn try_build(self) -> Result<GestationalAge, BuildError> {
+        Ok(GestationalAge {
+            weeks: self.weeks.ok_or(BuildError::MissingField("weeks"))?,
+            days: self.days.unwrap_or(0),
+            ..Default::default()
+        })
+    }
+}
+
 impl Build<GestationalAge> for GestationalAgeBuilder<Set> {
     fn build(self) -> GestationalAge {
-        GestationalAge {
-            weeks: self.weeks.expect("weeks must have been set"),
-            days: self.days.unwrap_or(0),
-        }
+        self.try_build().unwrap_or_else(|e| panic!("{e}"))
     }
 }
 
@@ -319,11 +1171,13 @@ impl Buildable for ExternalReference {
 }
 
 impl Build<ExternalReference> for ExternalReferenceBuilder {
+    #[allow(clippy::needless_update)]
     fn build(self) -> ExternalReference {
         ExternalReference {
-            id: self.id.unwrap_or_default(),
-            reference: self.reference.unwrap_or_default(),
-            description: self.description.unwrap_or_default(),
+            id: or_default(self.id, "ExternalReferenceBuilder", "id"),
+            reference: or_default(self.reference, "ExternalReferenceBuilder", "reference"),
+            description: or_default(self.description, "ExternalReferenceBuilder", "description"),
+            ..Default::default()
         }
     }
 }
@@ -0,0 +1,315 @@
+use crate::trace::or_default;
+use crate::{Build, BuildError, Buildable, Set, TryBuild, Unset};
+use phenopackets::schema::v2::core::{
+    Biosample, File, Measurement, OntologyClass, PhenotypicFeature, Procedure, TimeElement,
+};
+use std::marker::PhantomData;
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct BiosampleBuilder<T = Unset> {
+    id: Option<String>,
+    individual_id: Option<String>,
+    derived_from_id: Option<String>,
+    description: Option<String>,
+    sampled_tissue: Option<OntologyClass>,
+    sample_type: Option<OntologyClass>,
+    phenotypic_features: Vec<PhenotypicFeature>,
+    measurements: Vec<Measurement>,
+    taxonomy: Option<OntologyClass>,
+    time_of_collection: Option<TimeElement>,
+    histological_diagnosis: Option<OntologyClass>,
+    tumor_progression: Option<OntologyClass>,
+    tumor_grade: Option<OntologyClass>,
+    pathological_stage: Option<OntologyClass>,
+    pathological_tnm_findings: Vec<OntologyClass>,
+    diagnostic_markers: Vec<OntologyClass>,
+    procedure: Option<Procedure>,
+    files: Vec<File>,
+    material_sample: Option<OntologyClass>,
+    sample_processing: Option<OntologyClass>,
+    sample_storage: Option<OntologyClass>,
+    data: PhantomData<T>,
+}
+
+impl BiosampleBuilder<Unset> {
+    pub fn id(self, id: impl Into<String>) -> BiosampleBuilder<Set> {
+        BiosampleBuilder {
+            id: Some(id.into()),
+            individual_id: self.individual_id,
+            derived_from_id: self.derived_from_id,
+            description: self.description,
+            sampled_tissue: self.sampled_tissue,
+            sample_type: self.sample_type,
+            phenotypic_features: self.phenotypic_features,
+            measurements: self.measurements,
+            taxonomy: self.taxonomy,
+            time_of_collection: self.time_of_collection,
+            histological_diagnosis: self.histological_diagnosis,
+            tumor_progression: self.tumor_progression,
+            tumor_grade: self.tumor_grade,
+            pathological_stage: self.pathological_stage,
+            pathological_tnm_findings: self.pathological_tnm_findings,
+            diagnostic_markers: self.diagnostic_markers,
+            procedure: self.procedure,
+            files: self.files,
+            material_sample: self.material_sample,
+            sample_processing: self.sample_processing,
+            sample_storage: self.sample_storage,
+            data: PhantomData,
+        }
+    }
+}
+
+impl<T> BiosampleBuilder<T> {
+    pub fn individual_id(mut self, individual_id: impl Into<String>) -> Self {
+        self.individual_id = Some(individual_id.into());
+        self
+    }
+
+    pub fn derived_from_id(mut self, derived_from_id: impl Into<String>) -> Self {
+        self.derived_from_id = Some(derived_from_id.into());
+        self
+    }
+
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    pub fn sampled_tissue(mut self, sampled_tissue: impl Build<OntologyClass>) -> Self {
+        self.sampled_tissue = Some(sampled_tissue.build());
+        self
+    }
+
+    pub fn sample_type(mut self, sample_type: impl Build<OntologyClass>) -> Self {
+        self.sample_type = Some(sample_type.build());
+        self
+    }
+
+    pub fn add_phenotypic_feature(
+        mut self,
+        phenotypic_feature: impl Build<PhenotypicFeature>,
+    ) -> Self {
+        self.phenotypic_features.push(phenotypic_feature.build());
+        self
+    }
+
+    pub fn extend_phenotypic_features(
+        mut self,
+        phenotypic_features: impl IntoIterator<Item = impl Build<PhenotypicFeature>>,
+    ) -> Self {
+        self.phenotypic_features
+            .extend(phenotypic_features.into_iter().map(Build::build));
+        self
+    }
+
+    pub fn clear_phenotypic_features(mut self) -> Self {
+        self.phenotypic_features.clear();
+        self
+    }
+
+    pub fn add_measurement(mut self, measurement: impl Build<Measurement>) -> Self {
+        self.measurements.push(measurement.build());
+        self
+    }
+
+    pub fn extend_measurements(
+        mut self,
+        measurements: impl IntoIterator<Item = impl Build<Measurement>>,
+    ) -> Self {
+        self.measurements
+            .extend(measurements.into_iter().map(Build::build));
+        self
+    }
+
+    pub fn clear_measurements(mut self) -> Self {
+        self.measurements.clear();
+        self
+    }
+
+    pub fn taxonomy(mut self, taxonomy: impl Build<OntologyClass>) -> Self {
+        self.taxonomy = Some(taxonomy.build());
+        self
+    }
+
+    pub fn homo_sapiens(self) -> Self {
+        self.taxonomy(OntologyClass::builder().id_label("NCBITaxon:9606", "homo sapiens"))
+    }
+
+    pub fn time_of_collection(mut self, time_of_collection: impl Build<TimeElement>) -> Self {
+        self.time_of_collection = Some(time_of_collection.build());
+        self
+    }
+
+    pub fn histological_diagnosis(
+        mut self,
+        histological_diagnosis: impl Build<OntologyClass>,
+    ) -> Self {
+        self.histological_diagnosis = Some(histological_diagnosis.build());
+        self
+    }
+
+    pub fn tumor_progression(mut self, tumor_progression: impl Build<OntologyClass>) -> Self {
+        self.tumor_progression = Some(tumor_progression.build());
+        self
+    }
+
+    /// Mark the biosample as a tumor sample,
+    /// setting the tumor progression to *Primary Malignant Neoplasm* (`NCIT:C84509`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use phenopacket_builder::{Build, Buildable};
+    /// use phenopackets::schema::v2::core::Biosample;
+    ///
+    /// let biosample: Biosample = Biosample::builder()
+    ///                             .id("biosample-id")
+    ///                             .tumor()
+    ///                             .build();
+    ///
+    /// assert_eq!(&biosample.tumor_progression.unwrap().id, "NCIT:C84509");
+    /// ```
+    pub fn tumor(self) -> Self {
+        self.tumor_progression(
+            OntologyClass::builder().id_label("NCIT:C84509", "Primary Malignant Neoplasm"),
+        )
+    }
+
+    /// Mark the biosample as a normal (reference) sample,
+    /// setting the tumor progression to *Normal Tissue* (`NCIT:C14165`).
+    pub fn normal(self) -> Self {
+        self.tumor_progression(OntologyClass::builder().id_label("NCIT:C14165", "Normal Tissue"))
+    }
+
+    pub fn tumor_grade(mut self, tumor_grade: impl Build<OntologyClass>) -> Self {
+        self.tumor_grade = Some(tumor_grade.build());
+        self
+    }
+
+    pub fn pathological_stage(mut self, pathological_stage: impl Build<OntologyClass>) -> Self {
+        self.pathological_stage = Some(pathological_stage.build());
+        self
+    }
+
+    pub fn add_pathological_tnm_finding(
+        mut self,
+        pathological_tnm_finding: impl Build<OntologyClass>,
+    ) -> Self {
+        self.pathological_tnm_findings
+            .push(pathological_tnm_finding.build());
+        self
+    }
+
+    pub fn extend_pathological_tnm_findings(
+        mut self,
+        pathological_tnm_findings: impl IntoIterator<Item = impl Build<OntologyClass>>,
+    ) -> Self {
+        self.pathological_tnm_findings
+            .extend(pathological_tnm_findings.into_iter().map(Build::build));
+        self
+    }
+
+    pub fn clear_pathological_tnm_findings(mut self) -> Self {
+        self.pathological_tnm_findings.clear();
+        self
+    }
+
+    pub fn add_diagnostic_marker(mut self, diagnostic_marker: impl Build<OntologyClass>) -> Self {
+        self.diagnostic_markers.push(diagnostic_marker.build());
+        self
+    }
+
+    pub fn extend_diagnostic_markers(
+        mut self,
+        diagnostic_markers: impl IntoIterator<Item = impl Build<OntologyClass>>,
+    ) -> Self {
+        self.diagnostic_markers
+            .extend(diagnostic_markers.into_iter().map(Build::build));
+        self
+    }
+
+    pub fn clear_diagnostic_markers(mut self) -> Self {
+        self.diagnostic_markers.clear();
+        self
+    }
+
+    pub fn procedure(mut self, procedure: impl Build<Procedure>) -> Self {
+        self.procedure = Some(procedure.build());
+        self
+    }
+
+    pub fn add_file(mut self, file: impl Build<File>) -> Self {
+        self.files.push(file.build());
+        self
+    }
+
+    pub fn extend_files(mut self, files: impl IntoIterator<Item = impl Build<File>>) -> Self {
+        self.files.extend(files.into_iter().map(Build::build));
+        self
+    }
+
+    pub fn clear_files(mut self) -> Self {
+        self.files.clear();
+        self
+    }
+
+    pub fn material_sample(mut self, material_sample: impl Build<OntologyClass>) -> Self {
+        self.material_sample = Some(material_sample.build());
+        self
+    }
+
+    pub fn sample_processing(mut self, sample_processing: impl Build<OntologyClass>) -> Self {
+        self.sample_processing = Some(sample_processing.build());
+        self
+    }
+
+    pub fn sample_storage(mut self, sample_storage: impl Build<OntologyClass>) -> Self {
+        self.sample_storage = Some(sample_storage.build());
+        self
+    }
+}
+
+impl Buildable for Biosample {
+    type Builder = BiosampleBuilder;
+}
+
+impl<T> TryBuild<Biosample> for BiosampleBuilder<T> {
+    #[allow(clippy::needless_update)]
+    fn try_build(self) -> Result<Biosample, BuildError> {
+        Ok(Biosample {
+            id: self.id.ok_or(BuildError::MissingField("id"))?,
+            individual_id: or_default(self.individual_id, "BiosampleBuilder", "individual_id"),
+            derived_from_id: or_default(
+                self.derived_from_id,
+                "BiosampleBuilder",
+                "derived_from_id",
+            ),
+            description: or_default(self.description, "BiosampleBuilder", "description"),
+            sampled_tissue: self.sampled_tissue,
+            sample_type: self.sample_type,
+            phenotypic_features: self.phenotypic_features,
+            measurements: self.measurements,
+            taxonomy: self.taxonomy,
+            time_of_collection: self.time_of_collection,
+            histological_diagnosis: self.histological_diagnosis,
+            tumor_progression: self.tumor_progression,
+            tumor_grade: self.tumor_grade,
+            pathological_stage: self.pathological_stage,
+            pathological_tnm_finding: self.pathological_tnm_findings,
+            diagnostic_markers: self.diagnostic_markers,
+            procedure: self.procedure,
+            files: self.files,
+            material_sample: self.material_sample,
+            sample_processing: self.sample_processing,
+            sample_storage: self.sample_storage,
+            ..Default::default()
+        })
+    }
+}
+
+impl Build<Biosample> for BiosampleBuilder<Set> {
+    fn build(self) -> Biosample {
+        self.try_build().unwrap_or_else(|e| panic!("{e}"))
+    }
+}
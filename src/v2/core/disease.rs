@@ -0,0 +1,204 @@
+use crate::v2::HpoOnset;
+use crate::{Build, BuildError, Buildable, Set, TryBuild, Unset};
+use phenopackets::schema::v2::core::{Disease, OntologyClass, TimeElement};
+use std::marker::PhantomData;
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct DiseaseBuilder<T = Unset> {
+    term: Option<OntologyClass>,
+    excluded: bool,
+    onset: Option<TimeElement>,
+    resolution: Option<TimeElement>,
+    disease_stages: Vec<OntologyClass>,
+    clinical_tnm_findings: Vec<OntologyClass>,
+    primary_site: Option<OntologyClass>,
+    laterality: Option<OntologyClass>,
+    data: PhantomData<T>,
+}
+
+impl DiseaseBuilder<Set> {
+    /// Create a builder pre-populated with the fields of an existing [`Disease`],
+    /// e.g. to add a stage to a decoded disease.
+    ///
+    /// The optional fields are taken as they are, including the empty ones.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BuildError::MissingField`] if the `term` is unset.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use phenopacket_builder::{oc, Build};
+    /// use phenopacket_builder::v2::DiseaseBuilder;
+    /// use phenopackets::schema::v2::core::Disease;
+    ///
+    /// let disease = Disease {
+    ///     term: Some(oc("MONDO:0005233", "non-small cell lung carcinoma")),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let disease: Disease = DiseaseBuilder::from_disease(disease)
+    ///                         .expect("the term is set")
+    ///                         .add_disease_stage(oc("NCIT:C27977", "Stage IIIA"))
+    ///                         .build();
+    ///
+    /// assert_eq!(&disease.term.unwrap().id, "MONDO:0005233");
+    /// assert_eq!(disease.disease_stage.len(), 1);
+    /// ```
+    pub fn from_disease(disease: Disease) -> Result<DiseaseBuilder<Set>, BuildError> {
+        Ok(DiseaseBuilder {
+            term: Some(disease.term.ok_or(BuildError::MissingField("term"))?),
+            excluded: disease.excluded,
+            onset: disease.onset,
+            resolution: disease.resolution,
+            disease_stages: disease.disease_stage,
+            clinical_tnm_findings: disease.clinical_tnm_finding,
+            primary_site: disease.primary_site,
+            laterality: disease.laterality,
+            data: PhantomData,
+        })
+    }
+}
+
+impl DiseaseBuilder<Unset> {
+    pub fn term(self, term: impl Build<OntologyClass>) -> DiseaseBuilder<Set> {
+        DiseaseBuilder {
+            term: Some(term.build()),
+            excluded: self.excluded,
+            onset: self.onset,
+            resolution: self.resolution,
+            disease_stages: self.disease_stages,
+            clinical_tnm_findings: self.clinical_tnm_findings,
+            primary_site: self.primary_site,
+            laterality: self.laterality,
+            data: PhantomData,
+        }
+    }
+}
+
+impl<T> DiseaseBuilder<T> {
+    pub fn observed(mut self) -> Self {
+        self.excluded = false;
+        self
+    }
+
+    pub fn excluded(mut self) -> Self {
+        self.excluded = true;
+        self
+    }
+
+    pub fn onset(mut self, onset: impl Build<TimeElement>) -> Self {
+        self.onset = Some(onset.build());
+        self
+    }
+
+    /// Set the onset to *Congenital onset* (`HP:0003577`).
+    pub fn onset_congenital(self) -> Self {
+        self.onset(HpoOnset::Congenital)
+    }
+
+    /// Set the onset to *Neonatal onset* (`HP:0003623`).
+    pub fn onset_neonatal(self) -> Self {
+        self.onset(HpoOnset::Neonatal)
+    }
+
+    /// Set the onset to *Infantile onset* (`HP:0003593`).
+    pub fn onset_infantile(self) -> Self {
+        self.onset(HpoOnset::Infantile)
+    }
+
+    /// Set the onset to *Childhood onset* (`HP:0011463`).
+    pub fn onset_childhood(self) -> Self {
+        self.onset(HpoOnset::Childhood)
+    }
+
+    /// Set the onset to *Adult onset* (`HP:0003581`).
+    pub fn onset_adult(self) -> Self {
+        self.onset(HpoOnset::Adult)
+    }
+
+    pub fn resolution(mut self, resolution: impl Build<TimeElement>) -> Self {
+        self.resolution = Some(resolution.build());
+        self
+    }
+
+    pub fn add_disease_stage(mut self, disease_stage: impl Build<OntologyClass>) -> Self {
+        self.disease_stages.push(disease_stage.build());
+        self
+    }
+
+    pub fn extend_disease_stages(
+        mut self,
+        disease_stages: impl IntoIterator<Item = impl Build<OntologyClass>>,
+    ) -> Self {
+        self.disease_stages
+            .extend(disease_stages.into_iter().map(Build::build));
+        self
+    }
+
+    pub fn clear_disease_stages(mut self) -> Self {
+        self.disease_stages.clear();
+        self
+    }
+
+    pub fn add_clinical_tnm_finding(
+        mut self,
+        clinical_tnm_finding: impl Build<OntologyClass>,
+    ) -> Self {
+        self.clinical_tnm_findings
+            .push(clinical_tnm_finding.build());
+        self
+    }
+
+    pub fn extend_clinical_tnm_findings(
+        mut self,
+        clinical_tnm_findings: impl IntoIterator<Item = impl Build<OntologyClass>>,
+    ) -> Self {
+        self.clinical_tnm_findings
+            .extend(clinical_tnm_findings.into_iter().map(Build::build));
+        self
+    }
+
+    pub fn clear_clinical_tnm_findings(mut self) -> Self {
+        self.clinical_tnm_findings.clear();
+        self
+    }
+
+    pub fn primary_site(mut self, primary_site: impl Build<OntologyClass>) -> Self {
+        self.primary_site = Some(primary_site.build());
+        self
+    }
+
+    pub fn laterality(mut self, laterality: impl Build<OntologyClass>) -> Self {
+        self.laterality = Some(laterality.build());
+        self
+    }
+}
+
+impl Buildable for Disease {
+    type Builder = DiseaseBuilder;
+}
+
+impl<T> TryBuild<Disease> for DiseaseBuilder<T> {
+    #[allow(clippy::needless_update)]
+    fn try_build(self) -> Result<Disease, BuildError> {
+        Ok(Disease {
+            term: Some(self.term.ok_or(BuildError::MissingField("term"))?),
+            excluded: self.excluded,
+            onset: self.onset,
+            resolution: self.resolution,
+            disease_stage: self.disease_stages,
+            clinical_tnm_finding: self.clinical_tnm_findings,
+            primary_site: self.primary_site,
+            laterality: self.laterality,
+            ..Default::default()
+        })
+    }
+}
+
+impl Build<Disease> for DiseaseBuilder<Set> {
+    fn build(self) -> Disease {
+        self.try_build().unwrap_or_else(|e| panic!("{e}"))
+    }
+}
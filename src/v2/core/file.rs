@@ -0,0 +1,121 @@
+use crate::{Build, BuildError, Buildable, Set, TryBuild, Unset};
+use phenopackets::schema::v2::core::File;
+use std::marker::PhantomData;
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct FileBuilder<T = Unset> {
+    uri: Option<String>,
+    individual_to_file_identifiers: Vec<(String, String)>,
+    file_attributes: Vec<(String, String)>,
+    data: PhantomData<T>,
+}
+
+impl FileBuilder<Unset> {
+    pub fn uri(self, uri: impl Into<String>) -> FileBuilder<Set> {
+        FileBuilder {
+            uri: Some(uri.into()),
+            individual_to_file_identifiers: self.individual_to_file_identifiers,
+            file_attributes: self.file_attributes,
+            data: PhantomData,
+        }
+    }
+}
+
+impl<T> FileBuilder<T> {
+    /// Map the id of an individual or a biosample to its identifier in the file,
+    /// such as the sample name in a VCF header.
+    ///
+    /// A later mapping of the same individual replaces the earlier one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use phenopacket_builder::{Build, Buildable};
+    /// use phenopackets::schema::v2::core::File;
+    ///
+    /// let file: File = File::builder()
+    ///                   .uri("file:///data/genomes/proband.vcf.gz")
+    ///                   .add_individual_file_identifier("proband A", "NA12345")
+    ///                   .add_file_attribute("fileFormat", "vcf")
+    ///                   .build();
+    ///
+    /// assert_eq!(&file.individual_to_file_identifiers["proband A"], "NA12345");
+    /// assert_eq!(&file.file_attributes["fileFormat"], "vcf");
+    /// ```
+    pub fn add_individual_file_identifier(
+        mut self,
+        individual: impl Into<String>,
+        identifier: impl Into<String>,
+    ) -> Self {
+        self.individual_to_file_identifiers
+            .push((individual.into(), identifier.into()));
+        self
+    }
+
+    pub fn extend_individual_file_identifiers(
+        mut self,
+        individual_to_file_identifiers: impl IntoIterator<Item = (impl Into<String>, impl Into<String>)>,
+    ) -> Self {
+        self.individual_to_file_identifiers.extend(
+            individual_to_file_identifiers
+                .into_iter()
+                .map(|(individual, identifier)| (individual.into(), identifier.into())),
+        );
+        self
+    }
+
+    pub fn clear_individual_file_identifiers(mut self) -> Self {
+        self.individual_to_file_identifiers.clear();
+        self
+    }
+
+    /// Add a file attribute, such as `genomeAssembly`.
+    ///
+    /// A later attribute with the same key replaces the earlier one.
+    pub fn add_file_attribute(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.file_attributes.push((key.into(), value.into()));
+        self
+    }
+
+    pub fn extend_file_attributes(
+        mut self,
+        file_attributes: impl IntoIterator<Item = (impl Into<String>, impl Into<String>)>,
+    ) -> Self {
+        self.file_attributes.extend(
+            file_attributes
+                .into_iter()
+                .map(|(key, value)| (key.into(), value.into())),
+        );
+        self
+    }
+
+    pub fn clear_file_attributes(mut self) -> Self {
+        self.file_attributes.clear();
+        self
+    }
+}
+
+impl Buildable for File {
+    type Builder = FileBuilder;
+}
+
+impl<T> TryBuild<File> for FileBuilder<T> {
+    #[allow(clippy::needless_update)]
+    fn try_build(self) -> Result<File, BuildError> {
+        Ok(File {
+            uri: self.uri.ok_or(BuildError::MissingField("uri"))?,
+            individual_to_file_identifiers: self
+                .individual_to_file_identifiers
+                .into_iter()
+                .collect(),
+            file_attributes: self.file_attributes.into_iter().collect(),
+            ..Default::default()
+        })
+    }
+}
+
+impl Build<File> for FileBuilder<Set> {
+    fn build(self) -> File {
+        self.try_build().unwrap_or_else(|e| panic!("{e}"))
+    }
+}
@@ -1,9 +1,13 @@
-use crate::{Build, Buildable, Set, Unset};
+use crate::redact::{redact_all, redact_option};
+use crate::trace::or_default;
+use crate::v2::{IntoTimeElement, TimestampParseError};
+use crate::{Build, BuildError, Buildable, Set, TryBuild, Unset};
 use phenopackets::schema::v2::core::time_element::Element;
 use phenopackets::schema::v2::core::vital_status::Status;
 use phenopackets::schema::v2::core::{
     Age, GestationalAge, Individual, KaryotypicSex, OntologyClass, Sex, TimeElement, VitalStatus,
 };
+use std::fmt::{Debug, Formatter};
 use std::marker::PhantomData;
 
 #[derive(Debug, Default, Clone, PartialEq)]
@@ -39,11 +43,52 @@ impl<T> IndividualBuilder<T> {
         self
     }
 
+    /// Replace the alternate ids added so far with the `ids`.
+    pub fn set_alternate_ids(self, ids: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.clear_alternate_ids().extend_alternate_ids(ids)
+    }
+
     pub fn date_of_birth(mut self, date: impl Build<prost_types::Timestamp>) -> Self {
         self.date_of_birth = Some(date.build());
         self
     }
 
+    /// Set the date of birth from an ISO8601 timestamp, such as `2018-03-01`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the timestamp is malformed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use phenopacket_builder::{Build, Buildable};
+    /// use phenopackets::schema::v2::core::Individual;
+    ///
+    /// let individual: Individual = Individual::builder()
+    ///                                .id("individual-id")
+    ///                                .date_of_birth_iso8601("2018-03-01")
+    ///                                .expect("a well formatted timestamp")
+    ///                                .build();
+    ///
+    /// assert_eq!(&individual.date_of_birth.unwrap().to_string(), "2018-03-01T00:00:00Z");
+    /// ```
+    pub fn date_of_birth_iso8601(self, date: impl AsRef<str>) -> Result<Self, TimestampParseError> {
+        Ok(self.date_of_birth(prost_types::Timestamp::builder().iso8601timestamp(date)?))
+    }
+
+    /// Set the date of birth to midnight UTC of the calendar date.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the month is not in `1..=12` or if the day is not in the month.
+    pub fn date_of_birth_ymd(self, year: i32, month: u32, day: u32) -> Self {
+        let date = prost_types::Timestamp::builder()
+            .ymd(year, month, day)
+            .unwrap_or_else(|e| panic!("{e}"));
+        self.date_of_birth(date)
+    }
+
     pub fn time_at_last_encounter(
         mut self,
         time_at_last_encounter: impl Build<TimeElement>,
@@ -52,6 +97,56 @@ impl<T> IndividualBuilder<T> {
         self
     }
 
+    /// Set the time at last encounter to an age, such as `P3Y4M`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use phenopacket_builder::{Build, Buildable};
+    /// use phenopackets::schema::v2::core::{Individual, TimeElement};
+    ///
+    /// let short: Individual = Individual::builder()
+    ///                             .id("subject-id")
+    ///                             .age_at_last_encounter("P3Y4M")
+    ///                             .build();
+    /// let long: Individual = Individual::builder()
+    ///                             .id("subject-id")
+    ///                             .time_at_last_encounter(TimeElement::builder().age_iso8601duration("P3Y4M"))
+    ///                             .build();
+    ///
+    /// assert_eq!(short, long);
+    /// ```
+    pub fn age_at_last_encounter(self, iso8601duration: impl Into<String>) -> Self {
+        self.time_at_last_encounter(TimeElement::builder().age_iso8601duration(iso8601duration))
+    }
+
+    /// Set the time at last encounter to a gestational age.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use phenopacket_builder::{Build, Buildable};
+    /// use phenopackets::schema::v2::core::{Individual, TimeElement};
+    ///
+    /// let short: Individual = Individual::builder()
+    ///                             .id("subject-id")
+    ///                             .age_at_last_encounter_gestational(25, 5)
+    ///                             .build();
+    /// let long: Individual = Individual::builder()
+    ///                             .id("subject-id")
+    ///                             .time_at_last_encounter(TimeElement::builder().gestational_age_weeks_days(25, 5))
+    ///                             .build();
+    ///
+    /// assert_eq!(short, long);
+    /// ```
+    pub fn age_at_last_encounter_gestational(
+        self,
+        weeks: impl Into<i32>,
+        days: impl Into<i32>,
+    ) -> Self {
+        self.time_at_last_encounter(TimeElement::builder().gestational_age_weeks_days(weeks, days))
+    }
+
     pub fn vital_status(mut self, vital_status: impl Build<VitalStatus>) -> Self {
         self.vital_status = Some(vital_status.build());
         self
@@ -62,7 +157,7 @@ impl<T> IndividualBuilder<T> {
         self
     }
 
-    pub fn deceased_at(mut self, time_of_death: impl Into<TimeElement>) -> Self {
+    pub fn deceased_at(mut self, time_of_death: impl IntoTimeElement) -> Self {
         self.vital_status = Some(
             VitalStatus::builder()
                 .deceased()
@@ -94,6 +189,21 @@ impl<T> IndividualBuilder<T> {
         self.sex(Sex::OtherSex)
     }
 
+    /// Set the sex based on the karyotypic sex set so far.
+    ///
+    /// This is an optional heuristic, which should only be used when the phenotypic sex is not known.
+    /// The mapping is conservative: `XX` is female, `XY` is male,
+    /// an unknown karyotype is unknown sex, and all other karyotypes are other sex.
+    pub fn infer_sex_from_karyotype(self) -> Self {
+        let sex = match self.karyotypic_sex {
+            KaryotypicSex::Xx => Sex::Female,
+            KaryotypicSex::Xy => Sex::Male,
+            KaryotypicSex::UnknownKaryotype => Sex::UnknownSex,
+            _ => Sex::OtherSex,
+        };
+        self.sex(sex)
+    }
+
     pub fn karyotypic_sex(mut self, karyotypic_sex: impl Into<KaryotypicSex>) -> Self {
         self.karyotypic_sex = karyotypic_sex.into();
         self
@@ -148,6 +258,45 @@ impl<T> IndividualBuilder<T> {
     pub fn homo_sapiens(self) -> Self {
         self.taxonomy(OntologyClass::builder().id_label("NCBITaxon:9606", "homo sapiens"))
     }
+
+    /// Get a [`Debug`] representation of the builder that is safe for logging,
+    /// with the `id`, `alternate_ids`, and `date_of_birth` masked.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use phenopacket_builder::Buildable;
+    /// use phenopackets::schema::v2::core::Individual;
+    ///
+    /// let builder = Individual::builder().id("individual-id").male();
+    ///
+    /// let debug = format!("{:?}", builder.redacted_debug());
+    ///
+    /// assert!(!debug.contains("individual-id"));
+    /// assert!(debug.contains("Male"));
+    /// ```
+    pub fn redacted_debug(&self) -> impl Debug + '_ {
+        RedactedIndividualBuilder(self)
+    }
+}
+
+struct RedactedIndividualBuilder<'a, T>(&'a IndividualBuilder<T>);
+
+impl<T> Debug for RedactedIndividualBuilder<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let builder = self.0;
+        f.debug_struct("IndividualBuilder")
+            .field("id", &redact_option(&builder.id))
+            .field("alternate_ids", &redact_all(&builder.alternate_ids))
+            .field("date_of_birth", &redact_option(&builder.date_of_birth))
+            .field("time_at_last_encounter", &builder.time_at_last_encounter)
+            .field("vital_status", &builder.vital_status)
+            .field("sex", &builder.sex)
+            .field("karyotypic_sex", &builder.karyotypic_sex)
+            .field("gender", &builder.gender)
+            .field("taxonomy", &builder.taxonomy)
+            .finish()
+    }
 }
 
 impl IndividualBuilder<Unset> {
@@ -170,10 +319,31 @@ impl Buildable for Individual {
     type Builder = IndividualBuilder;
 }
 
-impl Build<Individual> for IndividualBuilder<Set> {
-    fn build(self) -> Individual {
-        Individual {
-            id: self.id.expect("id must have been set"),
+/// Create a builder pre-populated with the fields of an existing [`Individual`].
+///
+/// The fields are taken as they are, including the empty ones.
+impl From<Individual> for IndividualBuilder<Set> {
+    fn from(individual: Individual) -> Self {
+        IndividualBuilder {
+            id: Some(individual.id),
+            alternate_ids: individual.alternate_ids,
+            date_of_birth: individual.date_of_birth,
+            time_at_last_encounter: individual.time_at_last_encounter,
+            vital_status: individual.vital_status,
+            sex: Sex::try_from(individual.sex).unwrap_or_default(),
+            karyotypic_sex: KaryotypicSex::try_from(individual.karyotypic_sex).unwrap_or_default(),
+            gender: individual.gender,
+            taxonomy: individual.taxonomy,
+            data: PhantomData,
+        }
+    }
+}
+
+impl<T> TryBuild<Individual> for IndividualBuilder<T> {
+    #[allow(clippy::needless_update)]
+    fn try_build(self) -> Result<Individual, BuildError> {
+        Ok(Individual {
+            id: self.id.ok_or(BuildError::MissingField("id"))?,
             alternate_ids: self.alternate_ids,
             date_of_birth: self.date_of_birth,
             time_at_last_encounter: self.time_at_last_encounter,
@@ -182,7 +352,14 @@ impl Build<Individual> for IndividualBuilder<Set> {
             karyotypic_sex: self.karyotypic_sex.into(),
             gender: self.gender,
             taxonomy: self.taxonomy,
-        }
+            ..Default::default()
+        })
+    }
+}
+
+impl Build<Individual> for IndividualBuilder<Set> {
+    fn build(self) -> Individual {
+        self.try_build().unwrap_or_else(|e| panic!("{e}"))
     }
 }
 
@@ -195,6 +372,43 @@ pub struct VitalStatusBuilder<T = Unset> {
     data: PhantomData<T>,
 }
 
+impl VitalStatusBuilder<Set> {
+    /// Create a builder pre-populated with the fields of an existing [`VitalStatus`],
+    /// e.g. to add the cause of death to a decoded status.
+    ///
+    /// A status that is not a known [`Status`] is read as [`Status::UnknownStatus`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use phenopacket_builder::{oc, Build};
+    /// use phenopacket_builder::v2::VitalStatusBuilder;
+    /// use phenopackets::schema::v2::core::vital_status::Status;
+    /// use phenopackets::schema::v2::core::VitalStatus;
+    ///
+    /// let vital_status = VitalStatus {
+    ///     status: Status::Deceased.into(),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let vital_status: VitalStatus = VitalStatusBuilder::from_vital_status(vital_status)
+    ///                                   .cause_of_death(oc("MONDO:0005233", "non-small cell lung carcinoma"))
+    ///                                   .build();
+    ///
+    /// assert_eq!(vital_status.status(), Status::Deceased);
+    /// assert!(vital_status.cause_of_death.is_some());
+    /// ```
+    pub fn from_vital_status(vital_status: VitalStatus) -> VitalStatusBuilder<Set> {
+        VitalStatusBuilder {
+            status: Some(Status::try_from(vital_status.status).unwrap_or_default()),
+            time_of_death: vital_status.time_of_death,
+            cause_of_death: vital_status.cause_of_death,
+            survival_time_in_days: Some(vital_status.survival_time_in_days),
+            data: PhantomData,
+        }
+    }
+}
+
 impl<T> VitalStatusBuilder<T> {
     pub fn status(self, status: impl Into<Status>) -> VitalStatusBuilder<Set> {
         VitalStatusBuilder {
@@ -214,24 +428,49 @@ impl<T> VitalStatusBuilder<T> {
         self.status(Status::Deceased)
     }
 
-    pub fn time_of_death(mut self, time_of_death: impl Into<TimeElement>) -> VitalStatusBuilder<T> {
-        self.time_of_death = Some(time_of_death.into());
+    /// Set the status to deceased, with the time of death at an age, such as `P63Y`.
+    #[allow(clippy::needless_update)]
+    pub fn deceased_at_age_iso8601(self, duration: impl Into<String>) -> VitalStatusBuilder<Set> {
+        self.deceased().time_of_death_at_age(Age {
+            iso8601duration: duration.into(),
+            ..Default::default()
+        })
+    }
+
+    /// Set the status to deceased, with the time of death at a timestamp.
+    #[allow(clippy::needless_update)]
+    pub fn deceased_at_timestamp(
+        self,
+        timestamp: impl Build<prost_types::Timestamp>,
+    ) -> VitalStatusBuilder<Set> {
+        self.deceased().time_of_death(TimeElement {
+            element: Some(Element::Timestamp(timestamp.build())),
+            ..Default::default()
+        })
+    }
+
+    pub fn time_of_death(mut self, time_of_death: impl IntoTimeElement) -> VitalStatusBuilder<T> {
+        self.time_of_death = Some(time_of_death.into_time_element());
         self
     }
 
+    #[allow(clippy::needless_update)]
     pub fn time_of_death_at_age(mut self, age: impl Into<Age>) -> VitalStatusBuilder<T> {
         self.time_of_death = Some(TimeElement {
             element: Some(Element::Age(age.into())),
+            ..Default::default()
         });
         self
     }
 
+    #[allow(clippy::needless_update)]
     pub fn time_of_death_at_gestational_age(
         mut self,
         gestational_age: impl Into<GestationalAge>,
     ) -> VitalStatusBuilder<T> {
         self.time_of_death = Some(TimeElement {
             element: Some(Element::GestationalAge(gestational_age.into())),
+            ..Default::default()
         });
         self
     }
@@ -257,13 +496,28 @@ impl Buildable for VitalStatus {
     type Builder = VitalStatusBuilder;
 }
 
-impl Build<VitalStatus> for VitalStatusBuilder<Set> {
-    fn build(self) -> VitalStatus {
-        VitalStatus {
-            status: self.status.expect("status must have been set").into(),
+impl<T> TryBuild<VitalStatus> for VitalStatusBuilder<T> {
+    #[allow(clippy::needless_update)]
+    fn try_build(self) -> Result<VitalStatus, BuildError> {
+        Ok(VitalStatus {
+            status: self
+                .status
+                .ok_or(BuildError::MissingField("status"))?
+                .into(),
             time_of_death: self.time_of_death,
             cause_of_death: self.cause_of_death,
-            survival_time_in_days: self.survival_time_in_days.unwrap_or_default(),
-        }
+            survival_time_in_days: or_default(
+                self.survival_time_in_days,
+                "VitalStatusBuilder",
+                "survival_time_in_days",
+            ),
+            ..Default::default()
+        })
+    }
+}
+
+impl Build<VitalStatus> for VitalStatusBuilder<Set> {
+    fn build(self) -> VitalStatus {
+        self.try_build().unwrap_or_else(|e| panic!("{e}"))
     }
 }
@@ -0,0 +1,277 @@
+use crate::trace::or_default;
+use crate::{Build, BuildError, Buildable, Set, TryBuild, Unset};
+use phenopackets::ga4gh::vrsatile::v1::GeneDescriptor;
+use phenopackets::schema::v2::core::genomic_interpretation::{Call, InterpretationStatus};
+use phenopackets::schema::v2::core::interpretation::ProgressStatus;
+use phenopackets::schema::v2::core::{
+    Diagnosis, GenomicInterpretation, Interpretation, VariantInterpretation,
+};
+use std::marker::PhantomData;
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct InterpretationBuilder<T = Unset, U = Unset> {
+    id: Option<String>,
+    progress_status: Option<ProgressStatus>,
+    diagnosis: Option<Diagnosis>,
+    summary: Option<String>,
+    data: PhantomData<(T, U)>,
+}
+
+impl InterpretationBuilder<Set, Set> {
+    /// Create a builder pre-populated with the fields of an existing [`Interpretation`],
+    /// e.g. to mark a decoded interpretation as solved.
+    ///
+    /// A progress status that is not a known [`ProgressStatus`]
+    /// is read as [`ProgressStatus::UnknownProgress`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use phenopacket_builder::Build;
+    /// use phenopacket_builder::v2::InterpretationBuilder;
+    /// use phenopackets::schema::v2::core::interpretation::ProgressStatus;
+    /// use phenopackets::schema::v2::core::Interpretation;
+    ///
+    /// let interpretation = Interpretation {
+    ///     id: "interpretation-id".into(),
+    ///     progress_status: ProgressStatus::InProgress.into(),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let interpretation: Interpretation = InterpretationBuilder::from_interpretation(interpretation)
+    ///                                       .set_status(ProgressStatus::Solved)
+    ///                                       .build();
+    ///
+    /// assert_eq!(interpretation.progress_status(), ProgressStatus::Solved);
+    /// ```
+    pub fn from_interpretation(interpretation: Interpretation) -> InterpretationBuilder<Set, Set> {
+        InterpretationBuilder {
+            id: Some(interpretation.id),
+            progress_status: Some(
+                ProgressStatus::try_from(interpretation.progress_status).unwrap_or_default(),
+            ),
+            diagnosis: interpretation.diagnosis,
+            summary: Some(interpretation.summary),
+            data: PhantomData,
+        }
+    }
+}
+
+impl<U> InterpretationBuilder<Unset, U> {
+    pub fn id(self, id: impl Into<String>) -> InterpretationBuilder<Set, U> {
+        InterpretationBuilder {
+            id: Some(id.into()),
+            progress_status: self.progress_status,
+            diagnosis: self.diagnosis,
+            summary: self.summary,
+            data: PhantomData,
+        }
+    }
+}
+
+impl<T> InterpretationBuilder<T, Unset> {
+    pub fn progress_status(
+        self,
+        progress_status: impl Into<ProgressStatus>,
+    ) -> InterpretationBuilder<T, Set> {
+        InterpretationBuilder {
+            id: self.id,
+            progress_status: Some(progress_status.into()),
+            diagnosis: self.diagnosis,
+            summary: self.summary,
+            data: PhantomData,
+        }
+    }
+
+    pub fn in_progress(self) -> InterpretationBuilder<T, Set> {
+        self.progress_status(ProgressStatus::InProgress)
+    }
+
+    pub fn completed(self) -> InterpretationBuilder<T, Set> {
+        self.progress_status(ProgressStatus::Completed)
+    }
+
+    pub fn solved(self) -> InterpretationBuilder<T, Set> {
+        self.progress_status(ProgressStatus::Solved)
+    }
+
+    pub fn unsolved(self) -> InterpretationBuilder<T, Set> {
+        self.progress_status(ProgressStatus::Unsolved)
+    }
+}
+
+impl<T> InterpretationBuilder<T, Set> {
+    /// Update the progress status set before.
+    pub fn set_status(mut self, progress_status: impl Into<ProgressStatus>) -> Self {
+        self.progress_status = Some(progress_status.into());
+        self
+    }
+}
+
+impl<T, U> InterpretationBuilder<T, U> {
+    pub fn diagnosis(mut self, diagnosis: impl Build<Diagnosis>) -> Self {
+        self.diagnosis = Some(diagnosis.build());
+        self
+    }
+
+    /// Add a genomic interpretation to the diagnosis.
+    ///
+    /// A diagnosis with no disease is created, if the diagnosis has not been set yet.
+    pub fn add_genomic_interpretation(
+        mut self,
+        genomic_interpretation: impl Build<GenomicInterpretation>,
+    ) -> Self {
+        self.diagnosis
+            .get_or_insert_with(Diagnosis::default)
+            .genomic_interpretations
+            .push(genomic_interpretation.build());
+        self
+    }
+
+    pub fn summary(mut self, summary: impl Into<String>) -> Self {
+        self.summary = Some(summary.into());
+        self
+    }
+}
+
+impl Buildable for Interpretation {
+    type Builder = InterpretationBuilder;
+}
+
+impl<T, U> TryBuild<Interpretation> for InterpretationBuilder<T, U> {
+    #[allow(clippy::needless_update)]
+    fn try_build(self) -> Result<Interpretation, BuildError> {
+        Ok(Interpretation {
+            id: self.id.ok_or(BuildError::MissingField("id"))?,
+            progress_status: self
+                .progress_status
+                .ok_or(BuildError::MissingField("progress_status"))?
+                .into(),
+            diagnosis: self.diagnosis,
+            summary: or_default(self.summary, "InterpretationBuilder", "summary"),
+            ..Default::default()
+        })
+    }
+}
+
+impl Build<Interpretation> for InterpretationBuilder<Set, Set> {
+    fn build(self) -> Interpretation {
+        self.try_build().unwrap_or_else(|e| panic!("{e}"))
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct GenomicInterpretationBuilder<T = Unset, U = Unset> {
+    subject_or_biosample_id: Option<String>,
+    interpretation_status: Option<InterpretationStatus>,
+    call: Option<Call>,
+    data: PhantomData<(T, U)>,
+}
+
+impl<U> GenomicInterpretationBuilder<Unset, U> {
+    pub fn subject_or_biosample_id(
+        self,
+        subject_or_biosample_id: impl Into<String>,
+    ) -> GenomicInterpretationBuilder<Set, U> {
+        GenomicInterpretationBuilder {
+            subject_or_biosample_id: Some(subject_or_biosample_id.into()),
+            interpretation_status: self.interpretation_status,
+            call: self.call,
+            data: PhantomData,
+        }
+    }
+}
+
+impl<T> GenomicInterpretationBuilder<T, Unset> {
+    pub fn interpretation_status(
+        self,
+        interpretation_status: impl Into<InterpretationStatus>,
+    ) -> GenomicInterpretationBuilder<T, Set> {
+        GenomicInterpretationBuilder {
+            subject_or_biosample_id: self.subject_or_biosample_id,
+            interpretation_status: Some(interpretation_status.into()),
+            call: self.call,
+            data: PhantomData,
+        }
+    }
+
+    pub fn rejected(self) -> GenomicInterpretationBuilder<T, Set> {
+        self.interpretation_status(InterpretationStatus::Rejected)
+    }
+
+    pub fn candidate(self) -> GenomicInterpretationBuilder<T, Set> {
+        self.interpretation_status(InterpretationStatus::Candidate)
+    }
+
+    pub fn contributory(self) -> GenomicInterpretationBuilder<T, Set> {
+        self.interpretation_status(InterpretationStatus::Contributory)
+    }
+
+    pub fn causative(self) -> GenomicInterpretationBuilder<T, Set> {
+        self.interpretation_status(InterpretationStatus::Causative)
+    }
+}
+
+impl<T, U> GenomicInterpretationBuilder<T, U> {
+    /// Set the call, replacing the call set before, if any.
+    pub fn call(mut self, call: impl Into<Call>) -> Self {
+        self.call = Some(call.into());
+        self
+    }
+
+    /// Set the call to a gene.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use phenopacket_builder::{Build, Buildable};
+    /// use phenopackets::ga4gh::vrsatile::v1::GeneDescriptor;
+    /// use phenopackets::schema::v2::core::genomic_interpretation::Call;
+    /// use phenopackets::schema::v2::core::GenomicInterpretation;
+    ///
+    /// let gi: GenomicInterpretation = GenomicInterpretation::builder()
+    ///                                   .subject_or_biosample_id("subject-id")
+    ///                                   .candidate()
+    ///                                   .gene_descriptor(GeneDescriptor::builder().hgnc("3603", "FBN1"))
+    ///                                   .build();
+    ///
+    /// assert!(matches!(gi.call, Some(Call::Gene(_))));
+    /// ```
+    pub fn gene_descriptor(self, gene_descriptor: impl Build<GeneDescriptor>) -> Self {
+        self.call(Call::Gene(gene_descriptor.build()))
+    }
+
+    pub fn variant_interpretation(
+        self,
+        variant_interpretation: impl Build<VariantInterpretation>,
+    ) -> Self {
+        self.call(Call::VariantInterpretation(variant_interpretation.build()))
+    }
+}
+
+impl Buildable for GenomicInterpretation {
+    type Builder = GenomicInterpretationBuilder;
+}
+
+impl<T, U> TryBuild<GenomicInterpretation> for GenomicInterpretationBuilder<T, U> {
+    #[allow(clippy::needless_update)]
+    fn try_build(self) -> Result<GenomicInterpretation, BuildError> {
+        Ok(GenomicInterpretation {
+            subject_or_biosample_id: self
+                .subject_or_biosample_id
+                .ok_or(BuildError::MissingField("subject_or_biosample_id"))?,
+            interpretation_status: self
+                .interpretation_status
+                .ok_or(BuildError::MissingField("interpretation_status"))?
+                .into(),
+            call: self.call,
+            ..Default::default()
+        })
+    }
+}
+
+impl Build<GenomicInterpretation> for GenomicInterpretationBuilder<Set, Set> {
+    fn build(self) -> GenomicInterpretation {
+        self.try_build().unwrap_or_else(|e| panic!("{e}"))
+    }
+}
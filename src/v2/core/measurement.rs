@@ -0,0 +1,498 @@
+use crate::trace::or_default;
+use crate::{Build, BuildError, Buildable, Set, TryBuild, Unset};
+use phenopackets::schema::v2::core::measurement::MeasurementValue;
+use phenopackets::schema::v2::core::{
+    value, ComplexValue, Measurement, OntologyClass, Procedure, Quantity, ReferenceRange,
+    TimeElement, TypedQuantity, Value,
+};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::marker::PhantomData;
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct MeasurementBuilder<T = Unset> {
+    description: Option<String>,
+    assay: Option<OntologyClass>,
+    measurement_value: Option<MeasurementValue>,
+    time_observed: Option<TimeElement>,
+    procedure: Option<Procedure>,
+    data: PhantomData<T>,
+}
+
+impl MeasurementBuilder<Unset> {
+    pub fn assay(self, assay: impl Build<OntologyClass>) -> MeasurementBuilder<Set> {
+        MeasurementBuilder {
+            description: self.description,
+            assay: Some(assay.build()),
+            measurement_value: self.measurement_value,
+            time_observed: self.time_observed,
+            procedure: self.procedure,
+            data: PhantomData,
+        }
+    }
+}
+
+impl<T> MeasurementBuilder<T> {
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Set the value of the measurement, replacing the complex value, if any.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use phenopacket_builder::{oc, Build, Buildable};
+    /// use phenopackets::schema::v2::core::measurement::MeasurementValue;
+    /// use phenopackets::schema::v2::core::{value, Measurement, Quantity, Value};
+    ///
+    /// let glucose: Quantity = Quantity::builder()
+    ///                           .unit(oc("UCUM:mg/dL", "mg/dL"))
+    ///                           .value(110.)
+    ///                           .build();
+    /// let measurement: Measurement = Measurement::builder()
+    ///                                  .assay(oc("LOINC:2345-7", "Glucose [Mass/volume] in Serum or Plasma"))
+    ///                                  .value(Value::builder().quantity(glucose.clone()))
+    ///                                  .build();
+    ///
+    /// assert_eq!(
+    ///     measurement.measurement_value,
+    ///     Some(MeasurementValue::Value(Value { value: Some(value::Value::Quantity(glucose)) })),
+    /// );
+    /// ```
+    pub fn value(mut self, value: impl Build<Value>) -> Self {
+        self.measurement_value = Some(MeasurementValue::Value(value.build()));
+        self
+    }
+
+    /// Set the value of the measurement to a categorical result, such as *positive*,
+    /// replacing the complex value, if any.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use phenopacket_builder::{oc, Build, Buildable};
+    /// use phenopackets::schema::v2::core::measurement::MeasurementValue;
+    /// use phenopackets::schema::v2::core::{value, Measurement, Value};
+    ///
+    /// let measurement: Measurement = Measurement::builder()
+    ///                                  .assay(oc("LOINC:94500-6", "SARS-CoV-2 RNA [Presence] in Respiratory specimen by NAA with probe detection"))
+    ///                                  .value_ontology_class(oc("NCIT:C25246", "Positive"))
+    ///                                  .build();
+    ///
+    /// assert_eq!(
+    ///     measurement.measurement_value,
+    ///     Some(MeasurementValue::Value(Value {
+    ///         value: Some(value::Value::OntologyClass(oc("NCIT:C25246", "Positive"))),
+    ///     })),
+    /// );
+    /// ```
+    pub fn value_ontology_class(self, ontology_class: impl Build<OntologyClass>) -> Self {
+        self.value(Value::builder().ontology_class(ontology_class))
+    }
+
+    /// Set the complex value of the measurement, replacing the value, if any.
+    pub fn complex_value(mut self, complex_value: impl Build<ComplexValue>) -> Self {
+        self.measurement_value = Some(MeasurementValue::ComplexValue(complex_value.build()));
+        self
+    }
+
+    pub fn time_observed(mut self, time_observed: impl Build<TimeElement>) -> Self {
+        self.time_observed = Some(time_observed.build());
+        self
+    }
+
+    pub fn procedure(mut self, procedure: impl Build<Procedure>) -> Self {
+        self.procedure = Some(procedure.build());
+        self
+    }
+}
+
+impl Buildable for Measurement {
+    type Builder = MeasurementBuilder;
+}
+
+impl<T> TryBuild<Measurement> for MeasurementBuilder<T> {
+    #[allow(clippy::needless_update)]
+    fn try_build(self) -> Result<Measurement, BuildError> {
+        Ok(Measurement {
+            description: or_default(self.description, "MeasurementBuilder", "description"),
+            assay: Some(self.assay.ok_or(BuildError::MissingField("assay"))?),
+            time_observed: self.time_observed,
+            procedure: self.procedure,
+            measurement_value: self.measurement_value,
+            ..Default::default()
+        })
+    }
+}
+
+impl Build<Measurement> for MeasurementBuilder<Set> {
+    fn build(self) -> Measurement {
+        self.try_build().unwrap_or_else(|e| panic!("{e}"))
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ValueBuilder<T = Unset> {
+    value: Option<value::Value>,
+    data: PhantomData<T>,
+}
+
+impl<T> ValueBuilder<T> {
+    /// Set the value to a quantity, replacing the ontology class, if any.
+    pub fn quantity(self, quantity: impl Build<Quantity>) -> ValueBuilder<Set> {
+        ValueBuilder {
+            value: Some(value::Value::Quantity(quantity.build())),
+            data: PhantomData,
+        }
+    }
+
+    /// Set the value to an ontology class, replacing the quantity, if any.
+    pub fn ontology_class(self, ontology_class: impl Build<OntologyClass>) -> ValueBuilder<Set> {
+        ValueBuilder {
+            value: Some(value::Value::OntologyClass(ontology_class.build())),
+            data: PhantomData,
+        }
+    }
+}
+
+impl Buildable for Value {
+    type Builder = ValueBuilder;
+}
+
+impl Build<Value> for ValueBuilder<Set> {
+    #[allow(clippy::needless_update)]
+    fn build(self) -> Value {
+        Value {
+            value: self.value,
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ComplexValueBuilder {
+    typed_quantities: Vec<TypedQuantity>,
+}
+
+impl ComplexValueBuilder {
+    pub fn add_typed_quantity(mut self, typed_quantity: impl Build<TypedQuantity>) -> Self {
+        self.typed_quantities.push(typed_quantity.build());
+        self
+    }
+
+    pub fn extend_typed_quantities(
+        mut self,
+        typed_quantities: impl IntoIterator<Item = impl Build<TypedQuantity>>,
+    ) -> Self {
+        self.typed_quantities
+            .extend(typed_quantities.into_iter().map(Build::build));
+        self
+    }
+
+    pub fn clear_typed_quantities(mut self) -> Self {
+        self.typed_quantities.clear();
+        self
+    }
+}
+
+impl Buildable for ComplexValue {
+    type Builder = ComplexValueBuilder;
+}
+
+impl Build<ComplexValue> for ComplexValueBuilder {
+    #[allow(clippy::needless_update)]
+    fn build(self) -> ComplexValue {
+        ComplexValue {
+            typed_quantities: self.typed_quantities,
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct TypedQuantityBuilder<T = Unset, U = Unset> {
+    r#type: Option<OntologyClass>,
+    quantity: Option<Quantity>,
+    data: PhantomData<(T, U)>,
+}
+
+impl<U> TypedQuantityBuilder<Unset, U> {
+    pub fn r#type(self, r#type: impl Build<OntologyClass>) -> TypedQuantityBuilder<Set, U> {
+        TypedQuantityBuilder {
+            r#type: Some(r#type.build()),
+            quantity: self.quantity,
+            data: PhantomData,
+        }
+    }
+}
+
+impl<T> TypedQuantityBuilder<T, Unset> {
+    pub fn quantity(self, quantity: impl Build<Quantity>) -> TypedQuantityBuilder<T, Set> {
+        TypedQuantityBuilder {
+            r#type: self.r#type,
+            quantity: Some(quantity.build()),
+            data: PhantomData,
+        }
+    }
+}
+
+impl Buildable for TypedQuantity {
+    type Builder = TypedQuantityBuilder;
+}
+
+impl<T, U> TryBuild<TypedQuantity> for TypedQuantityBuilder<T, U> {
+    #[allow(clippy::needless_update)]
+    fn try_build(self) -> Result<TypedQuantity, BuildError> {
+        Ok(TypedQuantity {
+            r#type: Some(self.r#type.ok_or(BuildError::MissingField("type"))?),
+            quantity: Some(self.quantity.ok_or(BuildError::MissingField("quantity"))?),
+            ..Default::default()
+        })
+    }
+}
+
+impl Build<TypedQuantity> for TypedQuantityBuilder<Set, Set> {
+    fn build(self) -> TypedQuantity {
+        self.try_build().unwrap_or_else(|e| panic!("{e}"))
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct QuantityBuilder<T = Unset, U = Unset> {
+    unit: Option<OntologyClass>,
+    value: Option<f64>,
+    reference_range: Option<ReferenceRange>,
+    data: PhantomData<(T, U)>,
+}
+
+impl<U> QuantityBuilder<Unset, U> {
+    pub fn unit(self, unit: impl Build<OntologyClass>) -> QuantityBuilder<Set, U> {
+        QuantityBuilder {
+            unit: Some(unit.build()),
+            value: self.value,
+            reference_range: self.reference_range,
+            data: PhantomData,
+        }
+    }
+}
+
+impl<T> QuantityBuilder<T, Unset> {
+    pub fn value(self, value: impl Into<f64>) -> QuantityBuilder<T, Set> {
+        QuantityBuilder {
+            unit: self.unit,
+            value: Some(value.into()),
+            reference_range: self.reference_range,
+            data: PhantomData,
+        }
+    }
+}
+
+impl QuantityBuilder<Unset, Unset> {
+    /// Set the value in percent, using the UCUM `%` unit.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use phenopacket_builder::{Build, Buildable};
+    /// use phenopackets::schema::v2::core::Quantity;
+    ///
+    /// let quantity: Quantity = Quantity::builder().percent(42.).build();
+    ///
+    /// assert_eq!(&quantity.unit.unwrap().id, "UCUM:%");
+    /// assert_eq!(quantity.value, 42.);
+    /// ```
+    pub fn percent(self, value: f64) -> QuantityBuilder<Set, Set> {
+        self.unit(OntologyClass::builder().id_label("UCUM:%", "percent"))
+            .value(value)
+    }
+
+    /// Set the value of a ratio, using the UCUM `{ratio}` unit.
+    pub fn ratio(self, value: f64) -> QuantityBuilder<Set, Set> {
+        self.unit(OntologyClass::builder().id_label("UCUM:{ratio}", "ratio"))
+            .value(value)
+    }
+}
+
+impl<T, U> QuantityBuilder<T, U> {
+    /// Set the reference range.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use phenopacket_builder::{oc, Build, Buildable};
+    /// use phenopackets::schema::v2::core::{Quantity, ReferenceRange};
+    ///
+    /// let quantity: Quantity = Quantity::builder()
+    ///                            .unit(oc("UCUM:mmol/L", "mmol/L"))
+    ///                            .value(7.2)
+    ///                            .reference_range(ReferenceRange::builder()
+    ///                                .unit(oc("UCUM:mmol/L", "mmol/L"))
+    ///                                .low(3.9)
+    ///                                .high(5.5)
+    ///                            )
+    ///                            .build();
+    ///
+    /// assert_eq!(quantity.value, 7.2);
+    /// let reference_range = quantity.reference_range.unwrap();
+    /// assert_eq!((reference_range.low, reference_range.high), (3.9, 5.5));
+    /// ```
+    pub fn reference_range(mut self, reference_range: impl Build<ReferenceRange>) -> Self {
+        self.reference_range = Some(reference_range.build());
+        self
+    }
+}
+
+impl<U> QuantityBuilder<Set, U> {
+    /// Set the reference range from its bounds, using the unit of the quantity.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a bound is `NaN` or if `low` is greater than `high`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use phenopacket_builder::{oc, Build, Buildable};
+    /// use phenopackets::schema::v2::core::Quantity;
+    ///
+    /// let quantity: Quantity = Quantity::builder()
+    ///                            .unit(oc("UCUM:mg/dL", "mg/dL"))
+    ///                            .value(110.)
+    ///                            .try_reference_range_low_high(70., 100.)
+    ///                            .expect("ordered bounds")
+    ///                            .build();
+    ///
+    /// let reference_range = quantity.reference_range.unwrap();
+    /// assert_eq!(&reference_range.unit.unwrap().id, "UCUM:mg/dL");
+    /// assert_eq!((reference_range.low, reference_range.high), (70., 100.));
+    /// ```
+    #[allow(clippy::needless_update)]
+    pub fn try_reference_range_low_high(
+        self,
+        low: f64,
+        high: f64,
+    ) -> Result<Self, ReferenceRangeError> {
+        if low.is_nan() || high.is_nan() {
+            return Err(ReferenceRangeError::NotANumber);
+        }
+        if low > high {
+            return Err(ReferenceRangeError::LowAboveHigh { low, high });
+        }
+        let unit = self.unit.clone();
+        Ok(self.reference_range(ReferenceRange {
+            unit,
+            low,
+            high,
+            ..Default::default()
+        }))
+    }
+}
+
+/// An error returned by [`QuantityBuilder::try_reference_range_low_high`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReferenceRangeError {
+    /// A bound is `NaN`.
+    NotANumber,
+    /// The lower bound is greater than the upper bound.
+    LowAboveHigh { low: f64, high: f64 },
+}
+
+impl Display for ReferenceRangeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReferenceRangeError::NotANumber => f.write_str("the bounds must be numbers"),
+            ReferenceRangeError::LowAboveHigh { low, high } => {
+                write!(f, "low ({low}) must not be greater than high ({high})")
+            }
+        }
+    }
+}
+
+impl Error for ReferenceRangeError {}
+
+impl Buildable for Quantity {
+    type Builder = QuantityBuilder;
+}
+
+impl<T, U> TryBuild<Quantity> for QuantityBuilder<T, U> {
+    #[allow(clippy::needless_update)]
+    fn try_build(self) -> Result<Quantity, BuildError> {
+        Ok(Quantity {
+            unit: Some(self.unit.ok_or(BuildError::MissingField("unit"))?),
+            value: self.value.ok_or(BuildError::MissingField("value"))?,
+            reference_range: self.reference_range,
+            ..Default::default()
+        })
+    }
+}
+
+impl Build<Quantity> for QuantityBuilder<Set, Set> {
+    fn build(self) -> Quantity {
+        self.try_build().unwrap_or_else(|e| panic!("{e}"))
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ReferenceRangeBuilder<T = Unset, U = Unset, V = Unset> {
+    unit: Option<OntologyClass>,
+    low: Option<f64>,
+    high: Option<f64>,
+    data: PhantomData<(T, U, V)>,
+}
+
+impl<U, V> ReferenceRangeBuilder<Unset, U, V> {
+    pub fn unit(self, unit: impl Build<OntologyClass>) -> ReferenceRangeBuilder<Set, U, V> {
+        ReferenceRangeBuilder {
+            unit: Some(unit.build()),
+            low: self.low,
+            high: self.high,
+            data: PhantomData,
+        }
+    }
+}
+
+impl<T, V> ReferenceRangeBuilder<T, Unset, V> {
+    pub fn low(self, low: impl Into<f64>) -> ReferenceRangeBuilder<T, Set, V> {
+        ReferenceRangeBuilder {
+            unit: self.unit,
+            low: Some(low.into()),
+            high: self.high,
+            data: PhantomData,
+        }
+    }
+}
+
+impl<T, U> ReferenceRangeBuilder<T, U, Unset> {
+    pub fn high(self, high: impl Into<f64>) -> ReferenceRangeBuilder<T, U, Set> {
+        ReferenceRangeBuilder {
+            unit: self.unit,
+            low: self.low,
+            high: Some(high.into()),
+            data: PhantomData,
+        }
+    }
+}
+
+impl Buildable for ReferenceRange {
+    type Builder = ReferenceRangeBuilder;
+}
+
+impl<T, U, V> TryBuild<ReferenceRange> for ReferenceRangeBuilder<T, U, V> {
+    #[allow(clippy::needless_update)]
+    fn try_build(self) -> Result<ReferenceRange, BuildError> {
+        Ok(ReferenceRange {
+            unit: Some(self.unit.ok_or(BuildError::MissingField("unit"))?),
+            low: self.low.ok_or(BuildError::MissingField("low"))?,
+            high: self.high.ok_or(BuildError::MissingField("high"))?,
+            ..Default::default()
+        })
+    }
+}
+
+impl Build<ReferenceRange> for ReferenceRangeBuilder<Set, Set, Set> {
+    fn build(self) -> ReferenceRange {
+        self.try_build().unwrap_or_else(|e| panic!("{e}"))
+    }
+}
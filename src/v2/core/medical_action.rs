@@ -0,0 +1,390 @@
+use crate::{Build, BuildError, Buildable, Set, TryBuild, Unset};
+use phenopackets::schema::v2::core::medical_action::Action;
+use phenopackets::schema::v2::core::therapeutic_regimen::{Identifier, RegimenStatus};
+use phenopackets::schema::v2::core::{
+    DoseInterval, DrugType, ExternalReference, MedicalAction, OntologyClass, Procedure, Quantity,
+    RadiationTherapy, TherapeuticRegimen, TimeElement, Treatment,
+};
+use std::marker::PhantomData;
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct MedicalActionBuilder<T = Unset> {
+    action: Option<Action>,
+    treatment_target: Option<OntologyClass>,
+    treatment_intent: Option<OntologyClass>,
+    response_to_treatment: Option<OntologyClass>,
+    adverse_events: Vec<OntologyClass>,
+    treatment_termination_reason: Option<OntologyClass>,
+    data: PhantomData<T>,
+}
+
+impl<T> MedicalActionBuilder<T> {
+    /// Set the action, replacing the action set before, if any.
+    pub fn action(self, action: impl Into<Action>) -> MedicalActionBuilder<Set> {
+        MedicalActionBuilder {
+            action: Some(action.into()),
+            treatment_target: self.treatment_target,
+            treatment_intent: self.treatment_intent,
+            response_to_treatment: self.response_to_treatment,
+            adverse_events: self.adverse_events,
+            treatment_termination_reason: self.treatment_termination_reason,
+            data: PhantomData,
+        }
+    }
+
+    pub fn procedure(self, procedure: impl Build<Procedure>) -> MedicalActionBuilder<Set> {
+        self.action(Action::Procedure(procedure.build()))
+    }
+
+    /// Set the action to a treatment.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use phenopacket_builder::{oc, Build, Buildable};
+    /// use phenopackets::schema::v2::core::medical_action::Action;
+    /// use phenopackets::schema::v2::core::{MedicalAction, Treatment};
+    ///
+    /// let action: MedicalAction = MedicalAction::builder()
+    ///                               .treatment(Treatment::builder()
+    ///                                   .agent(oc("DrugCentral:1678", "cisplatin"))
+    ///                                   .cumulative_dose_ucum(300., "mg")
+    ///                               )
+    ///                               .treatment_target(oc("MONDO:0008903", "lung cancer"))
+    ///                               .build();
+    ///
+    /// assert!(matches!(action.action, Some(Action::Treatment(_))));
+    /// ```
+    pub fn treatment(self, treatment: impl Build<Treatment>) -> MedicalActionBuilder<Set> {
+        self.action(Action::Treatment(treatment.build()))
+    }
+
+    pub fn radiation_therapy(
+        self,
+        radiation_therapy: impl Build<RadiationTherapy>,
+    ) -> MedicalActionBuilder<Set> {
+        self.action(Action::RadiationTherapy(radiation_therapy.build()))
+    }
+
+    pub fn therapeutic_regimen(
+        self,
+        therapeutic_regimen: impl Build<TherapeuticRegimen>,
+    ) -> MedicalActionBuilder<Set> {
+        self.action(Action::TherapeuticRegimen(therapeutic_regimen.build()))
+    }
+
+    pub fn treatment_target(mut self, treatment_target: impl Build<OntologyClass>) -> Self {
+        self.treatment_target = Some(treatment_target.build());
+        self
+    }
+
+    pub fn treatment_intent(mut self, treatment_intent: impl Build<OntologyClass>) -> Self {
+        self.treatment_intent = Some(treatment_intent.build());
+        self
+    }
+
+    pub fn response_to_treatment(
+        mut self,
+        response_to_treatment: impl Build<OntologyClass>,
+    ) -> Self {
+        self.response_to_treatment = Some(response_to_treatment.build());
+        self
+    }
+
+    pub fn add_adverse_event(mut self, adverse_event: impl Build<OntologyClass>) -> Self {
+        self.adverse_events.push(adverse_event.build());
+        self
+    }
+
+    pub fn extend_adverse_events(
+        mut self,
+        adverse_events: impl IntoIterator<Item = impl Build<OntologyClass>>,
+    ) -> Self {
+        self.adverse_events
+            .extend(adverse_events.into_iter().map(Build::build));
+        self
+    }
+
+    pub fn clear_adverse_events(mut self) -> Self {
+        self.adverse_events.clear();
+        self
+    }
+
+    pub fn treatment_termination_reason(
+        mut self,
+        treatment_termination_reason: impl Build<OntologyClass>,
+    ) -> Self {
+        self.treatment_termination_reason = Some(treatment_termination_reason.build());
+        self
+    }
+}
+
+impl Buildable for MedicalAction {
+    type Builder = MedicalActionBuilder;
+}
+
+impl<T> TryBuild<MedicalAction> for MedicalActionBuilder<T> {
+    #[allow(clippy::needless_update)]
+    fn try_build(self) -> Result<MedicalAction, BuildError> {
+        Ok(MedicalAction {
+            action: Some(self.action.ok_or(BuildError::MissingField("action"))?),
+            treatment_target: self.treatment_target,
+            treatment_intent: self.treatment_intent,
+            response_to_treatment: self.response_to_treatment,
+            adverse_events: self.adverse_events,
+            treatment_termination_reason: self.treatment_termination_reason,
+            ..Default::default()
+        })
+    }
+}
+
+impl Build<MedicalAction> for MedicalActionBuilder<Set> {
+    fn build(self) -> MedicalAction {
+        self.try_build().unwrap_or_else(|e| panic!("{e}"))
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct TreatmentBuilder<T = Unset> {
+    agent: Option<OntologyClass>,
+    route_of_administration: Option<OntologyClass>,
+    dose_intervals: Vec<DoseInterval>,
+    drug_type: DrugType,
+    cumulative_dose: Option<Quantity>,
+    data: PhantomData<T>,
+}
+
+impl TreatmentBuilder<Unset> {
+    pub fn agent(self, agent: impl Build<OntologyClass>) -> TreatmentBuilder<Set> {
+        TreatmentBuilder {
+            agent: Some(agent.build()),
+            route_of_administration: self.route_of_administration,
+            dose_intervals: self.dose_intervals,
+            drug_type: self.drug_type,
+            cumulative_dose: self.cumulative_dose,
+            data: PhantomData,
+        }
+    }
+}
+
+impl<T> TreatmentBuilder<T> {
+    pub fn route_of_administration(
+        mut self,
+        route_of_administration: impl Build<OntologyClass>,
+    ) -> Self {
+        self.route_of_administration = Some(route_of_administration.build());
+        self
+    }
+
+    pub fn add_dose_interval(mut self, dose_interval: impl Build<DoseInterval>) -> Self {
+        self.dose_intervals.push(dose_interval.build());
+        self
+    }
+
+    pub fn extend_dose_intervals(
+        mut self,
+        dose_intervals: impl IntoIterator<Item = impl Build<DoseInterval>>,
+    ) -> Self {
+        self.dose_intervals
+            .extend(dose_intervals.into_iter().map(Build::build));
+        self
+    }
+
+    pub fn clear_dose_intervals(mut self) -> Self {
+        self.dose_intervals.clear();
+        self
+    }
+
+    pub fn drug_type(mut self, drug_type: impl Into<DrugType>) -> Self {
+        self.drug_type = drug_type.into();
+        self
+    }
+
+    pub fn prescription(self) -> Self {
+        self.drug_type(DrugType::Prescription)
+    }
+
+    pub fn ehr_medication_list(self) -> Self {
+        self.drug_type(DrugType::EhrMedicationList)
+    }
+
+    pub fn administration_related_to_procedure(self) -> Self {
+        self.drug_type(DrugType::AdministrationRelatedToProcedure)
+    }
+
+    pub fn cumulative_dose(mut self, cumulative_dose: impl Build<Quantity>) -> Self {
+        self.cumulative_dose = Some(cumulative_dose.build());
+        self
+    }
+
+    /// Set the cumulative dose from its `value` and `unit`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use phenopacket_builder::{oc, Build, Buildable};
+    /// use phenopackets::schema::v2::core::Treatment;
+    ///
+    /// let treatment: Treatment = Treatment::builder()
+    ///                             .agent(oc("DrugCentral:1678", "cisplatin"))
+    ///                             .cumulative_dose_quantity(300., oc("UO:0000022", "milligram"))
+    ///                             .build();
+    ///
+    /// let dose = treatment.cumulative_dose.unwrap();
+    /// assert_eq!(dose.value, 300.);
+    /// assert_eq!(&dose.unit.unwrap().id, "UO:0000022");
+    /// ```
+    #[allow(clippy::needless_update)]
+    pub fn cumulative_dose_quantity(self, value: f64, unit: impl Build<OntologyClass>) -> Self {
+        self.cumulative_dose(Quantity {
+            unit: Some(unit.build()),
+            value,
+            reference_range: None,
+            ..Default::default()
+        })
+    }
+
+    /// Set the cumulative dose from its `value` and a UCUM unit `code`, such as `mg`.
+    pub fn cumulative_dose_ucum(self, value: f64, code: impl Into<String>) -> Self {
+        let code = code.into();
+        self.cumulative_dose_quantity(
+            value,
+            OntologyClass::builder().id_label(format!("UCUM:{code}"), code),
+        )
+    }
+}
+
+impl Buildable for Treatment {
+    type Builder = TreatmentBuilder;
+}
+
+impl<T> TryBuild<Treatment> for TreatmentBuilder<T> {
+    #[allow(clippy::needless_update)]
+    fn try_build(self) -> Result<Treatment, BuildError> {
+        Ok(Treatment {
+            agent: Some(self.agent.ok_or(BuildError::MissingField("agent"))?),
+            route_of_administration: self.route_of_administration,
+            dose_intervals: self.dose_intervals,
+            drug_type: self.drug_type.into(),
+            cumulative_dose: self.cumulative_dose,
+            ..Default::default()
+        })
+    }
+}
+
+impl Build<Treatment> for TreatmentBuilder<Set> {
+    fn build(self) -> Treatment {
+        self.try_build().unwrap_or_else(|e| panic!("{e}"))
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct TherapeuticRegimenBuilder<T = Unset> {
+    identifier: Option<Identifier>,
+    start_time: Option<TimeElement>,
+    end_time: Option<TimeElement>,
+    regimen_status: RegimenStatus,
+    data: PhantomData<T>,
+}
+
+impl<T> TherapeuticRegimenBuilder<T> {
+    /// Set the identifier, replacing the identifier set before, if any.
+    pub fn identifier(self, identifier: impl Into<Identifier>) -> TherapeuticRegimenBuilder<Set> {
+        TherapeuticRegimenBuilder {
+            identifier: Some(identifier.into()),
+            start_time: self.start_time,
+            end_time: self.end_time,
+            regimen_status: self.regimen_status,
+            data: PhantomData,
+        }
+    }
+
+    /// Identify the regimen by an ontology class.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use phenopacket_builder::{oc, Build, Buildable};
+    /// use phenopackets::schema::v2::core::therapeutic_regimen::{Identifier, RegimenStatus};
+    /// use phenopackets::schema::v2::core::TherapeuticRegimen;
+    ///
+    /// let regimen: TherapeuticRegimen = TherapeuticRegimen::builder()
+    ///                                     .ontology_class(oc("NCIT:C10894", "Carboplatin/Paclitaxel"))
+    ///                                     .completed()
+    ///                                     .build();
+    ///
+    /// assert!(matches!(regimen.identifier, Some(Identifier::OntologyClass(_))));
+    /// assert_eq!(regimen.regimen_status, RegimenStatus::Completed as i32);
+    /// ```
+    pub fn ontology_class(
+        self,
+        ontology_class: impl Build<OntologyClass>,
+    ) -> TherapeuticRegimenBuilder<Set> {
+        self.identifier(Identifier::OntologyClass(ontology_class.build()))
+    }
+
+    /// Identify the regimen by an external reference.
+    pub fn external_reference(
+        self,
+        external_reference: impl Build<ExternalReference>,
+    ) -> TherapeuticRegimenBuilder<Set> {
+        self.identifier(Identifier::ExternalReference(external_reference.build()))
+    }
+
+    pub fn start_time(mut self, start_time: impl Build<TimeElement>) -> Self {
+        self.start_time = Some(start_time.build());
+        self
+    }
+
+    pub fn end_time(mut self, end_time: impl Build<TimeElement>) -> Self {
+        self.end_time = Some(end_time.build());
+        self
+    }
+
+    pub fn regimen_status(mut self, regimen_status: impl Into<RegimenStatus>) -> Self {
+        self.regimen_status = regimen_status.into();
+        self
+    }
+
+    pub fn unknown_status(self) -> Self {
+        self.regimen_status(RegimenStatus::UnknownStatus)
+    }
+
+    pub fn started(self) -> Self {
+        self.regimen_status(RegimenStatus::Started)
+    }
+
+    pub fn completed(self) -> Self {
+        self.regimen_status(RegimenStatus::Completed)
+    }
+
+    pub fn discontinued(self) -> Self {
+        self.regimen_status(RegimenStatus::Discontinued)
+    }
+}
+
+impl Buildable for TherapeuticRegimen {
+    type Builder = TherapeuticRegimenBuilder;
+}
+
+impl<T> TryBuild<TherapeuticRegimen> for TherapeuticRegimenBuilder<T> {
+    #[allow(clippy::needless_update)]
+    fn try_build(self) -> Result<TherapeuticRegimen, BuildError> {
+        Ok(TherapeuticRegimen {
+            identifier: Some(
+                self.identifier
+                    .ok_or(BuildError::MissingField("identifier"))?,
+            ),
+            start_time: self.start_time,
+            end_time: self.end_time,
+            regimen_status: self.regimen_status.into(),
+            ..Default::default()
+        })
+    }
+}
+
+impl Build<TherapeuticRegimen> for TherapeuticRegimenBuilder<Set> {
+    fn build(self) -> TherapeuticRegimen {
+        self.try_build().unwrap_or_else(|e| panic!("{e}"))
+    }
+}
@@ -1,10 +1,11 @@
-use crate::{Build, Buildable, Set, Unset};
+use crate::trace::or_default;
+use crate::{Build, BuildError, Buildable, Set, TryBuild, Unset};
 use phenopackets::schema::v2::core::{ExternalReference, MetaData, Resource, Update};
 use prost_types::Timestamp;
 use std::marker::PhantomData;
 
 #[derive(Debug, Default, Clone, PartialEq)]
-pub struct MetaDataBuilder<T = Unset, U = Unset, V = Unset> {
+pub struct MetaDataBuilder<T = Unset, U = Unset> {
     created: Option<Timestamp>,
     created_by: Option<String>,
     submitted_by: Option<String>,
@@ -12,11 +13,71 @@ pub struct MetaDataBuilder<T = Unset, U = Unset, V = Unset> {
     updates: Vec<Update>,
     phenopacket_schema_version: Option<String>,
     external_references: Vec<ExternalReference>,
-    data: PhantomData<(T, U, V)>,
+    data: PhantomData<(T, U)>,
 }
 
-impl<U, V> MetaDataBuilder<Unset, U, V> {
-    pub fn created(self, created: impl Build<Timestamp>) -> MetaDataBuilder<Set, U, V> {
+impl MetaDataBuilder<Set, Set> {
+    /// Create a builder pre-populated with the fields of an existing [`MetaData`],
+    /// e.g. to append an update to decoded metadata.
+    ///
+    /// The optional fields are taken as they are, including the empty ones.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BuildError::MissingField`] if `created` is unset or `created_by` is empty.
+    ///
+    /// An empty `phenopacket_schema_version` is read as unset, so it defaults to `2.0.0`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use phenopacket_builder::Build;
+    /// use phenopacket_builder::v2::MetaDataBuilder;
+    /// use phenopackets::schema::v2::core::{MetaData, Update};
+    /// use prost_types::Timestamp;
+    ///
+    /// let md = MetaData {
+    ///     created: Some(Timestamp::default()),
+    ///     created_by: "Peter R.".into(),
+    ///     phenopacket_schema_version: "2.0.0".into(),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let md: MetaData = MetaDataBuilder::from_meta_data(md)
+    ///                     .expect("created and created_by are set")
+    ///                     .add_update(Update {
+    ///                         updated_by: "Daniel D.".into(),
+    ///                         ..Default::default()
+    ///                     })
+    ///                     .build();
+    ///
+    /// assert_eq!(&md.created_by, "Peter R.");
+    /// assert_eq!(&md.phenopacket_schema_version, "2.0.0");
+    /// assert_eq!(md.updates.len(), 1);
+    /// ```
+    pub fn from_meta_data(meta_data: MetaData) -> Result<MetaDataBuilder<Set, Set>, BuildError> {
+        if meta_data.created.is_none() {
+            return Err(BuildError::MissingField("created"));
+        }
+        if meta_data.created_by.trim().is_empty() {
+            return Err(BuildError::MissingField("created_by"));
+        }
+        Ok(MetaDataBuilder {
+            created: meta_data.created,
+            created_by: Some(meta_data.created_by),
+            submitted_by: Some(meta_data.submitted_by),
+            resources: meta_data.resources,
+            updates: meta_data.updates,
+            phenopacket_schema_version: Some(meta_data.phenopacket_schema_version)
+                .filter(|v| !v.is_empty()),
+            external_references: meta_data.external_references,
+            data: Default::default(),
+        })
+    }
+}
+
+impl<U> MetaDataBuilder<Unset, U> {
+    pub fn created(self, created: impl Build<Timestamp>) -> MetaDataBuilder<Set, U> {
         MetaDataBuilder {
             created: Some(created.build()),
             created_by: self.created_by,
@@ -28,10 +89,29 @@ impl<U, V> MetaDataBuilder<Unset, U, V> {
             data: Default::default(),
         }
     }
+
+    /// Set `created` to the current system time.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use phenopacket_builder::{Build, Buildable};
+    /// use phenopackets::schema::v2::core::MetaData;
+    ///
+    /// let md: MetaData = MetaData::builder()
+    ///                      .created_now()
+    ///                      .created_by("Peter R.")
+    ///                      .build();
+    ///
+    /// assert!(md.created.is_some());
+    /// ```
+    pub fn created_now(self) -> MetaDataBuilder<Set, U> {
+        self.created(Timestamp::builder().now())
+    }
 }
 
-impl<T, V> MetaDataBuilder<T, Unset, V> {
-    pub fn created_by(self, created_by: impl Into<String>) -> MetaDataBuilder<T, Set, V> {
+impl<T> MetaDataBuilder<T, Unset> {
+    pub fn created_by(self, created_by: impl Into<String>) -> MetaDataBuilder<T, Set> {
         MetaDataBuilder {
             created: self.created,
             created_by: Some(created_by.into()),
@@ -45,33 +125,21 @@ impl<T, V> MetaDataBuilder<T, Unset, V> {
     }
 }
 
-impl<T, U> MetaDataBuilder<T, U, Unset> {
-    pub fn phenopacket_schema_version(
-        self,
-        version: impl Into<String>,
-    ) -> MetaDataBuilder<T, U, Set> {
-        MetaDataBuilder {
-            created: self.created,
-            created_by: self.created_by,
-            submitted_by: self.submitted_by,
-            resources: self.resources,
-            updates: self.updates,
-            phenopacket_schema_version: Some(version.into()),
-            external_references: self.external_references,
-            data: Default::default(),
-        }
+impl<T, U> MetaDataBuilder<T, U> {
+    /// Set the Phenopacket Schema version, `2.0.0` if not set.
+    pub fn phenopacket_schema_version(mut self, version: impl Into<String>) -> Self {
+        self.phenopacket_schema_version = Some(version.into());
+        self
     }
 
-    pub fn v2(self) -> MetaDataBuilder<T, U, Set> {
+    pub fn v2(self) -> Self {
         self.phenopacket_schema_version("2.0.0")
     }
 
-    pub fn v2_0_2(self) -> MetaDataBuilder<T, U, Set> {
+    pub fn v2_0_2(self) -> Self {
         self.phenopacket_schema_version("2.0.2")
     }
-}
 
-impl<T, U, V> MetaDataBuilder<T, U, V> {
     pub fn submitted_by(mut self, submitted_by: impl Into<String>) -> Self {
         self.submitted_by = Some(submitted_by.into());
         self
@@ -96,6 +164,38 @@ impl<T, U, V> MetaDataBuilder<T, U, V> {
         self
     }
 
+    /// Replace the resources added so far with the `resources`.
+    pub fn set_resources(self, resources: impl IntoIterator<Item = impl Build<Resource>>) -> Self {
+        self.clear_resources().extend_resources(resources)
+    }
+
+    /// Get the namespace prefixes of the resources added so far.
+    pub fn resource_prefixes(&self) -> Vec<&str> {
+        self.resources
+            .iter()
+            .map(|resource| resource.namespace_prefix.as_str())
+            .collect()
+    }
+
+    /// Test if a resource with the namespace `prefix`, such as `HP`, has been added.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use phenopacket_builder::Buildable;
+    /// use phenopackets::schema::v2::core::{MetaData, Resource};
+    ///
+    /// let builder = MetaData::builder().add_resource(Resource::builder().hpo("2018-03-08"));
+    ///
+    /// assert!(builder.has_resource_for("HP"));
+    /// assert!(!builder.has_resource_for("MONDO"));
+    /// ```
+    pub fn has_resource_for(&self, prefix: &str) -> bool {
+        self.resources
+            .iter()
+            .any(|resource| resource.namespace_prefix == prefix)
+    }
+
     pub fn add_update(mut self, update: impl Build<Update>) -> Self {
         self.updates.push(update.build());
         self
@@ -111,6 +211,11 @@ impl<T, U, V> MetaDataBuilder<T, U, V> {
         self
     }
 
+    /// Replace the updates added so far with the `updates`.
+    pub fn set_updates(self, updates: impl IntoIterator<Item = impl Build<Update>>) -> Self {
+        self.clear_updates().extend_updates(updates)
+    }
+
     pub fn add_external_reference(
         mut self,
         external_reference: impl Build<ExternalReference>,
@@ -132,25 +237,53 @@ impl<T, U, V> MetaDataBuilder<T, U, V> {
         self.external_references.clear();
         self
     }
+
+    /// Replace the external references added so far with the `external_references`.
+    pub fn set_external_references(
+        self,
+        external_references: impl IntoIterator<Item = impl Build<ExternalReference>>,
+    ) -> Self {
+        self.clear_external_references()
+            .extend_external_references(external_references)
+    }
 }
 
 impl Buildable for MetaData {
     type Builder = MetaDataBuilder;
 }
 
-impl Build<MetaData> for MetaDataBuilder<Set, Set, Set> {
-    fn build(self) -> MetaData {
-        MetaData {
-            created: self.created,
-            created_by: self.created_by.expect("created_by must have been set"),
-            submitted_by: self.submitted_by.unwrap_or_default(),
+/// See [`MetaDataBuilder::from_meta_data`].
+impl TryFrom<MetaData> for MetaDataBuilder<Set, Set> {
+    type Error = BuildError;
+
+    fn try_from(meta_data: MetaData) -> Result<Self, Self::Error> {
+        MetaDataBuilder::from_meta_data(meta_data)
+    }
+}
+
+impl<T, U> TryBuild<MetaData> for MetaDataBuilder<T, U> {
+    #[allow(clippy::needless_update)]
+    fn try_build(self) -> Result<MetaData, BuildError> {
+        Ok(MetaData {
+            created: Some(self.created.ok_or(BuildError::MissingField("created"))?),
+            created_by: self
+                .created_by
+                .ok_or(BuildError::MissingField("created_by"))?,
+            submitted_by: or_default(self.submitted_by, "MetaDataBuilder", "submitted_by"),
             resources: self.resources,
             updates: self.updates,
             phenopacket_schema_version: self
                 .phenopacket_schema_version
-                .expect("phenopacket schema must have been set"),
+                .unwrap_or_else(|| "2.0.0".into()),
             external_references: self.external_references,
-        }
+            ..Default::default()
+        })
+    }
+}
+
+impl Build<MetaData> for MetaDataBuilder<Set, Set> {
+    fn build(self) -> MetaData {
+        self.try_build().unwrap_or_else(|e| panic!("{e}"))
     }
 }
 
@@ -254,192 +387,484 @@ impl<T, U, V, X, Y> ResourceBuilder<T, U, V, X, Y, Unset> {
 
 impl<T, U, V, X, Y, Z> ResourceBuilder<T, U, V, X, Y, Z> {
     pub fn hpo(self, version: impl Into<String>) -> ResourceBuilder<Set, Set, Set, Set, Set, Set> {
-        ResourceBuilder {
-            id: Some("hp".into()),
-            name: Some("human phenotype ontology".into()),
-            namespace_prefix: Some("HP".into()),
-            url: Some("https://purl.obolibrary.org/obo/hp.owl".into()),
-            version: Some(version.into()),
-            iri_prefix: Some("https://purl.obolibrary.org/obo/HP_".into()),
-            data: Default::default(),
-        }
+        HPO.builder(version)
     }
 
     pub fn geno(self, version: impl Into<String>) -> ResourceBuilder<Set, Set, Set, Set, Set, Set> {
-        ResourceBuilder {
-            id: Some("geno".into()),
-            name: Some("genotype ontology".into()),
-            namespace_prefix: Some("GENO".into()),
-            url: Some("https://purl.obolibrary.org/obo/geno.owl".into()),
-            version: Some(version.into()),
-            iri_prefix: Some("https://purl.obolibrary.org/obo/GENO_".into()),
-            data: Default::default(),
-        }
+        GENO.builder(version)
     }
 
     pub fn ncit(self, version: impl Into<String>) -> ResourceBuilder<Set, Set, Set, Set, Set, Set> {
-        ResourceBuilder {
-            id: Some("ncit".into()),
-            name: Some("NCI Thesaurus".into()),
-            namespace_prefix: Some("NCIT".into()),
-            url: Some("https://purl.obolibrary.org/obo/geno.owl".into()),
-            version: Some(version.into()),
-            iri_prefix: Some("https://purl.obolibrary.org/obo/NCIT_".into()),
-            data: Default::default(),
-        }
+        NCIT.builder(version)
     }
 
     pub fn mondo(
         self,
         version: impl Into<String>,
     ) -> ResourceBuilder<Set, Set, Set, Set, Set, Set> {
-        ResourceBuilder {
-            id: Some("mondo".into()),
-            name: Some("Mondo Disease Ontology".into()),
-            namespace_prefix: Some("MONDO".into()),
-            url: Some("https://purl.obolibrary.org/obo/mondo.obo".into()),
-            version: Some(version.into()),
-            iri_prefix: Some("https://purl.obolibrary.org/obo/MONDO_".into()),
-            data: Default::default(),
-        }
+        MONDO.builder(version)
     }
 
     pub fn uberon(
         self,
         version: impl Into<String>,
     ) -> ResourceBuilder<Set, Set, Set, Set, Set, Set> {
-        ResourceBuilder {
-            id: Some("uberon".into()),
-            name: Some("Uber-anatomy ontology".into()),
-            namespace_prefix: Some("UBERON".into()),
-            url: Some("https://purl.obolibrary.org/obo/uberon.owl".into()),
-            version: Some(version.into()),
-            iri_prefix: Some("https://purl.obolibrary.org/obo/UBERON_".into()),
-            data: Default::default(),
-        }
+        UBERON.builder(version)
     }
 
     pub fn ncbi_taxon(
         self,
         version: impl Into<String>,
     ) -> ResourceBuilder<Set, Set, Set, Set, Set, Set> {
-        ResourceBuilder {
-            id: Some("ncbitaxon".into()),
-            name: Some("NCBI organismal classification".into()),
-            namespace_prefix: Some("NCBITaxon".into()),
-            url: Some("https://purl.obolibrary.org/obo/ncbitaxon.owl".into()),
-            version: Some(version.into()),
-            iri_prefix: Some("https://purl.obolibrary.org/obo/NCBITaxon_".into()),
-            data: Default::default(),
-        }
+        NCBI_TAXON.builder(version)
     }
 
     pub fn so(self, version: impl Into<String>) -> ResourceBuilder<Set, Set, Set, Set, Set, Set> {
-        ResourceBuilder {
-            id: Some("so".into()),
-            name: Some("Sequence types and features ontology".into()),
-            namespace_prefix: Some("SO".into()),
-            url: Some("https://purl.obolibrary.org/obo/so.owl".into()),
-            version: Some(version.into()),
-            iri_prefix: Some("https://purl.obolibrary.org/obo/SO_".into()),
-            data: Default::default(),
-        }
+        SO.builder(version)
     }
 
     pub fn ucum(self, version: impl Into<String>) -> ResourceBuilder<Set, Set, Set, Set, Set, Set> {
-        ResourceBuilder {
-            id: Some("ucum".into()),
-            name: Some("Unified Code for Units of Measure".into()),
-            namespace_prefix: Some("UCUM".into()),
-            url: Some("https://ucum.org".into()),
-            version: Some(version.into()),
-            iri_prefix: Some("https://units-of-measurement.org/".into()),
-            data: Default::default(),
-        }
+        UCUM.builder(version)
     }
 
     pub fn uo(self, version: impl Into<String>) -> ResourceBuilder<Set, Set, Set, Set, Set, Set> {
-        ResourceBuilder {
-            id: Some("uo".into()),
-            name: Some("Units of measurement ontology".into()),
-            namespace_prefix: Some("UO".into()),
-            url: Some("https://purl.obolibrary.org/obo/uo.owl".into()),
-            version: Some(version.into()),
-            iri_prefix: Some("https://purl.obolibrary.org/obo/UO_".into()),
-            data: Default::default(),
-        }
+        UO.builder(version)
     }
 
     pub fn loinc(
         self,
         version: impl Into<String>,
     ) -> ResourceBuilder<Set, Set, Set, Set, Set, Set> {
-        ResourceBuilder {
-            id: Some("loinc".into()),
-            name: Some("Logical Observation Identifiers Names and Codes".into()),
-            namespace_prefix: Some("LOINC".into()),
-            url: Some("https://loinc.org".into()),
-            version: Some(version.into()),
-            iri_prefix: Some("https://loinc.org/".into()),
-            data: Default::default(),
-        }
+        LOINC.builder(version)
+    }
+
+    pub fn snomed(
+        self,
+        version: impl Into<String>,
+    ) -> ResourceBuilder<Set, Set, Set, Set, Set, Set> {
+        SNOMED.builder(version)
+    }
+
+    pub fn icd10(
+        self,
+        version: impl Into<String>,
+    ) -> ResourceBuilder<Set, Set, Set, Set, Set, Set> {
+        ICD10.builder(version)
+    }
+
+    pub fn umls(self, version: impl Into<String>) -> ResourceBuilder<Set, Set, Set, Set, Set, Set> {
+        UMLS.builder(version)
     }
 
     pub fn omim(self, version: impl Into<String>) -> ResourceBuilder<Set, Set, Set, Set, Set, Set> {
-        ResourceBuilder {
-            id: Some("omim".into()),
-            name: Some("An Online Catalog of Human Genes and Genetic Disorders".into()),
-            namespace_prefix: Some("OMIM".into()),
-            url: Some("https://www.omim.org".into()),
-            version: Some(version.into()),
-            iri_prefix: Some("https://www.omim.org/entry/".into()),
-            data: Default::default(),
-        }
+        OMIM.builder(version)
     }
 
     pub fn hgnc(self, version: impl Into<String>) -> ResourceBuilder<Set, Set, Set, Set, Set, Set> {
-        ResourceBuilder {
-            id: Some("hgnc".into()),
-            name: Some("HUGO Gene Nomenclature Committee".into()),
-            namespace_prefix: Some("HGNC".into()),
-            url: Some("https://www.genenames.org".into()),
-            version: Some(version.into()),
-            iri_prefix: Some(
-                "https://www.genenames.org/data/gene-symbol-report/#!/hgnc_id/".into(),
-            ),
-            data: Default::default(),
-        }
+        HGNC.builder(version)
     }
 
     pub fn pmid(self) -> ResourceBuilder<Set, Set, Set, Set, Set, Set> {
+        PMID.builder("")
+    }
+
+    pub fn orcid(self) -> ResourceBuilder<Set, Set, Set, Set, Set, Set> {
+        ORCID.builder("")
+    }
+
+    pub fn orphanet(
+        self,
+        version: impl Into<String>,
+    ) -> ResourceBuilder<Set, Set, Set, Set, Set, Set> {
+        ORPHANET.builder(version)
+    }
+
+    pub fn efo(self, version: impl Into<String>) -> ResourceBuilder<Set, Set, Set, Set, Set, Set> {
+        EFO.builder(version)
+    }
+
+    pub fn eco(self, version: impl Into<String>) -> ResourceBuilder<Set, Set, Set, Set, Set, Set> {
+        ECO.builder(version)
+    }
+
+    pub fn chebi(
+        self,
+        version: impl Into<String>,
+    ) -> ResourceBuilder<Set, Set, Set, Set, Set, Set> {
+        CHEBI.builder(version)
+    }
+
+    pub fn maxo(self, version: impl Into<String>) -> ResourceBuilder<Set, Set, Set, Set, Set, Set> {
+        MAXO.builder(version)
+    }
+
+    pub fn cl(self, version: impl Into<String>) -> ResourceBuilder<Set, Set, Set, Set, Set, Set> {
+        CL.builder(version)
+    }
+
+    pub fn pato(self, version: impl Into<String>) -> ResourceBuilder<Set, Set, Set, Set, Set, Set> {
+        PATO.builder(version)
+    }
+
+    pub fn go(self, version: impl Into<String>) -> ResourceBuilder<Set, Set, Set, Set, Set, Set> {
+        GO.builder(version)
+    }
+}
+
+impl ResourceBuilder {
+    /// Get the preset for the namespace `prefix`, such as `HP`, or `None` if there is no preset.
+    ///
+    /// The `version` is ignored by the presets without a version, such as `PMID`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use phenopacket_builder::Build;
+    /// use phenopacket_builder::v2::ResourceBuilder;
+    /// use phenopackets::schema::v2::core::Resource;
+    ///
+    /// let hpo: Resource = ResourceBuilder::from_prefix("HP", "2024-08-13")
+    ///                         .expect("HP should have a preset")
+    ///                         .build();
+    ///
+    /// assert_eq!(&hpo.id, "hp");
+    /// assert!(ResourceBuilder::from_prefix("DOID", "2024-08-13").is_none());
+    /// ```
+    pub fn from_prefix(
+        prefix: &str,
+        version: impl Into<String>,
+    ) -> Option<ResourceBuilder<Set, Set, Set, Set, Set, Set>> {
+        PRESETS
+            .iter()
+            .find(|preset| preset.namespace_prefix == prefix)
+            .map(|preset| {
+                if preset.versioned {
+                    preset.builder(version)
+                } else {
+                    preset.builder("")
+                }
+            })
+    }
+
+    /// Get the namespace prefixes that have a preset, such as `HP`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use phenopacket_builder::v2::ResourceBuilder;
+    ///
+    /// assert!(ResourceBuilder::preset_prefixes().any(|prefix| prefix == "MONDO"));
+    /// ```
+    pub fn preset_prefixes() -> impl Iterator<Item = &'static str> {
+        PRESETS.iter().map(|preset| preset.namespace_prefix)
+    }
+}
+
+/// A preset resource, without the version.
+struct Preset {
+    id: &'static str,
+    name: &'static str,
+    namespace_prefix: &'static str,
+    url: &'static str,
+    iri_prefix: &'static str,
+    /// Whether the resource is versioned, which is not the case for e.g. `PMID`.
+    versioned: bool,
+}
+
+impl Preset {
+    fn builder(&self, version: impl Into<String>) -> ResourceBuilder<Set, Set, Set, Set, Set, Set> {
         ResourceBuilder {
-            id: Some("pmid".into()),
-            name: Some("PubMed".into()),
-            namespace_prefix: Some("PMID".into()),
-            url: Some("https://pubmed.ncbi.nlm.nih.gov".into()),
-            version: Some("".into()),
-            iri_prefix: Some("https://pubmed.ncbi.nlm.nih.gov/".into()),
+            id: Some(self.id.into()),
+            name: Some(self.name.into()),
+            namespace_prefix: Some(self.namespace_prefix.into()),
+            url: Some(self.url.into()),
+            version: Some(version.into()),
+            iri_prefix: Some(self.iri_prefix.into()),
             data: Default::default(),
         }
     }
 }
 
+const HPO: Preset = Preset {
+    id: "hp",
+    name: "human phenotype ontology",
+    namespace_prefix: "HP",
+    url: "https://purl.obolibrary.org/obo/hp.owl",
+    iri_prefix: "https://purl.obolibrary.org/obo/HP_",
+    versioned: true,
+};
+
+const GENO: Preset = Preset {
+    id: "geno",
+    name: "genotype ontology",
+    namespace_prefix: "GENO",
+    url: "https://purl.obolibrary.org/obo/geno.owl",
+    iri_prefix: "https://purl.obolibrary.org/obo/GENO_",
+    versioned: true,
+};
+
+const NCIT: Preset = Preset {
+    id: "ncit",
+    name: "NCI Thesaurus",
+    namespace_prefix: "NCIT",
+    url: "https://purl.obolibrary.org/obo/ncit.owl",
+    iri_prefix: "https://purl.obolibrary.org/obo/NCIT_",
+    versioned: true,
+};
+
+const MONDO: Preset = Preset {
+    id: "mondo",
+    name: "Mondo Disease Ontology",
+    namespace_prefix: "MONDO",
+    url: "https://purl.obolibrary.org/obo/mondo.obo",
+    iri_prefix: "https://purl.obolibrary.org/obo/MONDO_",
+    versioned: true,
+};
+
+const UBERON: Preset = Preset {
+    id: "uberon",
+    name: "Uber-anatomy ontology",
+    namespace_prefix: "UBERON",
+    url: "https://purl.obolibrary.org/obo/uberon.owl",
+    iri_prefix: "https://purl.obolibrary.org/obo/UBERON_",
+    versioned: true,
+};
+
+const NCBI_TAXON: Preset = Preset {
+    id: "ncbitaxon",
+    name: "NCBI organismal classification",
+    namespace_prefix: "NCBITaxon",
+    url: "https://purl.obolibrary.org/obo/ncbitaxon.owl",
+    iri_prefix: "https://purl.obolibrary.org/obo/NCBITaxon_",
+    versioned: true,
+};
+
+const SO: Preset = Preset {
+    id: "so",
+    name: "Sequence types and features ontology",
+    namespace_prefix: "SO",
+    url: "https://purl.obolibrary.org/obo/so.owl",
+    iri_prefix: "https://purl.obolibrary.org/obo/SO_",
+    versioned: true,
+};
+
+const UCUM: Preset = Preset {
+    id: "ucum",
+    name: "Unified Code for Units of Measure",
+    namespace_prefix: "UCUM",
+    url: "https://ucum.org",
+    iri_prefix: "https://units-of-measurement.org/",
+    versioned: true,
+};
+
+const UO: Preset = Preset {
+    id: "uo",
+    name: "Units of measurement ontology",
+    namespace_prefix: "UO",
+    url: "https://purl.obolibrary.org/obo/uo.owl",
+    iri_prefix: "https://purl.obolibrary.org/obo/UO_",
+    versioned: true,
+};
+
+const LOINC: Preset = Preset {
+    id: "loinc",
+    name: "Logical Observation Identifiers Names and Codes",
+    namespace_prefix: "LOINC",
+    url: "https://loinc.org",
+    iri_prefix: "https://loinc.org/",
+    versioned: true,
+};
+
+const SNOMED: Preset = Preset {
+    id: "snomedct",
+    name: "SNOMED Clinical Terms",
+    namespace_prefix: "SNOMEDCT",
+    url: "https://www.snomed.org",
+    iri_prefix: "http://snomed.info/id/",
+    versioned: true,
+};
+
+const ICD10: Preset = Preset {
+    id: "icd10",
+    name: "International Classification of Diseases, 10th Revision",
+    namespace_prefix: "ICD10",
+    url: "https://icd.who.int/browse10",
+    iri_prefix: "https://icd.who.int/browse10/2019/en#/",
+    versioned: true,
+};
+
+const UMLS: Preset = Preset {
+    id: "umls",
+    name: "Unified Medical Language System",
+    namespace_prefix: "UMLS",
+    url: "https://www.nlm.nih.gov/research/umls",
+    iri_prefix: "https://uts.nlm.nih.gov/uts/umls/concept/",
+    versioned: true,
+};
+
+const OMIM: Preset = Preset {
+    id: "omim",
+    name: "An Online Catalog of Human Genes and Genetic Disorders",
+    namespace_prefix: "OMIM",
+    url: "https://www.omim.org",
+    iri_prefix: "https://www.omim.org/entry/",
+    versioned: true,
+};
+
+const HGNC: Preset = Preset {
+    id: "hgnc",
+    name: "HUGO Gene Nomenclature Committee",
+    namespace_prefix: "HGNC",
+    url: "https://www.genenames.org",
+    iri_prefix: "https://www.genenames.org/data/gene-symbol-report/#!/hgnc_id/",
+    versioned: true,
+};
+
+const PMID: Preset = Preset {
+    id: "pmid",
+    name: "PubMed",
+    namespace_prefix: "PMID",
+    url: "https://pubmed.ncbi.nlm.nih.gov",
+    iri_prefix: "https://pubmed.ncbi.nlm.nih.gov/",
+    versioned: false,
+};
+
+const ORCID: Preset = Preset {
+    id: "orcid",
+    name: "Open Researcher and Contributor ID",
+    namespace_prefix: "ORCID",
+    url: "https://orcid.org",
+    iri_prefix: "https://orcid.org/",
+    versioned: false,
+};
+
+const ORPHANET: Preset = Preset {
+    id: "orpha",
+    name: "Orphanet Rare Disease Ontology",
+    namespace_prefix: "ORPHA",
+    url: "https://www.orpha.net",
+    iri_prefix: "https://www.orpha.net/ORDO/Orphanet_",
+    versioned: true,
+};
+
+const EFO: Preset = Preset {
+    id: "efo",
+    name: "Experimental Factor Ontology",
+    namespace_prefix: "EFO",
+    url: "https://www.ebi.ac.uk/efo/efo.owl",
+    iri_prefix: "https://www.ebi.ac.uk/efo/EFO_",
+    versioned: true,
+};
+
+const ECO: Preset = Preset {
+    id: "eco",
+    name: "Evidence and Conclusion Ontology",
+    namespace_prefix: "ECO",
+    url: "https://purl.obolibrary.org/obo/eco.owl",
+    iri_prefix: "https://purl.obolibrary.org/obo/ECO_",
+    versioned: true,
+};
+
+const CHEBI: Preset = Preset {
+    id: "chebi",
+    name: "Chemical Entities of Biological Interest",
+    namespace_prefix: "CHEBI",
+    url: "https://purl.obolibrary.org/obo/chebi.owl",
+    iri_prefix: "https://purl.obolibrary.org/obo/CHEBI_",
+    versioned: true,
+};
+
+const MAXO: Preset = Preset {
+    id: "maxo",
+    name: "Medical Action Ontology",
+    namespace_prefix: "MAXO",
+    url: "https://purl.obolibrary.org/obo/maxo.owl",
+    iri_prefix: "https://purl.obolibrary.org/obo/MAXO_",
+    versioned: true,
+};
+
+const CL: Preset = Preset {
+    id: "cl",
+    name: "Cell Ontology",
+    namespace_prefix: "CL",
+    url: "https://purl.obolibrary.org/obo/cl.owl",
+    iri_prefix: "https://purl.obolibrary.org/obo/CL_",
+    versioned: true,
+};
+
+const PATO: Preset = Preset {
+    id: "pato",
+    name: "Phenotype And Trait Ontology",
+    namespace_prefix: "PATO",
+    url: "https://purl.obolibrary.org/obo/pato.owl",
+    iri_prefix: "https://purl.obolibrary.org/obo/PATO_",
+    versioned: true,
+};
+
+const GO: Preset = Preset {
+    id: "go",
+    name: "Gene Ontology",
+    namespace_prefix: "GO",
+    url: "https://purl.obolibrary.org/obo/go.owl",
+    iri_prefix: "https://purl.obolibrary.org/obo/GO_",
+    versioned: true,
+};
+
+/// The presets of the named [`ResourceBuilder`] methods, looked up by the namespace prefix.
+const PRESETS: [&Preset; 25] = [
+    &HPO,
+    &GENO,
+    &NCIT,
+    &MONDO,
+    &UBERON,
+    &NCBI_TAXON,
+    &SO,
+    &UCUM,
+    &UO,
+    &LOINC,
+    &SNOMED,
+    &ICD10,
+    &UMLS,
+    &OMIM,
+    &HGNC,
+    &PMID,
+    &ORCID,
+    &ORPHANET,
+    &EFO,
+    &ECO,
+    &CHEBI,
+    &MAXO,
+    &CL,
+    &PATO,
+    &GO,
+];
+
 impl Buildable for Resource {
     type Builder = ResourceBuilder;
 }
 
-impl Build<Resource> for ResourceBuilder<Set, Set, Set, Set, Set, Set> {
-    fn build(self) -> Resource {
-        Resource {
-            id: self.id.expect("id must have been set"),
-            name: self.name.expect("name must have been set"),
-            url: self.url.expect("url must have been set"),
-            version: self.version.expect("version must have been set"),
+impl<T, U, V, X, Y, Z> TryBuild<Resource> for ResourceBuilder<T, U, V, X, Y, Z> {
+    #[allow(clippy::needless_update)]
+    fn try_build(self) -> Result<Resource, BuildError> {
+        Ok(Resource {
+            id: self.id.ok_or(BuildError::MissingField("id"))?,
+            name: self.name.ok_or(BuildError::MissingField("name"))?,
+            url: self.url.ok_or(BuildError::MissingField("url"))?,
+            version: self.version.ok_or(BuildError::MissingField("version"))?,
             namespace_prefix: self
                 .namespace_prefix
-                .expect("namespace prefix must have been set"),
-            iri_prefix: self.iri_prefix.expect("iri prefix must have been set"),
-        }
+                .ok_or(BuildError::MissingField("namespace_prefix"))?,
+            iri_prefix: self
+                .iri_prefix
+                .ok_or(BuildError::MissingField("iri_prefix"))?,
+            ..Default::default()
+        })
+    }
+}
+
+impl Build<Resource> for ResourceBuilder<Set, Set, Set, Set, Set, Set> {
+    fn build(self) -> Resource {
+        self.try_build().unwrap_or_else(|e| panic!("{e}"))
     }
 }
 
@@ -487,12 +912,23 @@ impl Buildable for Update {
     type Builder = UpdateBuilder;
 }
 
+impl<T> TryBuild<Update> for UpdateBuilder<T> {
+    #[allow(clippy::needless_update)]
+    fn try_build(self) -> Result<Update, BuildError> {
+        Ok(Update {
+            timestamp: Some(
+                self.timestamp
+                    .ok_or(BuildError::MissingField("timestamp"))?,
+            ),
+            updated_by: or_default(self.updated_by, "UpdateBuilder", "updated_by"),
+            comment: or_default(self.comment, "UpdateBuilder", "comment"),
+            ..Default::default()
+        })
+    }
+}
+
 impl Build<Update> for UpdateBuilder<Set> {
     fn build(self) -> Update {
-        Update {
-            timestamp: self.timestamp,
-            updated_by: self.updated_by.unwrap_or_default(),
-            comment: self.comment.unwrap_or_default(),
-        }
+        self.try_build().unwrap_or_else(|e| panic!("{e}"))
     }
 }
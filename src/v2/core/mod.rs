@@ -1,4 +1,35 @@
 mod base;
+mod biosample;
+mod disease;
+mod file;
 mod individual;
+mod interpretation;
+mod measurement;
+mod medical_action;
 mod meta_data;
+mod pedigree;
 mod phenotypic_feature;
+mod variation_descriptor;
+mod vrsatile;
+
+pub(crate) use base::{is_curie, HpoOnset};
+pub use base::{
+    AgeBuilder, AgeError, CurieError, DynOntologyClassBuilder, ExternalReferenceBuilder,
+    GestationalAgeBuilder, GestationalAgeError, IntoTimeElement, OntologyClassBuilder,
+    TimeElementBuilder, TimeIntervalBuilder,
+};
+pub use biosample::BiosampleBuilder;
+pub use disease::DiseaseBuilder;
+pub use file::FileBuilder;
+pub use individual::{IndividualBuilder, VitalStatusBuilder};
+pub use interpretation::{GenomicInterpretationBuilder, InterpretationBuilder};
+pub use measurement::{
+    ComplexValueBuilder, MeasurementBuilder, QuantityBuilder, ReferenceRangeBuilder,
+    ReferenceRangeError, TypedQuantityBuilder, ValueBuilder,
+};
+pub use medical_action::{MedicalActionBuilder, TherapeuticRegimenBuilder, TreatmentBuilder};
+pub use meta_data::{MetaDataBuilder, ResourceBuilder, UpdateBuilder};
+pub use pedigree::{PedigreeBuilder, PersonBuilder};
+pub use phenotypic_feature::{EvidenceBuilder, PhenotypicFeatureBuilder};
+pub use variation_descriptor::VariationDescriptorBuilder;
+pub use vrsatile::GeneDescriptorBuilder;
@@ -0,0 +1,163 @@
+use crate::trace::or_default;
+use crate::{Build, BuildError, Buildable, Set, TryBuild, Unset};
+use phenopackets::schema::v2::core::pedigree::person::AffectedStatus;
+use phenopackets::schema::v2::core::pedigree::Person;
+use phenopackets::schema::v2::core::{Pedigree, Sex};
+use std::marker::PhantomData;
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct PedigreeBuilder {
+    persons: Vec<Person>,
+}
+
+impl PedigreeBuilder {
+    /// Add a person to the pedigree.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use phenopacket_builder::{Build, Buildable};
+    /// use phenopackets::schema::v2::core::pedigree::Person;
+    /// use phenopackets::schema::v2::core::Pedigree;
+    ///
+    /// let pedigree: Pedigree = Pedigree::builder()
+    ///                           .add_person(Person::builder()
+    ///                               .family_id("family-id")
+    ///                               .individual_id("proband")
+    ///                               .paternal_id("father")
+    ///                               .maternal_id("mother")
+    ///                               .male()
+    ///                               .affected()
+    ///                           )
+    ///                           .build();
+    ///
+    /// assert_eq!(&pedigree.persons[0].individual_id, "proband");
+    /// ```
+    pub fn add_person(mut self, person: impl Build<Person>) -> Self {
+        self.persons.push(person.build());
+        self
+    }
+
+    pub fn extend_persons(mut self, persons: impl IntoIterator<Item = impl Build<Person>>) -> Self {
+        self.persons.extend(persons.into_iter().map(Build::build));
+        self
+    }
+
+    pub fn clear_persons(mut self) -> Self {
+        self.persons.clear();
+        self
+    }
+}
+
+impl Buildable for Pedigree {
+    type Builder = PedigreeBuilder;
+}
+
+impl Build<Pedigree> for PedigreeBuilder {
+    #[allow(clippy::needless_update)]
+    fn build(self) -> Pedigree {
+        Pedigree {
+            persons: self.persons,
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct PersonBuilder<T = Unset> {
+    family_id: Option<String>,
+    individual_id: Option<String>,
+    paternal_id: Option<String>,
+    maternal_id: Option<String>,
+    sex: Sex,
+    affected_status: AffectedStatus,
+    data: PhantomData<T>,
+}
+
+impl PersonBuilder<Unset> {
+    pub fn individual_id(self, individual_id: impl Into<String>) -> PersonBuilder<Set> {
+        PersonBuilder {
+            family_id: self.family_id,
+            individual_id: Some(individual_id.into()),
+            paternal_id: self.paternal_id,
+            maternal_id: self.maternal_id,
+            sex: self.sex,
+            affected_status: self.affected_status,
+            data: PhantomData,
+        }
+    }
+}
+
+impl<T> PersonBuilder<T> {
+    pub fn family_id(mut self, family_id: impl Into<String>) -> Self {
+        self.family_id = Some(family_id.into());
+        self
+    }
+
+    pub fn paternal_id(mut self, paternal_id: impl Into<String>) -> Self {
+        self.paternal_id = Some(paternal_id.into());
+        self
+    }
+
+    pub fn maternal_id(mut self, maternal_id: impl Into<String>) -> Self {
+        self.maternal_id = Some(maternal_id.into());
+        self
+    }
+
+    pub fn sex(mut self, sex: impl Into<Sex>) -> Self {
+        self.sex = sex.into();
+        self
+    }
+
+    pub fn male(self) -> Self {
+        self.sex(Sex::Male)
+    }
+
+    pub fn female(self) -> Self {
+        self.sex(Sex::Female)
+    }
+
+    pub fn affected_status(mut self, affected_status: impl Into<AffectedStatus>) -> Self {
+        self.affected_status = affected_status.into();
+        self
+    }
+
+    pub fn affected(self) -> Self {
+        self.affected_status(AffectedStatus::Affected)
+    }
+
+    pub fn unaffected(self) -> Self {
+        self.affected_status(AffectedStatus::Unaffected)
+    }
+
+    pub fn missing_status(self) -> Self {
+        self.affected_status(AffectedStatus::Missing)
+    }
+}
+
+impl Buildable for Person {
+    type Builder = PersonBuilder;
+}
+
+impl<T> TryBuild<Person> for PersonBuilder<T> {
+    #[allow(clippy::needless_update)]
+    fn try_build(self) -> Result<Person, BuildError> {
+        Ok(Person {
+            family_id: or_default(self.family_id, "PersonBuilder", "family_id"),
+            individual_id: self
+                .individual_id
+                .ok_or(BuildError::MissingField("individual_id"))?,
+            paternal_id: or_default(self.paternal_id, "PersonBuilder", "paternal_id"),
+            maternal_id: or_default(self.maternal_id, "PersonBuilder", "maternal_id"),
+            sex: self.sex.into(),
+            affected_status: self.affected_status.into(),
+            ..Default::default()
+        })
+    }
+}
+
+impl Build<Person> for PersonBuilder<Set> {
+    fn build(self) -> Person {
+        self.try_build().unwrap_or_else(|e| panic!("{e}"))
+    }
+}
@@ -1,5 +1,10 @@
-use crate::{Build, Buildable, Set, Unset};
-use phenopackets::schema::v2::core::{Evidence, OntologyClass, PhenotypicFeature, TimeElement};
+use crate::trace::or_default;
+use crate::v2::HpoOnset;
+use crate::{oc, Build, BuildError, Buildable, Set, TryBuild, Unset};
+use phenopackets::schema::v2::core::{
+    Evidence, ExternalReference, OntologyClass, PhenotypicFeature, TimeElement,
+};
+use std::collections::HashSet;
 use std::marker::PhantomData;
 
 #[derive(Debug, Default, Clone, PartialEq)]
@@ -52,6 +57,31 @@ impl<T> PhenotypicFeatureBuilder<T> {
         self
     }
 
+    /// Set the severity to *Borderline* (`HP:0012827`).
+    pub fn borderline(self) -> PhenotypicFeatureBuilder<T> {
+        self.severity(oc("HP:0012827", "Borderline"))
+    }
+
+    /// Set the severity to *Mild* (`HP:0012825`).
+    pub fn mild(self) -> PhenotypicFeatureBuilder<T> {
+        self.severity(oc("HP:0012825", "Mild"))
+    }
+
+    /// Set the severity to *Moderate* (`HP:0012826`).
+    pub fn moderate(self) -> PhenotypicFeatureBuilder<T> {
+        self.severity(oc("HP:0012826", "Moderate"))
+    }
+
+    /// Set the severity to *Severe* (`HP:0012828`).
+    pub fn severe(self) -> PhenotypicFeatureBuilder<T> {
+        self.severity(oc("HP:0012828", "Severe"))
+    }
+
+    /// Set the severity to *Profound* (`HP:0012829`).
+    pub fn profound(self) -> PhenotypicFeatureBuilder<T> {
+        self.severity(oc("HP:0012829", "Profound"))
+    }
+
     pub fn add_modifier(
         mut self,
         modifier: impl Build<OntologyClass>,
@@ -74,11 +104,44 @@ impl<T> PhenotypicFeatureBuilder<T> {
         self
     }
 
+    /// Remove the modifiers with the same id as an earlier modifier, regardless of the label.
+    pub fn dedup_modifiers(mut self) -> PhenotypicFeatureBuilder<T> {
+        let mut ids = HashSet::new();
+        self.modifiers
+            .retain(|modifier| ids.insert(modifier.id.clone()));
+        self
+    }
+
     pub fn onset(mut self, onset: impl Build<TimeElement>) -> PhenotypicFeatureBuilder<T> {
         self.onset = Some(onset.build());
         self
     }
 
+    /// Set the onset to *Congenital onset* (`HP:0003577`).
+    pub fn onset_congenital(self) -> PhenotypicFeatureBuilder<T> {
+        self.onset(HpoOnset::Congenital)
+    }
+
+    /// Set the onset to *Neonatal onset* (`HP:0003623`).
+    pub fn onset_neonatal(self) -> PhenotypicFeatureBuilder<T> {
+        self.onset(HpoOnset::Neonatal)
+    }
+
+    /// Set the onset to *Infantile onset* (`HP:0003593`).
+    pub fn onset_infantile(self) -> PhenotypicFeatureBuilder<T> {
+        self.onset(HpoOnset::Infantile)
+    }
+
+    /// Set the onset to *Childhood onset* (`HP:0011463`).
+    pub fn onset_childhood(self) -> PhenotypicFeatureBuilder<T> {
+        self.onset(HpoOnset::Childhood)
+    }
+
+    /// Set the onset to *Adult onset* (`HP:0003581`).
+    pub fn onset_adult(self) -> PhenotypicFeatureBuilder<T> {
+        self.onset(HpoOnset::Adult)
+    }
+
     pub fn resolution(
         mut self,
         resolution: impl Build<TimeElement>,
@@ -110,17 +173,104 @@ impl Buildable for PhenotypicFeature {
     type Builder = PhenotypicFeatureBuilder;
 }
 
-impl Build<PhenotypicFeature> for PhenotypicFeatureBuilder<Set> {
-    fn build(self) -> PhenotypicFeature {
-        PhenotypicFeature {
-            description: self.description.unwrap_or_default(),
-            r#type: self.r#type,
+impl<T> TryBuild<PhenotypicFeature> for PhenotypicFeatureBuilder<T> {
+    #[allow(clippy::needless_update)]
+    fn try_build(self) -> Result<PhenotypicFeature, BuildError> {
+        Ok(PhenotypicFeature {
+            description: or_default(self.description, "PhenotypicFeatureBuilder", "description"),
+            r#type: Some(self.r#type.ok_or(BuildError::MissingField("type"))?),
             excluded: self.excluded,
             severity: self.severity,
             modifiers: self.modifiers,
             onset: self.onset,
             resolution: self.resolution,
             evidence: self.evidence,
+            ..Default::default()
+        })
+    }
+}
+
+impl Build<PhenotypicFeature> for PhenotypicFeatureBuilder<Set> {
+    fn build(self) -> PhenotypicFeature {
+        self.try_build().unwrap_or_else(|e| panic!("{e}"))
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct EvidenceBuilder<T = Unset> {
+    evidence_code: Option<OntologyClass>,
+    reference: Option<ExternalReference>,
+    data: PhantomData<T>,
+}
+
+impl EvidenceBuilder<Unset> {
+    pub fn evidence_code(self, evidence_code: impl Build<OntologyClass>) -> EvidenceBuilder<Set> {
+        EvidenceBuilder {
+            evidence_code: Some(evidence_code.build()),
+            reference: self.reference,
+            data: PhantomData,
         }
     }
+
+    /// Set the evidence code to
+    /// *author statement from published clinical study used in manual assertion* (`ECO:0006017`).
+    pub fn pcs(self) -> EvidenceBuilder<Set> {
+        self.evidence_code(OntologyClass::builder().id_label(
+            "ECO:0006017",
+            "author statement from published clinical study used in manual assertion",
+        ))
+    }
+
+    /// Set the evidence code to *author statement supported by traceable reference* (`ECO:0000033`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use phenopacket_builder::{Build, Buildable};
+    /// use phenopackets::schema::v2::core::{Evidence, ExternalReference};
+    ///
+    /// let evidence: Evidence = Evidence::builder()
+    ///                            .tas()
+    ///                            .reference(ExternalReference::builder().id("PMID:30808312"))
+    ///                            .build();
+    ///
+    /// assert_eq!(&evidence.evidence_code.unwrap().id, "ECO:0000033");
+    /// ```
+    pub fn tas(self) -> EvidenceBuilder<Set> {
+        self.evidence_code(OntologyClass::builder().id_label(
+            "ECO:0000033",
+            "author statement supported by traceable reference",
+        ))
+    }
+}
+
+impl<T> EvidenceBuilder<T> {
+    pub fn reference(mut self, reference: impl Build<ExternalReference>) -> EvidenceBuilder<T> {
+        self.reference = Some(reference.build());
+        self
+    }
+}
+
+impl Buildable for Evidence {
+    type Builder = EvidenceBuilder;
+}
+
+impl<T> TryBuild<Evidence> for EvidenceBuilder<T> {
+    #[allow(clippy::needless_update)]
+    fn try_build(self) -> Result<Evidence, BuildError> {
+        Ok(Evidence {
+            evidence_code: Some(
+                self.evidence_code
+                    .ok_or(BuildError::MissingField("evidence_code"))?,
+            ),
+            reference: self.reference,
+            ..Default::default()
+        })
+    }
+}
+
+impl Build<Evidence> for EvidenceBuilder<Set> {
+    fn build(self) -> Evidence {
+        self.try_build().unwrap_or_else(|e| panic!("{e}"))
+    }
 }
@@ -0,0 +1,200 @@
+use crate::trace::or_default;
+use crate::{Build, BuildError, Buildable, Set, TryBuild, Unset};
+use phenopackets::ga4gh::vrsatile::v1::{
+    Expression, GeneDescriptor, MoleculeContext, VariationDescriptor, VcfRecord,
+};
+use phenopackets::schema::v2::core::OntologyClass;
+use std::marker::PhantomData;
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct VariationDescriptorBuilder<T = Unset> {
+    id: Option<String>,
+    label: Option<String>,
+    description: Option<String>,
+    gene_context: Option<GeneDescriptor>,
+    expressions: Vec<Expression>,
+    vcf_record: Option<VcfRecord>,
+    xrefs: Vec<String>,
+    alternate_labels: Vec<String>,
+    molecule_context: MoleculeContext,
+    structural_type: Option<OntologyClass>,
+    allelic_state: Option<OntologyClass>,
+    data: PhantomData<T>,
+}
+
+impl VariationDescriptorBuilder<Unset> {
+    pub fn id(self, id: impl Into<String>) -> VariationDescriptorBuilder<Set> {
+        VariationDescriptorBuilder {
+            id: Some(id.into()),
+            label: self.label,
+            description: self.description,
+            gene_context: self.gene_context,
+            expressions: self.expressions,
+            vcf_record: self.vcf_record,
+            xrefs: self.xrefs,
+            alternate_labels: self.alternate_labels,
+            molecule_context: self.molecule_context,
+            structural_type: self.structural_type,
+            allelic_state: self.allelic_state,
+            data: PhantomData,
+        }
+    }
+}
+
+impl<T> VariationDescriptorBuilder<T> {
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Set the gene the variant is in.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use phenopacket_builder::{oc, Build, Buildable};
+    /// use phenopackets::ga4gh::vrsatile::v1::{Expression, GeneDescriptor, VariationDescriptor};
+    ///
+    /// let vd: VariationDescriptor = VariationDescriptor::builder()
+    ///                                 .id("variant-1")
+    ///                                 .gene_context(GeneDescriptor::builder().hgnc("3603", "FBN1"))
+    ///                                 .add_expression(Expression {
+    ///                                     syntax: "hgvs.c".into(),
+    ///                                     value: "NM_000138.5:c.6751T>A".into(),
+    ///                                     version: "".into(),
+    ///                                 })
+    ///                                 .allelic_state(oc("GENO:0000135", "heterozygous"))
+    ///                                 .genomic()
+    ///                                 .build();
+    ///
+    /// assert_eq!(&vd.gene_context.unwrap().symbol, "FBN1");
+    /// assert_eq!(&vd.expressions[0].value, "NM_000138.5:c.6751T>A");
+    /// ```
+    pub fn gene_context(mut self, gene_context: impl Build<GeneDescriptor>) -> Self {
+        self.gene_context = Some(gene_context.build());
+        self
+    }
+
+    pub fn add_expression(mut self, expression: impl Build<Expression>) -> Self {
+        self.expressions.push(expression.build());
+        self
+    }
+
+    pub fn extend_expressions(
+        mut self,
+        expressions: impl IntoIterator<Item = impl Build<Expression>>,
+    ) -> Self {
+        self.expressions
+            .extend(expressions.into_iter().map(Build::build));
+        self
+    }
+
+    pub fn clear_expressions(mut self) -> Self {
+        self.expressions.clear();
+        self
+    }
+
+    pub fn vcf_record(mut self, vcf_record: impl Build<VcfRecord>) -> Self {
+        self.vcf_record = Some(vcf_record.build());
+        self
+    }
+
+    pub fn add_xref(mut self, xref: impl Into<String>) -> Self {
+        self.xrefs.push(xref.into());
+        self
+    }
+
+    pub fn extend_xrefs(mut self, xrefs: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.xrefs.extend(xrefs.into_iter().map(Into::into));
+        self
+    }
+
+    pub fn clear_xrefs(mut self) -> Self {
+        self.xrefs.clear();
+        self
+    }
+
+    pub fn add_alternate_label(mut self, alternate_label: impl Into<String>) -> Self {
+        self.alternate_labels.push(alternate_label.into());
+        self
+    }
+
+    pub fn extend_alternate_labels(
+        mut self,
+        alternate_labels: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        self.alternate_labels
+            .extend(alternate_labels.into_iter().map(Into::into));
+        self
+    }
+
+    pub fn clear_alternate_labels(mut self) -> Self {
+        self.alternate_labels.clear();
+        self
+    }
+
+    pub fn molecule_context(mut self, molecule_context: impl Into<MoleculeContext>) -> Self {
+        self.molecule_context = molecule_context.into();
+        self
+    }
+
+    pub fn genomic(self) -> Self {
+        self.molecule_context(MoleculeContext::Genomic)
+    }
+
+    pub fn transcript(self) -> Self {
+        self.molecule_context(MoleculeContext::Transcript)
+    }
+
+    pub fn protein(self) -> Self {
+        self.molecule_context(MoleculeContext::Protein)
+    }
+
+    pub fn structural_type(mut self, structural_type: impl Build<OntologyClass>) -> Self {
+        self.structural_type = Some(structural_type.build());
+        self
+    }
+
+    pub fn allelic_state(mut self, allelic_state: impl Build<OntologyClass>) -> Self {
+        self.allelic_state = Some(allelic_state.build());
+        self
+    }
+}
+
+impl Buildable for VariationDescriptor {
+    type Builder = VariationDescriptorBuilder;
+}
+
+impl<T> TryBuild<VariationDescriptor> for VariationDescriptorBuilder<T> {
+    fn try_build(self) -> Result<VariationDescriptor, BuildError> {
+        Ok(VariationDescriptor {
+            id: self.id.ok_or(BuildError::MissingField("id"))?,
+            label: or_default(self.label, "VariationDescriptorBuilder", "label"),
+            description: or_default(
+                self.description,
+                "VariationDescriptorBuilder",
+                "description",
+            ),
+            gene_context: self.gene_context,
+            expressions: self.expressions,
+            vcf_record: self.vcf_record,
+            xrefs: self.xrefs,
+            alternate_labels: self.alternate_labels,
+            molecule_context: self.molecule_context.into(),
+            structural_type: self.structural_type,
+            allelic_state: self.allelic_state,
+            ..Default::default()
+        })
+    }
+}
+
+impl Build<VariationDescriptor> for VariationDescriptorBuilder<Set> {
+    fn build(self) -> VariationDescriptor {
+        self.try_build().unwrap_or_else(|e| panic!("{e}"))
+    }
+}
@@ -0,0 +1,155 @@
+use crate::trace::or_default;
+use crate::{Build, BuildError, Buildable, Set, TryBuild, Unset};
+use phenopackets::ga4gh::vrsatile::v1::GeneDescriptor;
+use std::marker::PhantomData;
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct GeneDescriptorBuilder<T = Unset, U = Unset> {
+    value_id: Option<String>,
+    symbol: Option<String>,
+    description: Option<String>,
+    alternate_ids: Vec<String>,
+    alternate_symbols: Vec<String>,
+    xrefs: Vec<String>,
+    data: PhantomData<(T, U)>,
+}
+
+impl<U> GeneDescriptorBuilder<Unset, U> {
+    pub fn value_id(self, value_id: impl Into<String>) -> GeneDescriptorBuilder<Set, U> {
+        GeneDescriptorBuilder {
+            value_id: Some(value_id.into()),
+            symbol: self.symbol,
+            description: self.description,
+            alternate_ids: self.alternate_ids,
+            alternate_symbols: self.alternate_symbols,
+            xrefs: self.xrefs,
+            data: PhantomData,
+        }
+    }
+}
+
+impl<T> GeneDescriptorBuilder<T, Unset> {
+    pub fn symbol(self, symbol: impl Into<String>) -> GeneDescriptorBuilder<T, Set> {
+        GeneDescriptorBuilder {
+            value_id: self.value_id,
+            symbol: Some(symbol.into()),
+            description: self.description,
+            alternate_ids: self.alternate_ids,
+            alternate_symbols: self.alternate_symbols,
+            xrefs: self.xrefs,
+            data: PhantomData,
+        }
+    }
+}
+
+impl GeneDescriptorBuilder<Unset, Unset> {
+    /// Set the HGNC gene id, such as `3603`, and the gene symbol.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use phenopacket_builder::{Build, Buildable};
+    /// use phenopackets::ga4gh::vrsatile::v1::GeneDescriptor;
+    ///
+    /// let gene: GeneDescriptor = GeneDescriptor::builder()
+    ///                             .hgnc("3603", "FBN1")
+    ///                             .add_alternate_id("ensembl:ENSG00000166147")
+    ///                             .build();
+    ///
+    /// assert_eq!(&gene.value_id, "HGNC:3603");
+    /// assert_eq!(&gene.symbol, "FBN1");
+    /// ```
+    pub fn hgnc(
+        self,
+        hgnc_id: impl AsRef<str>,
+        symbol: impl Into<String>,
+    ) -> GeneDescriptorBuilder<Set, Set> {
+        self.value_id(format!("HGNC:{}", hgnc_id.as_ref()))
+            .symbol(symbol)
+    }
+}
+
+impl<T, U> GeneDescriptorBuilder<T, U> {
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    pub fn add_alternate_id(mut self, alternate_id: impl Into<String>) -> Self {
+        self.alternate_ids.push(alternate_id.into());
+        self
+    }
+
+    pub fn extend_alternate_ids(
+        mut self,
+        alternate_ids: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        self.alternate_ids
+            .extend(alternate_ids.into_iter().map(Into::into));
+        self
+    }
+
+    pub fn clear_alternate_ids(mut self) -> Self {
+        self.alternate_ids.clear();
+        self
+    }
+
+    pub fn add_alternate_symbol(mut self, alternate_symbol: impl Into<String>) -> Self {
+        self.alternate_symbols.push(alternate_symbol.into());
+        self
+    }
+
+    pub fn extend_alternate_symbols(
+        mut self,
+        alternate_symbols: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        self.alternate_symbols
+            .extend(alternate_symbols.into_iter().map(Into::into));
+        self
+    }
+
+    pub fn clear_alternate_symbols(mut self) -> Self {
+        self.alternate_symbols.clear();
+        self
+    }
+
+    pub fn add_xref(mut self, xref: impl Into<String>) -> Self {
+        self.xrefs.push(xref.into());
+        self
+    }
+
+    pub fn extend_xrefs(mut self, xrefs: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.xrefs.extend(xrefs.into_iter().map(Into::into));
+        self
+    }
+
+    pub fn clear_xrefs(mut self) -> Self {
+        self.xrefs.clear();
+        self
+    }
+}
+
+impl Buildable for GeneDescriptor {
+    type Builder = GeneDescriptorBuilder;
+}
+
+impl<T, U> TryBuild<GeneDescriptor> for GeneDescriptorBuilder<T, U> {
+    #[allow(clippy::needless_update)]
+    fn try_build(self) -> Result<GeneDescriptor, BuildError> {
+        Ok(GeneDescriptor {
+            value_id: self.value_id.ok_or(BuildError::MissingField("value_id"))?,
+            symbol: self.symbol.ok_or(BuildError::MissingField("symbol"))?,
+            description: or_default(self.description, "GeneDescriptorBuilder", "description"),
+            alternate_ids: self.alternate_ids,
+            alternate_symbols: self.alternate_symbols,
+            xrefs: self.xrefs,
+            ..Default::default()
+        })
+    }
+}
+
+impl Build<GeneDescriptor> for GeneDescriptorBuilder<Set, Set> {
+    fn build(self) -> GeneDescriptor {
+        self.try_build().unwrap_or_else(|e| panic!("{e}"))
+    }
+}
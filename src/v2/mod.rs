@@ -1,9 +1,20 @@
+//! Builders for the building blocks of Phenopacket Schema v2.
+
 mod core;
 mod phenopackets;
 
-use crate::{Build, Buildable, Set, Unset};
+pub use self::core::*;
+pub use phenopackets::{
+    FamilyBuilder, PhenopacketBuilder, PhenopacketTemplate, StrictPhenopacketBuilder,
+};
+
+use crate::time::{days_from_civil, days_in_month};
+use crate::{Build, BuildError, Buildable, Set, TryBuild, Unset};
 
+use std::error::Error;
+use std::fmt::{Display, Formatter};
 use std::marker::PhantomData;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 #[derive(Debug, Default, Clone, PartialEq)]
 pub struct TimestampBuilder<T = Unset> {
@@ -78,20 +89,180 @@ impl TimestampBuilder<Unset> {
     pub fn iso8601timestamp(
         self,
         timestamp: impl AsRef<str>,
-    ) -> Result<TimestampBuilder<Set>, prost_types::TimestampError> {
+    ) -> Result<TimestampBuilder<Set>, TimestampParseError> {
         Ok(TimestampBuilder {
             timestamp: Some(timestamp.as_ref().parse()?),
             data: PhantomData,
         })
     }
+
+    /// Set the timestamp to midnight UTC of the calendar date.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the month is not in `1..=12`
+    /// or if the day is not in the month, such as Feb 30th.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use prost_types::Timestamp;
+    /// use phenopacket_builder::{Buildable, Build};
+    ///
+    /// let ts: Timestamp = Timestamp::builder()
+    ///                       .ymd(2021, 11, 3)
+    ///                       .expect("a valid date")
+    ///                       .build();
+    ///
+    /// assert_eq!(&ts.to_string(), "2021-11-03T00:00:00Z");
+    /// ```
+    pub fn ymd(self, year: i32, month: u32, day: u32) -> Result<TimestampBuilder<Set>, DateError> {
+        if !(1..=12).contains(&month) {
+            return Err(DateError::InvalidMonth { month });
+        }
+        if day < 1 || i64::from(day) > days_in_month(i64::from(year), i64::from(month)) {
+            return Err(DateError::InvalidDay { year, month, day });
+        }
+        let days = days_from_civil(i64::from(year), i64::from(month), i64::from(day));
+        Ok(self.seconds_nanos(days * 86_400, 0))
+    }
+
+    /// Set the timestamp from a [`SystemTime`], such as the modification time of a file.
+    ///
+    /// Times before the Unix epoch get negative seconds and non-negative nanoseconds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::time::{Duration, UNIX_EPOCH};
+    /// use prost_types::Timestamp;
+    /// use phenopacket_builder::{Buildable, Build};
+    ///
+    /// let ts: Timestamp = Timestamp::builder()
+    ///                       .system_time(UNIX_EPOCH + Duration::new(605, 11))
+    ///                       .build();
+    ///
+    /// assert_eq!(ts.seconds, 605);
+    /// assert_eq!(ts.nanos, 11);
+    /// ```
+    pub fn system_time(self, time: SystemTime) -> TimestampBuilder<Set> {
+        let (seconds, nanos) = match time.duration_since(UNIX_EPOCH) {
+            Ok(since) => (since.as_secs() as i64, since.subsec_nanos() as i32),
+            Err(e) => {
+                let before = e.duration();
+                let (seconds, nanos) =
+                    (-(before.as_secs() as i64), -(before.subsec_nanos() as i32));
+                if nanos < 0 {
+                    (seconds - 1, nanos + 1_000_000_000)
+                } else {
+                    (seconds, nanos)
+                }
+            }
+        };
+        self.seconds_nanos(seconds, nanos)
+    }
+
+    /// Set the timestamp to the current system time.
+    pub fn now(self) -> TimestampBuilder<Set> {
+        self.system_time(SystemTime::now())
+    }
+
+    /// Set the timestamp from a [`chrono::DateTime`] in UTC.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::{TimeZone, Utc};
+    /// use prost_types::Timestamp;
+    /// use phenopacket_builder::{Buildable, Build};
+    ///
+    /// let ts: Timestamp = Timestamp::builder()
+    ///                       .date_time(Utc.with_ymd_and_hms(2021, 11, 3, 0, 0, 0).unwrap())
+    ///                       .build();
+    ///
+    /// assert_eq!(&ts.to_string(), "2021-11-03T00:00:00Z");
+    /// ```
+    #[cfg(feature = "chrono")]
+    pub fn date_time(self, date_time: chrono::DateTime<chrono::Utc>) -> TimestampBuilder<Set> {
+        let mut timestamp = prost_types::Timestamp {
+            seconds: date_time.timestamp(),
+            nanos: date_time.timestamp_subsec_nanos() as i32,
+        };
+        // A leap second has more than 1e9 nanoseconds.
+        timestamp.normalize();
+        TimestampBuilder {
+            timestamp: Some(timestamp),
+            data: PhantomData,
+        }
+    }
+}
+
+/// An error returned by [`TimestampBuilder::iso8601timestamp`]
+/// and the other builders that parse ISO8601 timestamps.
+#[derive(Debug)]
+pub enum TimestampParseError {
+    /// The value is not an ISO8601 timestamp.
+    Malformed(Box<dyn Error + Send + Sync>),
 }
 
+impl Display for TimestampParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TimestampParseError::Malformed(cause) => {
+                write!(f, "the value is not an ISO8601 timestamp: {cause}")
+            }
+        }
+    }
+}
+
+impl Error for TimestampParseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            TimestampParseError::Malformed(cause) => Some(cause.as_ref()),
+        }
+    }
+}
+
+impl From<prost_types::TimestampError> for TimestampParseError {
+    fn from(value: prost_types::TimestampError) -> Self {
+        TimestampParseError::Malformed(Box::new(value))
+    }
+}
+
+/// An error returned by [`TimestampBuilder::ymd`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DateError {
+    /// The month is not in `1..=12`.
+    InvalidMonth { month: u32 },
+    /// The day is not in the month.
+    InvalidDay { year: i32, month: u32, day: u32 },
+}
+
+impl Display for DateError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DateError::InvalidMonth { month } => write!(f, "month {month} is not in 1..=12"),
+            DateError::InvalidDay { year, month, day } => {
+                write!(f, "day {day} is not in month {year}-{month:02}")
+            }
+        }
+    }
+}
+
+impl Error for DateError {}
+
 impl Buildable for prost_types::Timestamp {
     type Builder = TimestampBuilder;
 }
 
+impl<T> TryBuild<prost_types::Timestamp> for TimestampBuilder<T> {
+    fn try_build(self) -> Result<prost_types::Timestamp, BuildError> {
+        self.timestamp.ok_or(BuildError::MissingField("timestamp"))
+    }
+}
+
 impl Build<prost_types::Timestamp> for TimestampBuilder<Set> {
     fn build(self) -> prost_types::Timestamp {
-        self.timestamp.expect("timestamp must have been set")
+        self.try_build().unwrap_or_else(|e| panic!("{e}"))
     }
 }
@@ -1,9 +1,23 @@
-use crate::{Build, Buildable, Set, Unset};
+#[cfg(feature = "json")]
+use crate::io::{write_json, WriteError};
+use crate::redact::{
+    redact_biosamples, redact_files, redact_interpretations, redact_option, RedactedIndividual,
+};
+use crate::v2::is_curie;
+use crate::validate::{validate_with, ValidationProfile};
+use crate::visit::visit_ontology_classes;
+use crate::{
+    excluded_feature, observed_feature, Build, BuildError, Buildable, Set, TryBuild, Unset,
+};
 use phenopackets::schema::v2::core::{
-    Biosample, Disease, File, Individual, Interpretation, Measurement, MedicalAction, MetaData,
-    PhenotypicFeature,
+    Age, Biosample, Disease, File, Individual, Interpretation, Measurement, MedicalAction,
+    MetaData, OntologyClass, Pedigree, PhenotypicFeature, Update, VitalStatus,
 };
-use phenopackets::schema::v2::Phenopacket;
+use phenopackets::schema::v2::{Family, Phenopacket};
+use prost::Message;
+use prost_types::Timestamp;
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Debug, Formatter};
 use std::marker::PhantomData;
 
 #[derive(Debug, Default, Clone, PartialEq)]
@@ -18,6 +32,7 @@ pub struct PhenopacketBuilder<T = Unset, U = Unset> {
     medical_actions: Vec<MedicalAction>,
     files: Vec<File>,
     meta_data: Option<MetaData>,
+    deterministic: bool,
     data: PhantomData<(T, U)>,
 }
 
@@ -34,6 +49,7 @@ impl<U> PhenopacketBuilder<Unset, U> {
             medical_actions: self.medical_actions,
             files: self.files,
             meta_data: self.meta_data,
+            deterministic: self.deterministic,
             data: Default::default(),
         }
     }
@@ -52,17 +68,173 @@ impl<T> PhenopacketBuilder<T, Unset> {
             medical_actions: self.medical_actions,
             files: self.files,
             meta_data: Some(meta_data.build()),
+            deterministic: self.deterministic,
             data: Default::default(),
         }
     }
 }
 
+impl<T> PhenopacketBuilder<T, Set> {
+    /// Record an update of the phenopacket, made at a given time, into the metadata.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use phenopacket_builder::{Build, Buildable};
+    /// use phenopackets::schema::v2::core::MetaData;
+    /// use phenopackets::schema::v2::Phenopacket;
+    /// use prost_types::Timestamp;
+    ///
+    /// let pp: Phenopacket = Phenopacket::builder()
+    ///                         .id("pp-id")
+    ///                         .meta_data(MetaData::builder()
+    ///                             .created(Timestamp::builder().seconds_nanos(0, 0))
+    ///                             .created_by("Peter R.")
+    ///                             .v2()
+    ///                         )
+    ///                         .timestamped_update(
+    ///                             "Daniel D.",
+    ///                             "Fixed the onset",
+    ///                             Timestamp::builder().iso8601timestamp("2021-11-03").unwrap(),
+    ///                         )
+    ///                         .build();
+    ///
+    /// let update = &pp.meta_data.unwrap().updates[0];
+    /// assert_eq!(&update.updated_by, "Daniel D.");
+    /// assert_eq!(&update.timestamp.unwrap().to_string(), "2021-11-03T00:00:00Z");
+    /// ```
+    #[allow(clippy::needless_update)]
+    pub fn timestamped_update(
+        mut self,
+        updated_by: impl Into<String>,
+        comment: impl Into<String>,
+        timestamp: impl Build<Timestamp>,
+    ) -> Self {
+        self.meta_data
+            .as_mut()
+            .expect("meta_data must have been set")
+            .updates
+            .push(Update {
+                timestamp: Some(timestamp.build()),
+                updated_by: updated_by.into(),
+                comment: comment.into(),
+                ..Default::default()
+            });
+        self
+    }
+
+    /// Merge the metadata of another source into the metadata of the phenopacket.
+    ///
+    /// The resources are united, keeping the resource already present if both sources
+    /// use the same namespace prefix. The updates are concatenated and sorted by timestamp,
+    /// and the earlier of the two `created` timestamps is kept.
+    /// The other fields, such as `created_by`, are kept as they are.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use phenopacket_builder::{Build, Buildable};
+    /// use phenopackets::schema::v2::core::{MetaData, Resource};
+    /// use phenopackets::schema::v2::Phenopacket;
+    /// use prost_types::Timestamp;
+    ///
+    /// let meta_data = |seconds: i64| MetaData::builder()
+    ///                     .created(Timestamp::builder().seconds_nanos(seconds, 0))
+    ///                     .created_by("Peter R.")
+    ///                     .add_resource(Resource::builder().hpo("2024-08-13"))
+    ///                     .v2();
+    ///
+    /// let pp: Phenopacket = Phenopacket::builder()
+    ///                         .id("pp-id")
+    ///                         .meta_data(meta_data(100))
+    ///                         .merge_metadata(meta_data(50).build())
+    ///                         .build();
+    ///
+    /// let meta_data = pp.meta_data.unwrap();
+    /// assert_eq!(meta_data.resources.len(), 1);
+    /// assert_eq!(meta_data.created.unwrap().seconds, 50);
+    /// ```
+    pub fn merge_metadata(mut self, other: MetaData) -> Self {
+        let meta_data = self
+            .meta_data
+            .as_mut()
+            .expect("meta_data must have been set");
+
+        for resource in other.resources {
+            if !meta_data
+                .resources
+                .iter()
+                .any(|r| r.namespace_prefix == resource.namespace_prefix)
+            {
+                meta_data.resources.push(resource);
+            }
+        }
+
+        meta_data.updates.extend(other.updates);
+        meta_data
+            .updates
+            .sort_by_key(|update| update.timestamp.map(|ts| (ts.seconds, ts.nanos)));
+
+        meta_data.created = match (meta_data.created, other.created) {
+            (Some(a), Some(b)) => Some(if (b.seconds, b.nanos) < (a.seconds, a.nanos) {
+                b
+            } else {
+                a
+            }),
+            (a, b) => a.or(b),
+        };
+        self
+    }
+}
+
 impl<T, U> PhenopacketBuilder<T, U> {
     pub fn subject(mut self, subject: impl Build<Individual>) -> Self {
         self.subject = Some(subject.build());
         self
     }
 
+    /// Set the subject to an individual with the `id` and no other data.
+    pub fn subject_id(self, id: impl Into<String>) -> Self {
+        self.subject(Individual {
+            id: id.into(),
+            ..Default::default()
+        })
+    }
+
+    /// Mark the subject as alive.
+    ///
+    /// A subject with no other data is created, if the subject has not been set yet.
+    pub fn subject_alive(self) -> Self {
+        self.subject_vital_status(VitalStatus::builder().alive())
+    }
+
+    /// Mark the subject as deceased.
+    ///
+    /// A subject with no other data is created, if the subject has not been set yet.
+    pub fn subject_deceased(self) -> Self {
+        self.subject_vital_status(VitalStatus::builder().deceased())
+    }
+
+    /// Mark the subject as deceased at an age, such as `P3Y4M`.
+    ///
+    /// A subject with no other data is created, if the subject has not been set yet.
+    pub fn subject_deceased_at_age(self, iso8601duration: impl Into<String>) -> Self {
+        self.subject_vital_status(
+            VitalStatus::builder()
+                .deceased()
+                .time_of_death_at_age(Build::<Age>::build(
+                    Age::builder().iso8601duration(iso8601duration),
+                )),
+        )
+    }
+
+    fn subject_vital_status(mut self, vital_status: impl Build<VitalStatus>) -> Self {
+        self.subject
+            .get_or_insert_with(Individual::default)
+            .vital_status = Some(vital_status.build());
+        self
+    }
+
     pub fn add_phenotypic_feature(
         mut self,
         phenotypic_feature: impl Build<PhenotypicFeature>,
@@ -85,17 +257,562 @@ impl<T, U> PhenopacketBuilder<T, U> {
         self
     }
 
-    // TODO: add the other fields
+    /// Remove the phenotypic features with the same type id as an earlier feature,
+    /// regardless of the label or of the other fields.
+    ///
+    /// The features without a type are kept.
+    pub fn dedup_phenotypic_features(mut self) -> Self {
+        let mut ids = HashSet::new();
+        self.phenotypic_features
+            .retain(|feature| match &feature.r#type {
+                Some(r#type) => ids.insert(r#type.id.clone()),
+                None => true,
+            });
+        self
+    }
+
+    /// Replace the phenotypic features added so far with the `phenotypic_features`.
+    pub fn set_phenotypic_features(
+        self,
+        phenotypic_features: impl IntoIterator<Item = impl Build<PhenotypicFeature>>,
+    ) -> Self {
+        self.clear_phenotypic_features()
+            .extend_phenotypic_features(phenotypic_features)
+    }
+
+    /// Add an observed phenotypic feature for each `(id, label)` pair, such as a list of HPO terms.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use phenopacket_builder::Buildable;
+    /// use phenopackets::schema::v2::Phenopacket;
+    ///
+    /// let builder = Phenopacket::builder()
+    ///                 .extend_observed_features([
+    ///                     ("HP:0001250", "Seizure"),
+    ///                     ("HP:0001263", "Global developmental delay"),
+    ///                 ])
+    ///                 .extend_excluded_features([("HP:0001627", "Abnormal heart morphology")]);
+    ///
+    /// assert_eq!(builder.feature_type_ids(), vec!["HP:0001250", "HP:0001263", "HP:0001627"]);
+    /// ```
+    pub fn extend_observed_features(
+        self,
+        ids: impl IntoIterator<Item = (impl Into<String>, impl Into<String>)>,
+    ) -> Self {
+        self.extend_phenotypic_features(
+            ids.into_iter()
+                .map(|(id, label)| observed_feature(id, label)),
+        )
+    }
+
+    /// Add an excluded phenotypic feature for each `(id, label)` pair.
+    ///
+    /// See [`PhenopacketBuilder::extend_observed_features`] for an example.
+    pub fn extend_excluded_features(
+        self,
+        ids: impl IntoIterator<Item = (impl Into<String>, impl Into<String>)>,
+    ) -> Self {
+        self.extend_phenotypic_features(
+            ids.into_iter()
+                .map(|(id, label)| excluded_feature(id, label)),
+        )
+    }
+
+    /// Clear the phenotypic features and get the number of the removed features.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use phenopacket_builder::{oc, Buildable};
+    /// use phenopackets::schema::v2::core::PhenotypicFeature;
+    /// use phenopackets::schema::v2::Phenopacket;
+    ///
+    /// let builder = Phenopacket::builder()
+    ///                 .add_phenotypic_feature(PhenotypicFeature::builder().r#type(oc("HP:0001250", "Seizure")));
+    ///
+    /// let (builder, removed) = builder.clear_phenotypic_features_counting();
+    ///
+    /// assert_eq!(removed, 1);
+    /// assert!(builder.feature_type_ids().is_empty());
+    /// ```
+    pub fn clear_phenotypic_features_counting(self) -> (Self, usize) {
+        let count = self.phenotypic_features.len();
+        (self.clear_phenotypic_features(), count)
+    }
+
+    /// Add a phenotypic feature observed in the family of the subject rather than in the subject,
+    /// using the *Family history* (`HP:0032316`) modifier.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use phenopacket_builder::{oc, Build, Buildable};
+    /// use phenopackets::schema::v2::core::MetaData;
+    /// use phenopackets::schema::v2::Phenopacket;
+    /// use prost_types::Timestamp;
+    ///
+    /// let pp: Phenopacket = Phenopacket::builder()
+    ///                         .id("pp-id")
+    ///                         .note_family_history(oc("HP:0001250", "Seizure"))
+    ///                         .meta_data(MetaData::builder()
+    ///                             .created(Timestamp::builder().seconds_nanos(0, 0))
+    ///                             .created_by("Peter R.")
+    ///                             .v2()
+    ///                         )
+    ///                         .build();
+    ///
+    /// assert_eq!(&pp.phenotypic_features[0].modifiers[0].id, "HP:0032316");
+    /// ```
+    pub fn note_family_history(self, term: impl Build<OntologyClass>) -> Self {
+        self.add_phenotypic_feature(
+            PhenotypicFeature::builder()
+                .r#type(term)
+                .add_modifier(OntologyClass::builder().id_label("HP:0032316", "Family history")),
+        )
+    }
+
+    pub fn add_biosample(mut self, biosample: impl Build<Biosample>) -> Self {
+        self.biosamples.push(biosample.build());
+        self
+    }
+
+    pub fn extend_biosamples(
+        mut self,
+        biosamples: impl IntoIterator<Item = impl Build<Biosample>>,
+    ) -> Self {
+        self.biosamples
+            .extend(biosamples.into_iter().map(Build::build));
+        self
+    }
+
+    pub fn clear_biosamples(mut self) -> Self {
+        self.biosamples.clear();
+        self
+    }
+
+    /// Clear the biosamples and get the number of the removed biosamples.
+    pub fn clear_biosamples_counting(self) -> (Self, usize) {
+        let count = self.biosamples.len();
+        (self.clear_biosamples(), count)
+    }
+
+    pub fn add_measurement(mut self, measurement: impl Build<Measurement>) -> Self {
+        self.measurements.push(measurement.build());
+        self
+    }
+
+    pub fn extend_measurements(
+        mut self,
+        measurements: impl IntoIterator<Item = impl Build<Measurement>>,
+    ) -> Self {
+        self.measurements
+            .extend(measurements.into_iter().map(Build::build));
+        self
+    }
+
+    pub fn clear_measurements(mut self) -> Self {
+        self.measurements.clear();
+        self
+    }
+
+    /// Clear the measurements and get the number of the removed measurements.
+    pub fn clear_measurements_counting(self) -> (Self, usize) {
+        let count = self.measurements.len();
+        (self.clear_measurements(), count)
+    }
+
+    pub fn add_interpretation(mut self, interpretation: impl Build<Interpretation>) -> Self {
+        self.interpretations.push(interpretation.build());
+        self
+    }
+
+    pub fn extend_interpretations(
+        mut self,
+        interpretations: impl IntoIterator<Item = impl Build<Interpretation>>,
+    ) -> Self {
+        self.interpretations
+            .extend(interpretations.into_iter().map(Build::build));
+        self
+    }
+
+    pub fn clear_interpretations(mut self) -> Self {
+        self.interpretations.clear();
+        self
+    }
+
+    /// Clear the interpretations and get the number of the removed interpretations.
+    pub fn clear_interpretations_counting(self) -> (Self, usize) {
+        let count = self.interpretations.len();
+        (self.clear_interpretations(), count)
+    }
+
+    pub fn add_disease(mut self, disease: impl Build<Disease>) -> Self {
+        self.diseases.push(disease.build());
+        self
+    }
+
+    pub fn extend_diseases(
+        mut self,
+        diseases: impl IntoIterator<Item = impl Build<Disease>>,
+    ) -> Self {
+        self.diseases.extend(diseases.into_iter().map(Build::build));
+        self
+    }
+
+    pub fn clear_diseases(mut self) -> Self {
+        self.diseases.clear();
+        self
+    }
+
+    /// Clear the diseases and get the number of the removed diseases.
+    pub fn clear_diseases_counting(self) -> (Self, usize) {
+        let count = self.diseases.len();
+        (self.clear_diseases(), count)
+    }
+
+    pub fn add_medical_action(mut self, medical_action: impl Build<MedicalAction>) -> Self {
+        self.medical_actions.push(medical_action.build());
+        self
+    }
+
+    pub fn extend_medical_actions(
+        mut self,
+        medical_actions: impl IntoIterator<Item = impl Build<MedicalAction>>,
+    ) -> Self {
+        self.medical_actions
+            .extend(medical_actions.into_iter().map(Build::build));
+        self
+    }
+
+    pub fn clear_medical_actions(mut self) -> Self {
+        self.medical_actions.clear();
+        self
+    }
+
+    /// Clear the medical actions and get the number of the removed medical actions.
+    pub fn clear_medical_actions_counting(self) -> (Self, usize) {
+        let count = self.medical_actions.len();
+        (self.clear_medical_actions(), count)
+    }
+
+    pub fn add_file(mut self, file: impl Build<File>) -> Self {
+        self.files.push(file.build());
+        self
+    }
+
+    pub fn extend_files(mut self, files: impl IntoIterator<Item = impl Build<File>>) -> Self {
+        self.files.extend(files.into_iter().map(Build::build));
+        self
+    }
+
+    pub fn clear_files(mut self) -> Self {
+        self.files.clear();
+        self
+    }
+
+    /// Clear the files and get the number of the removed files.
+    pub fn clear_files_counting(self) -> (Self, usize) {
+        let count = self.files.len();
+        (self.clear_files(), count)
+    }
+
+    /// Make the built phenopacket independent of the order in which the elements were added,
+    /// so that equivalent phenopackets encode into identical bytes.
+    ///
+    /// At build time:
+    /// * the phenotypic features are sorted by their type id,
+    /// * the biosamples and the interpretations by their id,
+    /// * the diseases by their term id,
+    /// * the files by their uri,
+    /// * the metadata resources and external references by their id,
+    /// * and the metadata `created` and update timestamps are truncated to whole seconds.
+    ///
+    /// Elements with the same sort key are ordered by their encoded bytes,
+    /// and the files with the same uri by their sorted attributes and individual identifiers.
+    /// The order of the measurements, the medical actions, and the metadata updates is kept,
+    /// as it is usually chronological.
+    ///
+    /// Prost encodes the map fields of a [`File`] in the arbitrary order of a `HashMap`,
+    /// so encode a phenopacket whose files have more than one attribute or individual identifier
+    /// with [`to_deterministic_bytes`](crate::io::to_deterministic_bytes)
+    /// to get identical bytes.
+    pub fn deterministic(mut self) -> Self {
+        self.deterministic = true;
+        self
+    }
+
+    /// Get the ids of the biosamples added so far.
+    pub fn biosample_ids(&self) -> Vec<&str> {
+        self.biosamples.iter().map(|b| b.id.as_str()).collect()
+    }
+
+    /// Get the term ids of the types of the phenotypic features added so far.
+    ///
+    /// Features without a type are skipped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use phenopacket_builder::{oc, Buildable};
+    /// use phenopackets::schema::v2::core::PhenotypicFeature;
+    /// use phenopackets::schema::v2::Phenopacket;
+    ///
+    /// let builder = Phenopacket::builder()
+    ///                 .add_phenotypic_feature(PhenotypicFeature::builder().r#type(oc("HP:0001250", "Seizure")))
+    ///                 .add_phenotypic_feature(PhenotypicFeature::builder().r#type(oc("HP:0001263", "Global developmental delay")));
+    ///
+    /// assert_eq!(builder.feature_type_ids(), vec!["HP:0001250", "HP:0001263"]);
+    /// ```
+    pub fn feature_type_ids(&self) -> Vec<&str> {
+        self.phenotypic_features
+            .iter()
+            .filter_map(|f| f.r#type.as_ref())
+            .map(|t| t.id.as_str())
+            .collect()
+    }
+
+    /// Get a [`Debug`] representation of the builder that is safe for logging,
+    /// with the phenopacket `id` and the subject's `id`, `alternate_ids`, and `date_of_birth` masked.
+    ///
+    /// The references to individuals are masked too, i.e. the biosample `individual_id`s,
+    /// the genomic interpretation `subject_or_biosample_id`s,
+    /// and the individuals of the file identifiers.
+    pub fn redacted_debug(&self) -> impl Debug + '_ {
+        RedactedPhenopacketBuilder(self)
+    }
+
+    /// Turn the builder into a [`StrictPhenopacketBuilder`]
+    /// which requires the `subject` to be set before building the phenopacket.
+    ///
+    /// The subject must be set on the strict builder,
+    /// even if it has already been set on this builder.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use phenopacket_builder::{Build, Buildable};
+    /// use phenopackets::schema::v2::core::{Individual, MetaData};
+    /// use phenopackets::schema::v2::Phenopacket;
+    /// use prost_types::Timestamp;
+    ///
+    /// let pp: Phenopacket = Phenopacket::builder()
+    ///                         .require_subject()
+    ///                         .id("pp-id")
+    ///                         .subject(Individual::builder().id("subject-id"))
+    ///                         .meta_data(MetaData::builder()
+    ///                             .created(Timestamp::builder().seconds_nanos(0, 0))
+    ///                             .created_by("Peter R.")
+    ///                             .v2()
+    ///                         ).build();
+    ///
+    /// assert_eq!(&pp.subject.unwrap().id, "subject-id");
+    /// ```
+    ///
+    /// The phenopacket cannot be built without the subject:
+    ///
+    /// ```compile_fail
+    /// use phenopacket_builder::{Build, Buildable};
+    /// use phenopackets::schema::v2::core::MetaData;
+    /// use phenopackets::schema::v2::Phenopacket;
+    /// use prost_types::Timestamp;
+    ///
+    /// let pp: Phenopacket = Phenopacket::builder()
+    ///                         .require_subject()
+    ///                         .id("pp-id")
+    ///                         .meta_data(MetaData::builder()
+    ///                             .created(Timestamp::builder().seconds_nanos(0, 0))
+    ///                             .created_by("Peter R.")
+    ///                             .v2()
+    ///                         ).build();
+    /// ```
+    pub fn require_subject(self) -> StrictPhenopacketBuilder<T, U, Unset> {
+        StrictPhenopacketBuilder {
+            builder: self,
+            data: PhantomData,
+        }
+    }
+}
+
+impl PhenopacketBuilder<Set, Set> {
+    /// Build the phenopacket and check it with the strict [`ValidationProfile`].
+    ///
+    /// Intended for tests and debugging, not for production code.
+    ///
+    /// # Panics
+    ///
+    /// Panics with the list of the issues if the phenopacket is not valid.
+    pub fn build_or_panic(self) -> Phenopacket {
+        let phenopacket = self.build();
+        if let Err(issues) = validate_with(&phenopacket, &ValidationProfile::strict()) {
+            let issues: Vec<_> = issues.iter().map(|issue| format!("  - {issue}")).collect();
+            panic!(
+                "phenopacket {} is not valid:\n{}",
+                phenopacket.id,
+                issues.join("\n")
+            );
+        }
+        phenopacket
+    }
+
+    /// Build the phenopacket and check that the id of each ontology class is a CURIE,
+    /// such as `HP:0001250`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the malformed ids, such as `HP_0001250`, in the order they are found.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use phenopacket_builder::{oc, Buildable};
+    /// use phenopackets::schema::v2::core::{MetaData, PhenotypicFeature};
+    /// use phenopackets::schema::v2::Phenopacket;
+    /// use prost_types::Timestamp;
+    ///
+    /// let result = Phenopacket::builder()
+    ///                 .id("pp-id")
+    ///                 .add_phenotypic_feature(PhenotypicFeature::builder().r#type(oc("HP_0001250", "Seizure")))
+    ///                 .meta_data(MetaData::builder()
+    ///                     .created(Timestamp::builder().seconds_nanos(0, 0))
+    ///                     .created_by("Peter R.")
+    ///                     .v2()
+    ///                 )
+    ///                 .build_checked_curies_strict();
+    ///
+    /// assert_eq!(result, Err(vec!["HP_0001250".to_string()]));
+    /// ```
+    pub fn build_checked_curies_strict(self) -> Result<Phenopacket, Vec<String>> {
+        let phenopacket: Phenopacket = self.build();
+        let mut malformed = Vec::new();
+        visit_ontology_classes(&phenopacket, |_, oc| {
+            if !is_curie(&oc.id) {
+                malformed.push(oc.id.clone());
+            }
+        });
+        if malformed.is_empty() {
+            Ok(phenopacket)
+        } else {
+            Err(malformed)
+        }
+    }
+
+    /// Build the phenopacket and write it into bytes in the canonical JSON format.
+    #[cfg(feature = "json")]
+    pub fn build_to_json_bytes(self) -> Result<Vec<u8>, WriteError> {
+        let phenopacket: Phenopacket = self.build();
+        let mut bytes = Vec::new();
+        write_json(&phenopacket, &mut bytes)?;
+        Ok(bytes)
+    }
+
+    /// Build the phenopacket and use it as the proband of a new family.
+    ///
+    /// The metadata of the phenopacket is used as the metadata of the family.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use phenopacket_builder::{Build, Buildable};
+    /// use phenopackets::schema::v2::core::MetaData;
+    /// use phenopackets::schema::v2::{Family, Phenopacket};
+    /// use prost_types::Timestamp;
+    ///
+    /// let family: Family = Phenopacket::builder()
+    ///                         .id("proband-id")
+    ///                         .meta_data(MetaData::builder()
+    ///                             .created(Timestamp::builder().seconds_nanos(0, 0))
+    ///                             .created_by("Peter R.")
+    ///                             .v2()
+    ///                         )
+    ///                         .into_family("family-id")
+    ///                         .build();
+    ///
+    /// assert_eq!(&family.proband.unwrap().id, "proband-id");
+    /// assert_eq!(&family.meta_data.unwrap().created_by, "Peter R.");
+    /// ```
+    pub fn into_family(self, family_id: impl Into<String>) -> FamilyBuilder<Set, Set> {
+        let proband: Phenopacket = self.build();
+        let meta_data = proband.meta_data.clone();
+        FamilyBuilder {
+            id: Some(family_id.into()),
+            proband: Some(proband),
+            relatives: Vec::new(),
+            consanguinous_parents: false,
+            pedigree: None,
+            files: Vec::new(),
+            meta_data,
+            data: PhantomData,
+        }
+    }
 }
 
 impl Buildable for Phenopacket {
     type Builder = PhenopacketBuilder;
 }
 
-impl Build<Phenopacket> for PhenopacketBuilder<Set, Set> {
-    fn build(self) -> Phenopacket {
-        Phenopacket {
-            id: self.id.expect("id must have been set"),
+/// Create a builder pre-populated with the fields of an existing [`Phenopacket`],
+/// e.g. to edit a decoded phenopacket.
+///
+/// The fields are taken as they are, including the empty ones.
+///
+/// # Errors
+///
+/// Returns [`BuildError::MissingField`] if the phenopacket has no `meta_data`.
+///
+/// # Example
+///
+/// ```
+/// use phenopacket_builder::{oc, Build, Buildable};
+/// use phenopacket_builder::v2::PhenopacketBuilder;
+/// use phenopackets::schema::v2::core::{Disease, MetaData};
+/// use phenopackets::schema::v2::Phenopacket;
+///
+/// let pp = Phenopacket {
+///     id: "pp-id".into(),
+///     meta_data: Some(MetaData::default()),
+///     ..Default::default()
+/// };
+///
+/// let pp: Phenopacket = PhenopacketBuilder::try_from(pp)
+///                         .unwrap()
+///                         .add_disease(Disease::builder().term(oc("MONDO:0007915", "Lupus")))
+///                         .build();
+///
+/// assert_eq!(&pp.id, "pp-id");
+/// assert_eq!(pp.diseases.len(), 1);
+/// ```
+impl TryFrom<Phenopacket> for PhenopacketBuilder<Set, Set> {
+    type Error = BuildError;
+
+    fn try_from(phenopacket: Phenopacket) -> Result<Self, Self::Error> {
+        let meta_data = phenopacket
+            .meta_data
+            .ok_or(BuildError::MissingField("meta_data"))?;
+        Ok(PhenopacketBuilder {
+            id: Some(phenopacket.id),
+            subject: phenopacket.subject,
+            phenotypic_features: phenopacket.phenotypic_features,
+            measurements: phenopacket.measurements,
+            biosamples: phenopacket.biosamples,
+            interpretations: phenopacket.interpretations,
+            diseases: phenopacket.diseases,
+            medical_actions: phenopacket.medical_actions,
+            files: phenopacket.files,
+            meta_data: Some(meta_data),
+            deterministic: false,
+            data: PhantomData,
+        })
+    }
+}
+
+impl<T, U> TryBuild<Phenopacket> for PhenopacketBuilder<T, U> {
+    #[allow(clippy::needless_update)]
+    fn try_build(self) -> Result<Phenopacket, BuildError> {
+        let deterministic = self.deterministic;
+        let mut phenopacket = Phenopacket {
+            id: self.id.ok_or(BuildError::MissingField("id"))?,
             subject: self.subject,
             phenotypic_features: self.phenotypic_features,
             measurements: self.measurements,
@@ -104,7 +821,502 @@ impl Build<Phenopacket> for PhenopacketBuilder<Set, Set> {
             diseases: self.diseases,
             medical_actions: self.medical_actions,
             files: self.files,
+            meta_data: Some(
+                self.meta_data
+                    .ok_or(BuildError::MissingField("meta_data"))?,
+            ),
+            ..Default::default()
+        };
+        if deterministic {
+            make_deterministic(&mut phenopacket);
+        }
+        Ok(phenopacket)
+    }
+}
+
+impl Build<Phenopacket> for PhenopacketBuilder<Set, Set> {
+    fn build(self) -> Phenopacket {
+        self.try_build().unwrap_or_else(|e| panic!("{e}"))
+    }
+}
+
+fn make_deterministic(phenopacket: &mut Phenopacket) {
+    sort_by_key(&mut phenopacket.phenotypic_features, |f| {
+        f.r#type.as_ref().map(|t| t.id.clone()).unwrap_or_default()
+    });
+    sort_by_key(&mut phenopacket.biosamples, |b| {
+        (
+            b.id.clone(),
+            b.files.iter().map(file_key).collect::<Vec<_>>(),
+            encode_without_file_maps(b),
+        )
+    });
+    sort_by_key(&mut phenopacket.interpretations, |i| i.id.clone());
+    sort_by_key(&mut phenopacket.diseases, |d| {
+        d.term.as_ref().map(|t| t.id.clone()).unwrap_or_default()
+    });
+    sort_by_key(&mut phenopacket.files, file_key);
+    if let Some(meta_data) = phenopacket.meta_data.as_mut() {
+        sort_by_key(&mut meta_data.resources, |r| r.id.clone());
+        sort_by_key(&mut meta_data.external_references, |r| r.id.clone());
+        let timestamps = meta_data.created.iter_mut().chain(
+            meta_data
+                .updates
+                .iter_mut()
+                .filter_map(|u| u.timestamp.as_mut()),
+        );
+        for timestamp in timestamps {
+            timestamp.nanos = 0;
+        }
+    }
+}
+
+/// Sort the elements by the key, breaking the ties by the encoded bytes.
+///
+/// The encoded bytes are only stable for messages without map fields,
+/// so the key of the messages with files must cover the files' map entries.
+fn sort_by_key<M: Message, K: Ord>(elements: &mut [M], key: impl Fn(&M) -> K) {
+    elements.sort_by_cached_key(|e| (key(e), e.encode_to_vec()));
+}
+
+type FileKey = (String, Vec<(String, String)>, Vec<(String, String)>);
+
+/// Get the uri and the sorted map entries of the file.
+fn file_key(file: &File) -> FileKey {
+    let sorted = |entries: &HashMap<String, String>| {
+        let mut entries: Vec<_> = entries
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect();
+        entries.sort();
+        entries
+    };
+    (
+        file.uri.clone(),
+        sorted(&file.individual_to_file_identifiers),
+        sorted(&file.file_attributes),
+    )
+}
+
+/// Encode the biosample with the map fields of its files cleared.
+fn encode_without_file_maps(biosample: &Biosample) -> Vec<u8> {
+    let mut biosample = biosample.clone();
+    for file in &mut biosample.files {
+        file.individual_to_file_identifiers.clear();
+        file.file_attributes.clear();
+    }
+    biosample.encode_to_vec()
+}
+
+struct RedactedPhenopacketBuilder<'a, T, U>(&'a PhenopacketBuilder<T, U>);
+
+impl<T, U> Debug for RedactedPhenopacketBuilder<'_, T, U> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let builder = self.0;
+        f.debug_struct("PhenopacketBuilder")
+            .field("id", &redact_option(&builder.id))
+            .field("subject", &builder.subject.as_ref().map(RedactedIndividual))
+            .field("phenotypic_features", &builder.phenotypic_features)
+            .field("measurements", &builder.measurements)
+            .field("biosamples", &redact_biosamples(&builder.biosamples))
+            .field(
+                "interpretations",
+                &redact_interpretations(&builder.interpretations),
+            )
+            .field("diseases", &builder.diseases)
+            .field("medical_actions", &builder.medical_actions)
+            .field("files", &redact_files(&builder.files))
+            .field("meta_data", &builder.meta_data)
+            .field("deterministic", &builder.deterministic)
+            .finish()
+    }
+}
+
+/// A [`PhenopacketBuilder`] that, on top of `id` and `meta_data`,
+/// requires the `subject` to be set before building the phenopacket.
+///
+/// Use [`PhenopacketBuilder::require_subject`] to create the builder.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct StrictPhenopacketBuilder<T = Unset, U = Unset, V = Unset> {
+    builder: PhenopacketBuilder<T, U>,
+    data: PhantomData<V>,
+}
+
+impl<U, V> StrictPhenopacketBuilder<Unset, U, V> {
+    pub fn id(self, id: impl Into<String>) -> StrictPhenopacketBuilder<Set, U, V> {
+        StrictPhenopacketBuilder {
+            builder: self.builder.id(id),
+            data: PhantomData,
+        }
+    }
+}
+
+impl<T, V> StrictPhenopacketBuilder<T, Unset, V> {
+    pub fn meta_data(self, meta_data: impl Build<MetaData>) -> StrictPhenopacketBuilder<T, Set, V> {
+        StrictPhenopacketBuilder {
+            builder: self.builder.meta_data(meta_data),
+            data: PhantomData,
+        }
+    }
+}
+
+impl<T, U> StrictPhenopacketBuilder<T, U, Unset> {
+    pub fn subject(self, subject: impl Build<Individual>) -> StrictPhenopacketBuilder<T, U, Set> {
+        StrictPhenopacketBuilder {
+            builder: self.builder.subject(subject),
+            data: PhantomData,
+        }
+    }
+
+    pub fn subject_id(self, id: impl Into<String>) -> StrictPhenopacketBuilder<T, U, Set> {
+        StrictPhenopacketBuilder {
+            builder: self.builder.subject_id(id),
+            data: PhantomData,
+        }
+    }
+}
+
+impl<T, U, V> StrictPhenopacketBuilder<T, U, V> {
+    pub fn add_phenotypic_feature(
+        mut self,
+        phenotypic_feature: impl Build<PhenotypicFeature>,
+    ) -> Self {
+        self.builder = self.builder.add_phenotypic_feature(phenotypic_feature);
+        self
+    }
+
+    pub fn extend_phenotypic_features(
+        mut self,
+        phenotypic_features: impl IntoIterator<Item = impl Build<PhenotypicFeature>>,
+    ) -> Self {
+        self.builder = self.builder.extend_phenotypic_features(phenotypic_features);
+        self
+    }
+
+    pub fn clear_phenotypic_features(mut self) -> Self {
+        self.builder = self.builder.clear_phenotypic_features();
+        self
+    }
+
+    pub fn set_phenotypic_features(
+        mut self,
+        phenotypic_features: impl IntoIterator<Item = impl Build<PhenotypicFeature>>,
+    ) -> Self {
+        self.builder = self.builder.set_phenotypic_features(phenotypic_features);
+        self
+    }
+
+    pub fn add_biosample(mut self, biosample: impl Build<Biosample>) -> Self {
+        self.builder = self.builder.add_biosample(biosample);
+        self
+    }
+
+    pub fn extend_biosamples(
+        mut self,
+        biosamples: impl IntoIterator<Item = impl Build<Biosample>>,
+    ) -> Self {
+        self.builder = self.builder.extend_biosamples(biosamples);
+        self
+    }
+
+    pub fn clear_biosamples(mut self) -> Self {
+        self.builder = self.builder.clear_biosamples();
+        self
+    }
+
+    pub fn add_measurement(mut self, measurement: impl Build<Measurement>) -> Self {
+        self.builder = self.builder.add_measurement(measurement);
+        self
+    }
+
+    pub fn extend_measurements(
+        mut self,
+        measurements: impl IntoIterator<Item = impl Build<Measurement>>,
+    ) -> Self {
+        self.builder = self.builder.extend_measurements(measurements);
+        self
+    }
+
+    pub fn clear_measurements(mut self) -> Self {
+        self.builder = self.builder.clear_measurements();
+        self
+    }
+
+    pub fn add_interpretation(mut self, interpretation: impl Build<Interpretation>) -> Self {
+        self.builder = self.builder.add_interpretation(interpretation);
+        self
+    }
+
+    pub fn extend_interpretations(
+        mut self,
+        interpretations: impl IntoIterator<Item = impl Build<Interpretation>>,
+    ) -> Self {
+        self.builder = self.builder.extend_interpretations(interpretations);
+        self
+    }
+
+    pub fn clear_interpretations(mut self) -> Self {
+        self.builder = self.builder.clear_interpretations();
+        self
+    }
+
+    pub fn add_disease(mut self, disease: impl Build<Disease>) -> Self {
+        self.builder = self.builder.add_disease(disease);
+        self
+    }
+
+    pub fn extend_diseases(
+        mut self,
+        diseases: impl IntoIterator<Item = impl Build<Disease>>,
+    ) -> Self {
+        self.builder = self.builder.extend_diseases(diseases);
+        self
+    }
+
+    pub fn clear_diseases(mut self) -> Self {
+        self.builder = self.builder.clear_diseases();
+        self
+    }
+
+    pub fn add_medical_action(mut self, medical_action: impl Build<MedicalAction>) -> Self {
+        self.builder = self.builder.add_medical_action(medical_action);
+        self
+    }
+
+    pub fn extend_medical_actions(
+        mut self,
+        medical_actions: impl IntoIterator<Item = impl Build<MedicalAction>>,
+    ) -> Self {
+        self.builder = self.builder.extend_medical_actions(medical_actions);
+        self
+    }
+
+    pub fn clear_medical_actions(mut self) -> Self {
+        self.builder = self.builder.clear_medical_actions();
+        self
+    }
+
+    pub fn add_file(mut self, file: impl Build<File>) -> Self {
+        self.builder = self.builder.add_file(file);
+        self
+    }
+
+    pub fn extend_files(mut self, files: impl IntoIterator<Item = impl Build<File>>) -> Self {
+        self.builder = self.builder.extend_files(files);
+        self
+    }
+
+    pub fn clear_files(mut self) -> Self {
+        self.builder = self.builder.clear_files();
+        self
+    }
+}
+
+impl<T, U, V> TryBuild<Phenopacket> for StrictPhenopacketBuilder<T, U, V> {
+    fn try_build(self) -> Result<Phenopacket, BuildError> {
+        if self.builder.subject.is_none() {
+            return Err(BuildError::MissingField("subject"));
+        }
+        self.builder.try_build()
+    }
+}
+
+impl Build<Phenopacket> for StrictPhenopacketBuilder<Set, Set, Set> {
+    fn build(self) -> Phenopacket {
+        self.try_build().unwrap_or_else(|e| panic!("{e}"))
+    }
+}
+
+/// The metadata and phenotypic features shared by phenopackets, such as those of a cohort.
+///
+/// # Example
+///
+/// ```
+/// use phenopacket_builder::{oc, Build, Buildable};
+/// use phenopacket_builder::v2::PhenopacketTemplate;
+/// use phenopackets::schema::v2::core::{Individual, MetaData, PhenotypicFeature};
+/// use phenopackets::schema::v2::Phenopacket;
+/// use prost_types::Timestamp;
+///
+/// let template = PhenopacketTemplate::new(MetaData::builder()
+///                                             .created(Timestamp::builder().seconds_nanos(0, 0))
+///                                             .created_by("Peter R.")
+///                                             .v2())
+///                     .add_phenotypic_feature(PhenotypicFeature::builder().r#type(oc("HP:0001250", "Seizure")));
+///
+/// let pp: Phenopacket = template.instantiate("pp-id", Individual::builder().id("subject-id")).build();
+///
+/// assert_eq!(&pp.meta_data.unwrap().created_by, "Peter R.");
+/// assert_eq!(pp.phenotypic_features.len(), 1);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct PhenopacketTemplate {
+    meta_data: MetaData,
+    phenotypic_features: Vec<PhenotypicFeature>,
+}
+
+impl PhenopacketTemplate {
+    pub fn new(meta_data: impl Build<MetaData>) -> Self {
+        PhenopacketTemplate {
+            meta_data: meta_data.build(),
+            phenotypic_features: Vec::new(),
+        }
+    }
+
+    pub fn add_phenotypic_feature(
+        mut self,
+        phenotypic_feature: impl Build<PhenotypicFeature>,
+    ) -> Self {
+        self.phenotypic_features.push(phenotypic_feature.build());
+        self
+    }
+
+    pub fn extend_phenotypic_features(
+        mut self,
+        phenotypic_features: impl IntoIterator<Item = impl Build<PhenotypicFeature>>,
+    ) -> Self {
+        self.phenotypic_features
+            .extend(phenotypic_features.into_iter().map(Build::build));
+        self
+    }
+
+    pub fn clear_phenotypic_features(mut self) -> Self {
+        self.phenotypic_features.clear();
+        self
+    }
+
+    /// Start a phenopacket of the `subject` with a copy of the template metadata and features.
+    pub fn instantiate(
+        &self,
+        id: impl Into<String>,
+        subject: impl Build<Individual>,
+    ) -> PhenopacketBuilder<Set, Set> {
+        PhenopacketBuilder::default()
+            .id(id)
+            .meta_data(self.meta_data.clone())
+            .subject(subject)
+            .extend_phenotypic_features(self.phenotypic_features.iter().cloned())
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct FamilyBuilder<T = Unset, U = Unset> {
+    id: Option<String>,
+    proband: Option<Phenopacket>,
+    relatives: Vec<Phenopacket>,
+    consanguinous_parents: bool,
+    pedigree: Option<Pedigree>,
+    files: Vec<File>,
+    meta_data: Option<MetaData>,
+    data: PhantomData<(T, U)>,
+}
+
+impl<U> FamilyBuilder<Unset, U> {
+    pub fn id(self, id: impl Into<String>) -> FamilyBuilder<Set, U> {
+        FamilyBuilder {
+            id: Some(id.into()),
+            proband: self.proband,
+            relatives: self.relatives,
+            consanguinous_parents: self.consanguinous_parents,
+            pedigree: self.pedigree,
+            files: self.files,
             meta_data: self.meta_data,
+            data: PhantomData,
+        }
+    }
+}
+
+impl<T> FamilyBuilder<T, Unset> {
+    pub fn meta_data(self, meta_data: impl Build<MetaData>) -> FamilyBuilder<T, Set> {
+        FamilyBuilder {
+            id: self.id,
+            proband: self.proband,
+            relatives: self.relatives,
+            consanguinous_parents: self.consanguinous_parents,
+            pedigree: self.pedigree,
+            files: self.files,
+            meta_data: Some(meta_data.build()),
+            data: PhantomData,
         }
     }
 }
+
+impl<T, U> FamilyBuilder<T, U> {
+    pub fn proband(mut self, proband: impl Build<Phenopacket>) -> Self {
+        self.proband = Some(proband.build());
+        self
+    }
+
+    pub fn add_relative(mut self, relative: impl Build<Phenopacket>) -> Self {
+        self.relatives.push(relative.build());
+        self
+    }
+
+    pub fn extend_relatives(
+        mut self,
+        relatives: impl IntoIterator<Item = impl Build<Phenopacket>>,
+    ) -> Self {
+        self.relatives
+            .extend(relatives.into_iter().map(Build::build));
+        self
+    }
+
+    pub fn clear_relatives(mut self) -> Self {
+        self.relatives.clear();
+        self
+    }
+
+    pub fn consanguinous_parents(mut self, consanguinous_parents: bool) -> Self {
+        self.consanguinous_parents = consanguinous_parents;
+        self
+    }
+
+    pub fn pedigree(mut self, pedigree: impl Build<Pedigree>) -> Self {
+        self.pedigree = Some(pedigree.build());
+        self
+    }
+
+    pub fn add_file(mut self, file: impl Build<File>) -> Self {
+        self.files.push(file.build());
+        self
+    }
+
+    pub fn extend_files(mut self, files: impl IntoIterator<Item = impl Build<File>>) -> Self {
+        self.files.extend(files.into_iter().map(Build::build));
+        self
+    }
+
+    pub fn clear_files(mut self) -> Self {
+        self.files.clear();
+        self
+    }
+}
+
+impl Buildable for Family {
+    type Builder = FamilyBuilder;
+}
+
+impl<T, U> TryBuild<Family> for FamilyBuilder<T, U> {
+    #[allow(clippy::needless_update)]
+    fn try_build(self) -> Result<Family, BuildError> {
+        Ok(Family {
+            id: self.id.ok_or(BuildError::MissingField("id"))?,
+            proband: self.proband,
+            relatives: self.relatives,
+            consanguinous_parents: self.consanguinous_parents,
+            pedigree: self.pedigree,
+            files: self.files,
+            meta_data: Some(
+                self.meta_data
+                    .ok_or(BuildError::MissingField("meta_data"))?,
+            ),
+            ..Default::default()
+        })
+    }
+}
+
+impl Build<Family> for FamilyBuilder<Set, Set> {
+    fn build(self) -> Family {
+        self.try_build().unwrap_or_else(|e| panic!("{e}"))
+    }
+}
@@ -0,0 +1,367 @@
+//! Validation of assembled phenopackets.
+//!
+//! # Example
+//!
+//! ```
+//! use phenopacket_builder::validate::{validate_with, ValidationProfile};
+//! use phenopackets::schema::v2::Phenopacket;
+//!
+//! let pp = Phenopacket::default();
+//!
+//! assert!(validate_with(&pp, &ValidationProfile::strict()).is_ok());
+//! ```
+
+use crate::time::{add_duration, parse_iso8601_duration};
+use crate::visit::{
+    visit_ontology_classes, visit_term_curies, visit_time_elements, visit_time_intervals,
+};
+use phenopackets::schema::v2::core::time_element::Element;
+use phenopackets::schema::v2::core::Age;
+use phenopackets::schema::v2::Phenopacket;
+use prost_types::Timestamp;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Display, Formatter};
+
+/// The severity of a [`ValidationIssue`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+/// An issue found in a phenopacket.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ValidationIssue {
+    /// The disease of an interpretation's diagnosis is not among the phenopacket's diseases.
+    OrphanDiagnosisDisease { path: String, id: String },
+    /// The ontology class has an empty label.
+    EmptyTermLabel { path: String, id: String },
+    /// The phenotypic feature type is both observed and excluded.
+    ContradictoryFeature { path: String, id: String },
+    /// The phenotypic feature type is present more than once, with the same status.
+    DuplicateFeature { path: String, id: String },
+    /// The start of the time interval or of the age range is after its end.
+    StartAfterEnd { path: String },
+    /// The file has an empty `uri`.
+    EmptyFileUri { path: String },
+    /// The file `uri` has no scheme, such as `file://`, and is likely a bare path.
+    FileUriWithoutScheme { path: String, uri: String },
+    /// The phenopacket has phenotypic features but no subject.
+    FeaturesWithoutSubject { path: String },
+    /// The id refers to neither the subject nor a biosample of the phenopacket.
+    DanglingReference { path: String, id: String },
+    /// The prefix of the CURIE is not declared by any of the metadata resources.
+    UndeclaredPrefix { path: String, prefix: String },
+}
+
+impl ValidationIssue {
+    pub fn severity(&self) -> Severity {
+        match self {
+            ValidationIssue::OrphanDiagnosisDisease { .. }
+            | ValidationIssue::EmptyTermLabel { .. }
+            | ValidationIssue::DuplicateFeature { .. }
+            | ValidationIssue::FileUriWithoutScheme { .. }
+            | ValidationIssue::FeaturesWithoutSubject { .. }
+            | ValidationIssue::UndeclaredPrefix { .. } => Severity::Warning,
+            ValidationIssue::ContradictoryFeature { .. }
+            | ValidationIssue::DanglingReference { .. }
+            | ValidationIssue::StartAfterEnd { .. }
+            | ValidationIssue::EmptyFileUri { .. } => Severity::Error,
+        }
+    }
+
+    /// Get the path to the offending element, such as `interpretations[0].diagnosis.disease`.
+    pub fn path(&self) -> &str {
+        match self {
+            ValidationIssue::OrphanDiagnosisDisease { path, .. }
+            | ValidationIssue::EmptyTermLabel { path, .. }
+            | ValidationIssue::ContradictoryFeature { path, .. }
+            | ValidationIssue::DuplicateFeature { path, .. }
+            | ValidationIssue::StartAfterEnd { path }
+            | ValidationIssue::EmptyFileUri { path }
+            | ValidationIssue::FileUriWithoutScheme { path, .. }
+            | ValidationIssue::FeaturesWithoutSubject { path }
+            | ValidationIssue::DanglingReference { path, .. }
+            | ValidationIssue::UndeclaredPrefix { path, .. } => path,
+        }
+    }
+}
+
+impl Display for ValidationIssue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ValidationIssue::OrphanDiagnosisDisease { path, id } => write!(
+                f,
+                "{path}: diagnosis disease {id} is not among the phenopacket diseases"
+            ),
+            ValidationIssue::EmptyTermLabel { path, id } => {
+                write!(f, "{path}: term {id} has an empty label")
+            }
+            ValidationIssue::ContradictoryFeature { path, id } => {
+                write!(f, "{path}: feature {id} is both observed and excluded")
+            }
+            ValidationIssue::DuplicateFeature { path, id } => {
+                write!(f, "{path}: feature {id} is present more than once")
+            }
+            ValidationIssue::StartAfterEnd { path } => write!(f, "{path}: start is after end"),
+            ValidationIssue::EmptyFileUri { path } => write!(f, "{path}: file uri is empty"),
+            ValidationIssue::FileUriWithoutScheme { path, uri } => {
+                write!(f, "{path}: file uri {uri} has no scheme")
+            }
+            ValidationIssue::FeaturesWithoutSubject { path } => {
+                write!(
+                    f,
+                    "{path}: subject is missing but phenotypic features are present"
+                )
+            }
+            ValidationIssue::DanglingReference { path, id } => {
+                write!(f, "{path}: {id} is neither the subject nor a biosample")
+            }
+            ValidationIssue::UndeclaredPrefix { path, prefix } => {
+                write!(
+                    f,
+                    "{path}: prefix {prefix} is not declared in the resources"
+                )
+            }
+        }
+    }
+}
+
+/// The optional rules to apply in [`validate_with`].
+///
+/// All optional rules are off by default.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ValidationProfile {
+    /// Warn if the disease of an interpretation's diagnosis is not among the phenopacket's diseases.
+    pub interpretations_reference_diseases: bool,
+    /// Warn if the phenopacket has phenotypic features but no subject.
+    pub features_require_subject: bool,
+}
+
+impl ValidationProfile {
+    /// Get a profile with all optional rules turned on.
+    pub fn strict() -> Self {
+        ValidationProfile {
+            interpretations_reference_diseases: true,
+            features_require_subject: true,
+        }
+    }
+}
+
+/// Validate the phenopacket using the default [`ValidationProfile`].
+pub fn validate(phenopacket: &Phenopacket) -> Result<(), Vec<ValidationIssue>> {
+    validate_with(phenopacket, &ValidationProfile::default())
+}
+
+/// Validate the phenopacket, applying the optional rules turned on in the `profile`.
+///
+/// All issues are reported, regardless of their [`Severity`].
+pub fn validate_with(
+    phenopacket: &Phenopacket,
+    profile: &ValidationProfile,
+) -> Result<(), Vec<ValidationIssue>> {
+    let mut issues = vec![];
+
+    check_term_labels(phenopacket, &mut issues);
+    check_duplicate_feature_types(phenopacket, &mut issues);
+    check_start_before_end(phenopacket, &mut issues);
+    check_file_uris(phenopacket, &mut issues);
+    check_references_resolve(phenopacket, &mut issues);
+    check_prefixes_declared(phenopacket, &mut issues);
+    if profile.interpretations_reference_diseases {
+        check_interpretations_reference_diseases(phenopacket, &mut issues);
+    }
+    if profile.features_require_subject {
+        check_features_require_subject(phenopacket, &mut issues);
+    }
+
+    if issues.is_empty() {
+        Ok(())
+    } else {
+        Err(issues)
+    }
+}
+
+fn check_interpretations_reference_diseases(
+    phenopacket: &Phenopacket,
+    issues: &mut Vec<ValidationIssue>,
+) {
+    for (i, interpretation) in phenopacket.interpretations.iter().enumerate() {
+        let Some(disease) = interpretation
+            .diagnosis
+            .as_ref()
+            .and_then(|diagnosis| diagnosis.disease.as_ref())
+        else {
+            continue;
+        };
+
+        let is_known = phenopacket
+            .diseases
+            .iter()
+            .filter_map(|d| d.term.as_ref())
+            .any(|term| term.id == disease.id);
+        if !is_known {
+            issues.push(ValidationIssue::OrphanDiagnosisDisease {
+                path: format!("interpretations[{i}].diagnosis.disease"),
+                id: disease.id.clone(),
+            });
+        }
+    }
+}
+
+fn check_features_require_subject(phenopacket: &Phenopacket, issues: &mut Vec<ValidationIssue>) {
+    if !phenopacket.phenotypic_features.is_empty() && phenopacket.subject.is_none() {
+        issues.push(ValidationIssue::FeaturesWithoutSubject {
+            path: "subject".into(),
+        });
+    }
+}
+
+fn check_references_resolve(phenopacket: &Phenopacket, issues: &mut Vec<ValidationIssue>) {
+    let subject_id = phenopacket.subject.as_ref().map(|s| s.id.as_str());
+
+    for (i, biosample) in phenopacket.biosamples.iter().enumerate() {
+        if !biosample.individual_id.is_empty() && subject_id != Some(&biosample.individual_id) {
+            issues.push(ValidationIssue::DanglingReference {
+                path: format!("biosamples[{i}].individual_id"),
+                id: biosample.individual_id.clone(),
+            });
+        }
+    }
+
+    let known_ids: HashSet<_> = subject_id
+        .into_iter()
+        .chain(phenopacket.biosamples.iter().map(|b| b.id.as_str()))
+        .collect();
+    for (i, interpretation) in phenopacket.interpretations.iter().enumerate() {
+        let Some(diagnosis) = &interpretation.diagnosis else {
+            continue;
+        };
+        for (j, gi) in diagnosis.genomic_interpretations.iter().enumerate() {
+            if !known_ids.contains(gi.subject_or_biosample_id.as_str()) {
+                issues.push(ValidationIssue::DanglingReference {
+                    path: format!(
+                        "interpretations[{i}].diagnosis.genomic_interpretations[{j}].subject_or_biosample_id"
+                    ),
+                    id: gi.subject_or_biosample_id.clone(),
+                });
+            }
+        }
+    }
+}
+
+fn check_prefixes_declared(phenopacket: &Phenopacket, issues: &mut Vec<ValidationIssue>) {
+    let declared: HashSet<_> = phenopacket
+        .meta_data
+        .iter()
+        .flat_map(|md| md.resources.iter())
+        .map(|r| r.namespace_prefix.as_str())
+        .collect();
+    visit_term_curies(phenopacket, |path, id| {
+        if let Some((prefix, _)) = id.split_once(':') {
+            if !declared.contains(prefix) {
+                issues.push(ValidationIssue::UndeclaredPrefix {
+                    path: path.to_string(),
+                    prefix: prefix.to_string(),
+                });
+            }
+        }
+    });
+}
+
+fn check_term_labels(phenopacket: &Phenopacket, issues: &mut Vec<ValidationIssue>) {
+    visit_ontology_classes(phenopacket, |path, oc| {
+        if oc.label.trim().is_empty() {
+            issues.push(ValidationIssue::EmptyTermLabel {
+                path: path.to_string(),
+                id: oc.id.clone(),
+            });
+        }
+    });
+}
+
+fn check_duplicate_feature_types(phenopacket: &Phenopacket, issues: &mut Vec<ValidationIssue>) {
+    let mut excluded_by_id = HashMap::new();
+    for (i, feature) in phenopacket.phenotypic_features.iter().enumerate() {
+        let Some(r#type) = &feature.r#type else {
+            continue;
+        };
+        match excluded_by_id.entry(r#type.id.as_str()) {
+            Entry::Vacant(entry) => {
+                entry.insert(feature.excluded);
+            }
+            Entry::Occupied(entry) => {
+                let path = format!("phenotypic_features[{i}]");
+                let id = r#type.id.clone();
+                issues.push(if *entry.get() == feature.excluded {
+                    ValidationIssue::DuplicateFeature { path, id }
+                } else {
+                    ValidationIssue::ContradictoryFeature { path, id }
+                });
+            }
+        }
+    }
+}
+
+fn check_start_before_end(phenopacket: &Phenopacket, issues: &mut Vec<ValidationIssue>) {
+    visit_time_intervals(phenopacket, |path, interval| {
+        if let (Some(start), Some(end)) = (&interval.start, &interval.end) {
+            if (start.seconds, start.nanos) > (end.seconds, end.nanos) {
+                issues.push(ValidationIssue::StartAfterEnd {
+                    path: path.to_string(),
+                });
+            }
+        }
+    });
+    visit_time_elements(phenopacket, |path, te| {
+        if let Some(Element::AgeRange(age_range)) = &te.element {
+            if let (Some(start), Some(end)) = (
+                age_range.start.as_ref().and_then(age_in_seconds),
+                age_range.end.as_ref().and_then(age_in_seconds),
+            ) {
+                if start > end {
+                    issues.push(ValidationIssue::StartAfterEnd {
+                        path: path.to_string(),
+                    });
+                }
+            }
+        }
+    });
+}
+
+/// Get the age in seconds, counting from the Unix epoch.
+fn age_in_seconds(age: &Age) -> Option<i64> {
+    let duration = parse_iso8601_duration(&age.iso8601duration)?;
+    Some(add_duration(&Timestamp::default(), &duration).seconds)
+}
+
+fn check_file_uris(phenopacket: &Phenopacket, issues: &mut Vec<ValidationIssue>) {
+    let files = phenopacket
+        .files
+        .iter()
+        .enumerate()
+        .map(|(i, file)| (format!("files[{i}]"), file))
+        .chain(
+            phenopacket
+                .biosamples
+                .iter()
+                .enumerate()
+                .flat_map(|(i, b)| {
+                    b.files
+                        .iter()
+                        .enumerate()
+                        .map(move |(j, file)| (format!("biosamples[{i}].files[{j}]"), file))
+                }),
+        );
+    for (path, file) in files {
+        if file.uri.trim().is_empty() {
+            issues.push(ValidationIssue::EmptyFileUri { path });
+        } else if !file.uri.contains("://") {
+            issues.push(ValidationIssue::FileUriWithoutScheme {
+                path,
+                uri: file.uri.clone(),
+            });
+        }
+    }
+}
@@ -0,0 +1,395 @@
+//! Traversal of the building blocks of a phenopacket.
+
+use phenopackets::ga4gh::vrsatile::v1::{GeneDescriptor, VariationDescriptor};
+use phenopackets::schema::v2::core::genomic_interpretation::Call;
+use phenopackets::schema::v2::core::measurement::MeasurementValue;
+use phenopackets::schema::v2::core::medical_action::Action;
+use phenopackets::schema::v2::core::therapeutic_regimen::Identifier;
+use phenopackets::schema::v2::core::time_element::Element;
+use phenopackets::schema::v2::core::{
+    value, Biosample, Disease, ExternalReference, Individual, Interpretation, Measurement,
+    MedicalAction, OntologyClass, PhenotypicFeature, Procedure, Quantity, TimeElement,
+    TimeInterval,
+};
+use phenopackets::schema::v2::Phenopacket;
+
+/// Callbacks for the building blocks found by [`visit`].
+///
+/// The paths use the schema field names, e.g. `phenotypic_features[0].modifiers[1]`.
+trait Visitor {
+    fn ontology_class(&mut self, _path: &str, _oc: &OntologyClass) {}
+
+    fn time_element(&mut self, _path: &str, _te: &TimeElement) {}
+
+    fn time_interval(&mut self, _path: &str, _ti: &TimeInterval) {}
+
+    fn gene_descriptor(&mut self, _path: &str, _gd: &GeneDescriptor) {}
+
+    fn external_reference(&mut self, _path: &str, _er: &ExternalReference) {}
+}
+
+/// Call `f` with the path and the value of each ontology class of the phenopacket,
+/// including those used as time elements.
+pub(crate) fn visit_ontology_classes(
+    phenopacket: &Phenopacket,
+    f: impl FnMut(&str, &OntologyClass),
+) {
+    struct OntologyClasses<F>(F);
+
+    impl<F: FnMut(&str, &OntologyClass)> Visitor for OntologyClasses<F> {
+        fn ontology_class(&mut self, path: &str, oc: &OntologyClass) {
+            (self.0)(path, oc)
+        }
+    }
+
+    visit(phenopacket, &mut OntologyClasses(f));
+}
+
+/// Call `f` with the path and the value of each time element of the phenopacket.
+pub(crate) fn visit_time_elements(phenopacket: &Phenopacket, f: impl FnMut(&str, &TimeElement)) {
+    struct TimeElements<F>(F);
+
+    impl<F: FnMut(&str, &TimeElement)> Visitor for TimeElements<F> {
+        fn time_element(&mut self, path: &str, te: &TimeElement) {
+            (self.0)(path, te)
+        }
+    }
+
+    visit(phenopacket, &mut TimeElements(f));
+}
+
+/// Call `f` with the path and the value of each time interval of the phenopacket,
+/// including those used as time elements, e.g. the intervals of the dose intervals.
+pub(crate) fn visit_time_intervals(phenopacket: &Phenopacket, f: impl FnMut(&str, &TimeInterval)) {
+    struct TimeIntervals<F>(F);
+
+    impl<F: FnMut(&str, &TimeInterval)> Visitor for TimeIntervals<F> {
+        fn time_interval(&mut self, path: &str, ti: &TimeInterval) {
+            (self.0)(path, ti)
+        }
+    }
+
+    visit(phenopacket, &mut TimeIntervals(f));
+}
+
+/// Call `f` with the path and the value of each CURIE of the phenopacket,
+/// i.e. the ids of the ontology classes, the gene descriptors, and the external references.
+pub(crate) fn visit_curies(phenopacket: &Phenopacket, f: impl FnMut(&str, &str)) {
+    struct Curies<F>(F);
+
+    impl<F: FnMut(&str, &str)> Visitor for Curies<F> {
+        fn ontology_class(&mut self, path: &str, oc: &OntologyClass) {
+            (self.0)(path, &oc.id)
+        }
+
+        fn gene_descriptor(&mut self, path: &str, gd: &GeneDescriptor) {
+            (self.0)(path, &gd.value_id)
+        }
+
+        fn external_reference(&mut self, path: &str, er: &ExternalReference) {
+            (self.0)(path, &er.id)
+        }
+    }
+
+    visit(phenopacket, &mut Curies(f));
+}
+
+/// Call `f` with the path and the value of each ontology class and gene descriptor id
+/// of the phenopacket, i.e. the CURIEs that are expected to have a metadata resource.
+pub(crate) fn visit_term_curies(phenopacket: &Phenopacket, f: impl FnMut(&str, &str)) {
+    struct TermCuries<F>(F);
+
+    impl<F: FnMut(&str, &str)> Visitor for TermCuries<F> {
+        fn ontology_class(&mut self, path: &str, oc: &OntologyClass) {
+            (self.0)(path, &oc.id)
+        }
+
+        fn gene_descriptor(&mut self, path: &str, gd: &GeneDescriptor) {
+            (self.0)(path, &gd.value_id)
+        }
+    }
+
+    visit(phenopacket, &mut TermCuries(f));
+}
+
+/// Report the building blocks of the phenopacket to the `visitor`.
+fn visit(phenopacket: &Phenopacket, v: &mut dyn Visitor) {
+    if let Some(subject) = &phenopacket.subject {
+        individual("subject", subject, v);
+    }
+    for (i, feature) in phenopacket.phenotypic_features.iter().enumerate() {
+        phenotypic_feature(&format!("phenotypic_features[{i}]"), feature, v);
+    }
+    for (i, m) in phenopacket.measurements.iter().enumerate() {
+        measurement(&format!("measurements[{i}]"), m, v);
+    }
+    for (i, b) in phenopacket.biosamples.iter().enumerate() {
+        biosample(&format!("biosamples[{i}]"), b, v);
+    }
+    for (i, interp) in phenopacket.interpretations.iter().enumerate() {
+        interpretation(&format!("interpretations[{i}]"), interp, v);
+    }
+    for (i, d) in phenopacket.diseases.iter().enumerate() {
+        disease(&format!("diseases[{i}]"), d, v);
+    }
+    for (i, action) in phenopacket.medical_actions.iter().enumerate() {
+        medical_action(&format!("medical_actions[{i}]"), action, v);
+    }
+    if let Some(meta_data) = &phenopacket.meta_data {
+        for (i, er) in meta_data.external_references.iter().enumerate() {
+            v.external_reference(&format!("meta_data.external_references[{i}]"), er);
+        }
+    }
+}
+
+fn term(path: &str, field: &str, oc: &Option<OntologyClass>, v: &mut dyn Visitor) {
+    if let Some(oc) = oc {
+        v.ontology_class(&format!("{path}.{field}"), oc);
+    }
+}
+
+fn terms(path: &str, field: &str, ocs: &[OntologyClass], v: &mut dyn Visitor) {
+    for (i, oc) in ocs.iter().enumerate() {
+        v.ontology_class(&format!("{path}.{field}[{i}]"), oc);
+    }
+}
+
+fn time_element(path: &str, field: &str, te: &Option<TimeElement>, v: &mut dyn Visitor) {
+    if let Some(te) = te {
+        let path = format!("{path}.{field}");
+        v.time_element(&path, te);
+        match &te.element {
+            Some(Element::OntologyClass(oc)) => v.ontology_class(&path, oc),
+            Some(Element::Interval(ti)) => v.time_interval(&path, ti),
+            _ => {}
+        }
+    }
+}
+
+fn individual(path: &str, individual: &Individual, v: &mut dyn Visitor) {
+    time_element(
+        path,
+        "time_at_last_encounter",
+        &individual.time_at_last_encounter,
+        v,
+    );
+    if let Some(vital_status) = &individual.vital_status {
+        let path = format!("{path}.vital_status");
+        time_element(&path, "time_of_death", &vital_status.time_of_death, v);
+        term(&path, "cause_of_death", &vital_status.cause_of_death, v);
+    }
+    term(path, "gender", &individual.gender, v);
+    term(path, "taxonomy", &individual.taxonomy, v);
+}
+
+fn phenotypic_feature(path: &str, feature: &PhenotypicFeature, v: &mut dyn Visitor) {
+    term(path, "type", &feature.r#type, v);
+    term(path, "severity", &feature.severity, v);
+    terms(path, "modifiers", &feature.modifiers, v);
+    time_element(path, "onset", &feature.onset, v);
+    time_element(path, "resolution", &feature.resolution, v);
+    for (i, evidence) in feature.evidence.iter().enumerate() {
+        let path = format!("{path}.evidence[{i}]");
+        term(&path, "evidence_code", &evidence.evidence_code, v);
+        if let Some(reference) = &evidence.reference {
+            v.external_reference(&format!("{path}.reference"), reference);
+        }
+    }
+}
+
+fn procedure(path: &str, procedure: &Procedure, v: &mut dyn Visitor) {
+    term(path, "code", &procedure.code, v);
+    term(path, "body_site", &procedure.body_site, v);
+    time_element(path, "performed", &procedure.performed, v);
+}
+
+fn quantity(path: &str, quantity: &Quantity, v: &mut dyn Visitor) {
+    term(path, "unit", &quantity.unit, v);
+    if let Some(reference_range) = &quantity.reference_range {
+        term(
+            &format!("{path}.reference_range"),
+            "unit",
+            &reference_range.unit,
+            v,
+        );
+    }
+}
+
+fn measurement(path: &str, measurement: &Measurement, v: &mut dyn Visitor) {
+    term(path, "assay", &measurement.assay, v);
+    match &measurement.measurement_value {
+        Some(MeasurementValue::Value(value)) => match &value.value {
+            Some(value::Value::Quantity(q)) => quantity(&format!("{path}.value.quantity"), q, v),
+            Some(value::Value::OntologyClass(oc)) => {
+                v.ontology_class(&format!("{path}.value.ontology_class"), oc)
+            }
+            None => {}
+        },
+        Some(MeasurementValue::ComplexValue(cv)) => {
+            for (i, tq) in cv.typed_quantities.iter().enumerate() {
+                let path = format!("{path}.complex_value.typed_quantities[{i}]");
+                term(&path, "type", &tq.r#type, v);
+                if let Some(q) = &tq.quantity {
+                    quantity(&format!("{path}.quantity"), q, v);
+                }
+            }
+        }
+        None => {}
+    }
+    time_element(path, "time_observed", &measurement.time_observed, v);
+    if let Some(p) = &measurement.procedure {
+        procedure(&format!("{path}.procedure"), p, v);
+    }
+}
+
+fn biosample(path: &str, biosample: &Biosample, v: &mut dyn Visitor) {
+    term(path, "sampled_tissue", &biosample.sampled_tissue, v);
+    term(path, "sample_type", &biosample.sample_type, v);
+    for (i, feature) in biosample.phenotypic_features.iter().enumerate() {
+        phenotypic_feature(&format!("{path}.phenotypic_features[{i}]"), feature, v);
+    }
+    for (i, m) in biosample.measurements.iter().enumerate() {
+        measurement(&format!("{path}.measurements[{i}]"), m, v);
+    }
+    term(path, "taxonomy", &biosample.taxonomy, v);
+    time_element(path, "time_of_collection", &biosample.time_of_collection, v);
+    term(
+        path,
+        "histological_diagnosis",
+        &biosample.histological_diagnosis,
+        v,
+    );
+    term(path, "tumor_progression", &biosample.tumor_progression, v);
+    term(path, "tumor_grade", &biosample.tumor_grade, v);
+    term(path, "pathological_stage", &biosample.pathological_stage, v);
+    terms(
+        path,
+        "pathological_tnm_finding",
+        &biosample.pathological_tnm_finding,
+        v,
+    );
+    terms(path, "diagnostic_markers", &biosample.diagnostic_markers, v);
+    if let Some(p) = &biosample.procedure {
+        procedure(&format!("{path}.procedure"), p, v);
+    }
+    term(path, "material_sample", &biosample.material_sample, v);
+    term(path, "sample_processing", &biosample.sample_processing, v);
+    term(path, "sample_storage", &biosample.sample_storage, v);
+}
+
+fn variation_descriptor(path: &str, descriptor: &VariationDescriptor, v: &mut dyn Visitor) {
+    if let Some(gene_context) = &descriptor.gene_context {
+        v.gene_descriptor(&format!("{path}.gene_context"), gene_context);
+    }
+    term(path, "structural_type", &descriptor.structural_type, v);
+    term(path, "allelic_state", &descriptor.allelic_state, v);
+}
+
+fn interpretation(path: &str, interpretation: &Interpretation, v: &mut dyn Visitor) {
+    if let Some(diagnosis) = &interpretation.diagnosis {
+        let path = format!("{path}.diagnosis");
+        term(&path, "disease", &diagnosis.disease, v);
+        for (i, gi) in diagnosis.genomic_interpretations.iter().enumerate() {
+            match &gi.call {
+                Some(Call::Gene(gene)) => {
+                    v.gene_descriptor(&format!("{path}.genomic_interpretations[{i}].gene"), gene)
+                }
+                Some(Call::VariantInterpretation(vi)) => {
+                    if let Some(descriptor) = &vi.variation_descriptor {
+                        variation_descriptor(
+                            &format!(
+                                "{path}.genomic_interpretations[{i}].variant_interpretation.variation_descriptor"
+                            ),
+                            descriptor, v,
+                        );
+                    }
+                }
+                None => {}
+            }
+        }
+    }
+}
+
+fn disease(path: &str, disease: &Disease, v: &mut dyn Visitor) {
+    term(path, "term", &disease.term, v);
+    time_element(path, "onset", &disease.onset, v);
+    time_element(path, "resolution", &disease.resolution, v);
+    terms(path, "disease_stage", &disease.disease_stage, v);
+    terms(
+        path,
+        "clinical_tnm_finding",
+        &disease.clinical_tnm_finding,
+        v,
+    );
+    term(path, "primary_site", &disease.primary_site, v);
+    term(path, "laterality", &disease.laterality, v);
+}
+
+fn medical_action(path: &str, action: &MedicalAction, v: &mut dyn Visitor) {
+    match &action.action {
+        Some(Action::Procedure(p)) => procedure(&format!("{path}.procedure"), p, v),
+        Some(Action::Treatment(treatment)) => {
+            let path = format!("{path}.treatment");
+            term(&path, "agent", &treatment.agent, v);
+            term(
+                &path,
+                "route_of_administration",
+                &treatment.route_of_administration,
+                v,
+            );
+            for (i, dose_interval) in treatment.dose_intervals.iter().enumerate() {
+                let path = format!("{path}.dose_intervals[{i}]");
+                if let Some(q) = &dose_interval.quantity {
+                    quantity(&format!("{path}.quantity"), q, v);
+                }
+                if let Some(ti) = &dose_interval.interval {
+                    v.time_interval(&format!("{path}.interval"), ti);
+                }
+                term(
+                    &path,
+                    "schedule_frequency",
+                    &dose_interval.schedule_frequency,
+                    v,
+                );
+            }
+            if let Some(q) = &treatment.cumulative_dose {
+                quantity(&format!("{path}.cumulative_dose"), q, v);
+            }
+        }
+        Some(Action::RadiationTherapy(therapy)) => {
+            let path = format!("{path}.radiation_therapy");
+            term(&path, "modality", &therapy.modality, v);
+            term(&path, "body_site", &therapy.body_site, v);
+        }
+        Some(Action::TherapeuticRegimen(regimen)) => {
+            let path = format!("{path}.therapeutic_regimen");
+            match &regimen.identifier {
+                Some(Identifier::OntologyClass(oc)) => {
+                    v.ontology_class(&format!("{path}.ontology_class"), oc)
+                }
+                Some(Identifier::ExternalReference(er)) => {
+                    v.external_reference(&format!("{path}.external_reference"), er)
+                }
+                None => {}
+            }
+            time_element(&path, "start_time", &regimen.start_time, v);
+            time_element(&path, "end_time", &regimen.end_time, v);
+        }
+        None => {}
+    }
+    term(path, "treatment_target", &action.treatment_target, v);
+    term(path, "treatment_intent", &action.treatment_intent, v);
+    term(
+        path,
+        "response_to_treatment",
+        &action.response_to_treatment,
+        v,
+    );
+    terms(path, "adverse_events", &action.adverse_events, v);
+    term(
+        path,
+        "treatment_termination_reason",
+        &action.treatment_termination_reason,
+        v,
+    );
+}
@@ -0,0 +1,55 @@
+#[cfg(feature = "chrono")]
+mod date_time {
+    use chrono::{NaiveDate, TimeZone, Utc};
+    use phenopacket_builder::v2::IntoTimeElement;
+    use phenopacket_builder::{Build, Buildable};
+    use phenopackets::schema::v2::core::time_element::Element;
+    use prost_types::Timestamp;
+
+    #[test]
+    fn timestamp_from_date_time() {
+        let date_time = Utc.with_ymd_and_hms(2021, 11, 3, 10, 15, 0).unwrap()
+            + chrono::Duration::nanoseconds(500);
+
+        let ts: Timestamp = Timestamp::builder().date_time(date_time).build();
+
+        assert_eq!(ts.seconds, 1_635_934_500);
+        assert_eq!(ts.nanos, 500);
+    }
+
+    #[test]
+    fn timestamp_from_date_time_before_epoch() {
+        let ts: Timestamp = Timestamp::builder()
+            .date_time(Utc.with_ymd_and_hms(1969, 12, 31, 0, 0, 0).unwrap())
+            .build();
+
+        assert_eq!(ts.seconds, -86_400);
+        assert_eq!(ts.nanos, 0);
+    }
+
+    #[test]
+    fn timestamp_from_leap_second() {
+        let leap_second = NaiveDate::from_ymd_opt(2016, 12, 31)
+            .unwrap()
+            .and_hms_nano_opt(23, 59, 59, 1_500_000_000)
+            .unwrap()
+            .and_utc();
+
+        let ts: Timestamp = Timestamp::builder().date_time(leap_second).build();
+
+        assert_eq!(&ts.to_string(), "2017-01-01T00:00:00.500Z");
+    }
+
+    #[test]
+    fn date_time_into_time_element() {
+        let te = Utc
+            .with_ymd_and_hms(2021, 11, 3, 0, 0, 0)
+            .unwrap()
+            .into_time_element();
+
+        let Some(Element::Timestamp(ts)) = te.element else {
+            panic!("expected a timestamp");
+        };
+        assert_eq!(&ts.to_string(), "2021-11-03T00:00:00Z");
+    }
+}
@@ -0,0 +1,46 @@
+#![cfg(feature = "derive")]
+
+use phenopacket_builder::derived::AgeBuilder;
+use phenopacket_builder::{Build, BuildError, Buildable, TryBuild, Unset};
+use phenopackets::schema::v2::core::Age;
+
+#[derive(Debug, Clone, PartialEq, Buildable)]
+struct Sample {
+    #[builder(required)]
+    id: String,
+    tissue: String,
+    passages: u32,
+}
+
+#[test]
+fn derived_age_builder() {
+    let age: Age = AgeBuilder::default().iso8601duration("P10Y").build();
+
+    assert_eq!(
+        age,
+        Age {
+            iso8601duration: "P10Y".into()
+        }
+    );
+}
+
+#[test]
+fn derived_age_builder_reports_missing_field() {
+    let result: Result<Age, _> = AgeBuilder::<Unset>::default().try_build();
+
+    assert_eq!(result, Err(BuildError::MissingField("iso8601duration")));
+}
+
+#[test]
+fn derived_builder_fills_unset_optional_fields() {
+    let sample: Sample = Sample::builder().passages(3u32).id("S1").build();
+
+    assert_eq!(
+        sample,
+        Sample {
+            id: "S1".into(),
+            tissue: String::new(),
+            passages: 3,
+        }
+    );
+}
@@ -0,0 +1,195 @@
+use phenopacket_builder::edit::{auto_resources, prune_unused_resources, rename_subject};
+use phenopacket_builder::{oc, Build, Buildable};
+use phenopackets::ga4gh::vrsatile::v1::GeneDescriptor;
+use phenopackets::schema::v2::core::genomic_interpretation::Call;
+use phenopackets::schema::v2::core::{
+    Biosample, Diagnosis, Disease, ExternalReference, File, GenomicInterpretation, Individual,
+    Interpretation, MetaData, PhenotypicFeature, Resource,
+};
+use phenopackets::schema::v2::Phenopacket;
+use std::collections::HashMap;
+
+fn file(individual_id: &str, file_id: &str) -> File {
+    File {
+        uri: "file:///data/genome.vcf.gz".into(),
+        individual_to_file_identifiers: HashMap::from([(individual_id.into(), file_id.into())]),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn rename_subject_everywhere() {
+    let mut phenopacket = Phenopacket {
+        subject: Some(Individual {
+            id: "John Doe".into(),
+            ..Default::default()
+        }),
+        biosamples: vec![
+            Biosample {
+                id: "biosample-1".into(),
+                individual_id: "John Doe".into(),
+                files: vec![file("John Doe", "NA12345")],
+                ..Default::default()
+            },
+            Biosample {
+                id: "biosample-2".into(),
+                individual_id: "Jane Doe".into(),
+                ..Default::default()
+            },
+        ],
+        interpretations: vec![Interpretation {
+            id: "interpretation-1".into(),
+            diagnosis: Some(Diagnosis {
+                disease: None,
+                genomic_interpretations: vec![
+                    GenomicInterpretation {
+                        subject_or_biosample_id: "John Doe".into(),
+                        ..Default::default()
+                    },
+                    GenomicInterpretation {
+                        subject_or_biosample_id: "biosample-1".into(),
+                        ..Default::default()
+                    },
+                ],
+            }),
+            ..Default::default()
+        }],
+        files: vec![file("John Doe", "NA12345")],
+        ..Default::default()
+    };
+
+    rename_subject(&mut phenopacket, "John Doe", "individual-1");
+
+    assert_eq!(&phenopacket.subject.unwrap().id, "individual-1");
+    assert_eq!(&phenopacket.biosamples[0].individual_id, "individual-1");
+    assert_eq!(&phenopacket.biosamples[1].individual_id, "Jane Doe");
+    assert_eq!(
+        phenopacket.biosamples[0].files[0].individual_to_file_identifiers,
+        HashMap::from([("individual-1".to_string(), "NA12345".to_string())])
+    );
+    let genomic_interpretations = &phenopacket.interpretations[0]
+        .diagnosis
+        .as_ref()
+        .unwrap()
+        .genomic_interpretations;
+    assert_eq!(
+        &genomic_interpretations[0].subject_or_biosample_id,
+        "individual-1"
+    );
+    assert_eq!(
+        &genomic_interpretations[1].subject_or_biosample_id,
+        "biosample-1"
+    );
+    assert_eq!(
+        phenopacket.files[0].individual_to_file_identifiers,
+        HashMap::from([("individual-1".to_string(), "NA12345".to_string())])
+    );
+}
+
+#[test]
+fn prune_the_unused_hpo_resource() {
+    let mut phenopacket = Phenopacket {
+        diseases: vec![Disease {
+            term: Some(oc("MONDO:0007947", "Marfan syndrome")),
+            ..Default::default()
+        }],
+        interpretations: vec![Interpretation {
+            diagnosis: Some(Diagnosis {
+                genomic_interpretations: vec![GenomicInterpretation {
+                    call: Some(Call::Gene(
+                        GeneDescriptor::builder().hgnc("3603", "FBN1").build(),
+                    )),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }),
+            ..Default::default()
+        }],
+        meta_data: Some(MetaData {
+            resources: vec![
+                Resource::builder().hpo("2024-04-26").build(),
+                Resource::builder().mondo("2024-06-04").build(),
+                Resource::builder().hgnc("2024-08-23").build(),
+            ],
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+
+    prune_unused_resources(&mut phenopacket);
+
+    let prefixes: Vec<_> = phenopacket
+        .meta_data
+        .unwrap()
+        .resources
+        .into_iter()
+        .map(|resource| resource.namespace_prefix)
+        .collect();
+    assert_eq!(prefixes, vec!["MONDO", "HGNC"]);
+}
+
+#[test]
+fn auto_resources_for_hpo_and_mondo() {
+    let phenopacket = Phenopacket {
+        phenotypic_features: vec![PhenotypicFeature {
+            r#type: Some(oc("HP:0001166", "Arachnodactyly")),
+            ..Default::default()
+        }],
+        diseases: vec![Disease {
+            term: Some(oc("MONDO:0007947", "Marfan syndrome")),
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+
+    let found = auto_resources(
+        &phenopacket,
+        &HashMap::from([("HP", "2024-04-26"), ("MONDO", "2024-06-04")]),
+    );
+
+    assert_eq!(
+        found.resources,
+        vec![
+            Resource::builder().hpo("2024-04-26").build(),
+            Resource::builder().mondo("2024-06-04").build(),
+        ]
+    );
+    assert!(found.unknown_prefixes.is_empty());
+}
+
+#[test]
+fn auto_resources_reports_unknown_prefixes() {
+    let phenopacket = Phenopacket {
+        diseases: vec![Disease {
+            term: Some(oc("DOID:0060041", "autism spectrum disorder")),
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+
+    let found = auto_resources(&phenopacket, &HashMap::new());
+
+    assert!(found.resources.is_empty());
+    assert_eq!(found.unknown_prefixes, vec!["DOID"]);
+}
+
+#[test]
+fn auto_resources_skips_external_reference_ids() {
+    let phenopacket = Phenopacket {
+        meta_data: Some(MetaData {
+            external_references: vec![
+                ExternalReference::builder().id("PMID:30808312").build(),
+                ExternalReference::builder()
+                    .id("https://example.org/cohort")
+                    .build(),
+            ],
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+
+    let found = auto_resources(&phenopacket, &HashMap::new());
+
+    assert!(found.resources.is_empty());
+    assert!(found.unknown_prefixes.is_empty());
+}
@@ -0,0 +1,88 @@
+use phenopacket_builder::ingest::{measurements_from_rows, MeasurementRow, MeasurementRowError};
+use phenopackets::schema::v2::core::measurement::MeasurementValue;
+use phenopackets::schema::v2::core::time_element::Element;
+use phenopackets::schema::v2::core::value::Value;
+
+fn glucose(time_iso8601: Option<&str>) -> MeasurementRow {
+    MeasurementRow {
+        assay_id: "LOINC:2345-7".into(),
+        assay_label: "Glucose [Mass/volume] in Serum or Plasma".into(),
+        value: 110.,
+        unit_id: "UCUM:mg/dL".into(),
+        unit_label: "mg/dL".into(),
+        time_iso8601: time_iso8601.map(String::from),
+    }
+}
+
+#[test]
+fn timed_and_untimed_rows() {
+    let measurements =
+        measurements_from_rows([glucose(Some("2021-11-03T10:00:00Z")), glucose(None)])
+            .expect("the rows should be well formatted");
+
+    assert_eq!(measurements.len(), 2);
+    for measurement in &measurements {
+        assert_eq!(&measurement.assay.as_ref().unwrap().id, "LOINC:2345-7");
+        let Some(MeasurementValue::Value(value)) = &measurement.measurement_value else {
+            panic!("expected a value");
+        };
+        let Some(Value::Quantity(quantity)) = &value.value else {
+            panic!("expected a quantity");
+        };
+        assert_eq!(quantity.value, 110.);
+        assert_eq!(&quantity.unit.as_ref().unwrap().id, "UCUM:mg/dL");
+    }
+
+    let Some(Element::Timestamp(timestamp)) = measurements[0]
+        .time_observed
+        .as_ref()
+        .and_then(|te| te.element.as_ref())
+    else {
+        panic!("expected a timestamp");
+    };
+    assert_eq!(timestamp.seconds, 1_635_933_600);
+    assert!(measurements[1].time_observed.is_none());
+}
+
+#[test]
+fn row_with_age() {
+    let measurements =
+        measurements_from_rows([glucose(Some("P3Y4M"))]).expect("the row should be well formatted");
+
+    let Some(Element::Age(age)) = measurements[0]
+        .time_observed
+        .as_ref()
+        .and_then(|te| te.element.as_ref())
+    else {
+        panic!("expected an age");
+    };
+    assert_eq!(&age.iso8601duration, "P3Y4M");
+}
+
+#[test]
+fn row_with_malformed_time() {
+    let result = measurements_from_rows([glucose(None), glucose(Some("yesterday"))]);
+
+    assert_eq!(
+        result,
+        Err(MeasurementRowError {
+            row: 1,
+            time_iso8601: "yesterday".into(),
+        })
+    );
+}
+
+#[test]
+fn row_with_malformed_duration() {
+    for time in ["Pfoo", "P-1Y"] {
+        let result = measurements_from_rows([glucose(Some(time))]);
+
+        assert_eq!(
+            result,
+            Err(MeasurementRowError {
+                row: 0,
+                time_iso8601: time.into(),
+            })
+        );
+    }
+}
@@ -0,0 +1,325 @@
+use phenopacket_builder::{oc, Build, Buildable};
+use phenopackets::schema::v2::core::{
+    Individual, MetaData, PhenotypicFeature, Resource, TimeElement,
+};
+use phenopackets::schema::v2::Phenopacket;
+use prost_types::Timestamp;
+
+/// The phenopacket stored in `data/proband.json`.
+fn proband() -> Phenopacket {
+    Phenopacket::builder()
+        .id("proband")
+        .subject(
+            Individual::builder()
+                .id("II-1")
+                .date_of_birth(Timestamp::builder().iso8601timestamp("2018-03-01").unwrap())
+                .female()
+                .karyotypic_sex_xx(),
+        )
+        .add_phenotypic_feature(
+            PhenotypicFeature::builder()
+                .r#type(oc("HP:0001250", "Seizure"))
+                .onset(TimeElement::builder().age_iso8601duration("P6M")),
+        )
+        .extend_excluded_features([("HP:0001627", "Abnormal heart morphology")])
+        .meta_data(
+            MetaData::builder()
+                .created(
+                    Timestamp::builder()
+                        .iso8601timestamp("2021-11-03T10:15:00Z")
+                        .unwrap(),
+                )
+                .created_by("Peter R.")
+                .add_resource(Resource::builder().hpo("2018-03-08"))
+                .v2(),
+        )
+        .build()
+}
+
+mod pb {
+    use super::proband;
+    use phenopacket_builder::io::{
+        from_bytes, read_pb, to_bytes, to_deterministic_bytes, write_pb, ParseError,
+    };
+    use phenopacket_builder::{Build, Buildable};
+    use phenopackets::schema::v2::core::{Biosample, File};
+    use phenopackets::schema::v2::Phenopacket;
+
+    #[test]
+    fn write_and_read_back() {
+        let mut buffer = Vec::new();
+        write_pb(&proband(), &mut buffer).expect("writing into a vector should not fail");
+
+        let phenopacket = read_pb(buffer.as_slice()).expect("the bytes should be valid");
+
+        assert_eq!(phenopacket, proband());
+    }
+
+    #[test]
+    fn encode_and_decode_bytes() {
+        let bytes = to_bytes(&proband());
+
+        assert_eq!(from_bytes(&bytes).unwrap(), proband());
+    }
+
+    #[test]
+    fn decode_truncated_bytes() {
+        let bytes = to_bytes(&proband());
+
+        assert!(matches!(
+            from_bytes(&bytes[..bytes.len() - 1]),
+            Err(ParseError::Protobuf(_))
+        ));
+    }
+
+    #[test]
+    fn deterministic_bytes_match_the_prost_encoding() {
+        assert_eq!(to_deterministic_bytes(&proband()), to_bytes(&proband()));
+    }
+
+    #[test]
+    fn deterministic_bytes_sort_the_file_maps() {
+        let file = |keys: &[&str]| -> File {
+            let mut file = File::builder().uri("file:///data/genomes/proband.vcf.gz");
+            for key in keys {
+                file = file
+                    .add_file_attribute(*key, "value")
+                    .add_individual_file_identifier(*key, "value");
+            }
+            file.build()
+        };
+        let phenopacket = |keys: &[&str]| Phenopacket {
+            biosamples: vec![Biosample {
+                id: "sample-1".into(),
+                files: vec![file(keys)],
+                ..Default::default()
+            }],
+            files: vec![file(keys)],
+            ..proband()
+        };
+        let keys = ["a", "b", "c", "d", "e", "f", "g", "h"];
+        let reversed: Vec<_> = keys.iter().rev().copied().collect();
+
+        let bytes = to_deterministic_bytes(&phenopacket(&keys));
+
+        assert_eq!(bytes, to_deterministic_bytes(&phenopacket(&reversed)));
+        assert_eq!(from_bytes(&bytes).unwrap(), phenopacket(&keys));
+    }
+}
+
+#[cfg(feature = "json")]
+mod json {
+    use super::proband;
+    use phenopacket_builder::io::{
+        from_json, read_phenopackets_json_array, to_json, to_json_pretty, ParseError,
+    };
+    use phenopacket_builder::{oc, Build, Buildable};
+    use phenopackets::schema::v2::core::interpretation::ProgressStatus;
+    use phenopackets::schema::v2::core::vital_status::Status;
+    use phenopackets::schema::v2::core::{KaryotypicSex, MetaData, PhenotypicFeature, Sex};
+    use phenopackets::schema::v2::Phenopacket;
+    use prost_types::Timestamp;
+
+    #[test]
+    fn read_a_json_array() {
+        let value = r#"[
+            {
+                "id": "proband",
+                "subject": {"id": "II-1", "sex": "FEMALE"},
+                "phenotypicFeatures": [{"type": {"id": "HP:0001250", "label": "Seizure"}}],
+                "metaData": {"createdBy": "Peter R.", "phenopacketSchemaVersion": "2.0"}
+            },
+            {
+                "id": "father",
+                "subject": {"id": "I-1", "sex": "MALE"},
+                "metaData": {"createdBy": "Peter R.", "phenopacketSchemaVersion": "2.0"}
+            }
+        ]"#;
+
+        let phenopackets = read_phenopackets_json_array(value).expect("well formatted array");
+
+        assert_eq!(phenopackets.len(), 2);
+        assert_eq!(&phenopackets[0].id, "proband");
+        assert_eq!(phenopackets[0].phenotypic_features.len(), 1);
+        assert_eq!(
+            phenopackets[1].subject.as_ref().map(|s| s.sex),
+            Some(Sex::Male.into())
+        );
+    }
+
+    #[test]
+    fn read_a_json_object_as_array() {
+        assert!(read_phenopackets_json_array(r#"{"id": "proband"}"#).is_err());
+    }
+
+    #[test]
+    fn read_a_json_array_with_integer_enums() {
+        let value = r#"[{"id": "a"}, {"id": "b", "subject": {"id": "II-1", "sex": 1}}]"#;
+
+        let phenopackets = read_phenopackets_json_array(value).expect("well formatted array");
+
+        assert_eq!(
+            phenopackets[1].subject.as_ref().map(|s| s.sex()),
+            Some(Sex::Female)
+        );
+    }
+
+    #[test]
+    fn read_a_json_array_with_unknown_enum() {
+        let value = r#"[{"id": "a"}, {"id": "b", "subject": {"id": "II-1", "sex": "ROBOT"}}]"#;
+
+        let error = read_phenopackets_json_array(value).expect_err("the sex is unknown");
+
+        assert!(
+            matches!(error, ParseError::InvalidField { ref path, .. } if path == "[1].subject.sex"),
+            "{error:?}"
+        );
+    }
+
+    #[test]
+    fn build_to_json_bytes_and_read_back() {
+        let builder = Phenopacket::builder()
+            .id("proband")
+            .add_phenotypic_feature(
+                PhenotypicFeature::builder().r#type(oc("HP:0001250", "Seizure")),
+            )
+            .meta_data(
+                MetaData::builder()
+                    .created(Timestamp::builder().seconds_nanos(0, 0))
+                    .created_by("Peter R.")
+                    .v2(),
+            );
+        let expected: Phenopacket = builder.clone().build();
+
+        let bytes = builder
+            .build_to_json_bytes()
+            .expect("writing into bytes should not fail");
+        let phenopacket: Phenopacket =
+            serde_json::from_slice(&bytes).expect("the bytes should be valid JSON");
+
+        assert_eq!(phenopacket, expected);
+    }
+
+    const PROBAND: &str = include_str!("data/proband.json");
+
+    #[test]
+    fn write_the_ga4gh_json_format() {
+        let json = to_json_pretty(&proband()).expect("serialization should not fail");
+
+        let actual: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let expected: serde_json::Value = serde_json::from_str(PROBAND).unwrap();
+        assert_eq!(actual, expected);
+
+        assert!(json.contains(r#""sex": "FEMALE""#));
+        assert!(json.contains(r#""created": "2021-11-03T10:15:00Z""#));
+    }
+
+    #[test]
+    fn round_trip_through_json() {
+        let json = to_json(&proband()).expect("serialization should not fail");
+        let phenopacket: Phenopacket =
+            serde_json::from_str(&json).expect("the JSON should be valid");
+
+        assert_eq!(phenopacket, proband());
+        assert_eq!(
+            serde_json::from_str::<Phenopacket>(PROBAND).unwrap(),
+            proband()
+        );
+    }
+
+    #[test]
+    fn read_a_minimal_phenopacket() {
+        let phenopacket =
+            from_json(r#"{"id": "proband", "subject": {"id": "II-1", "sex": "MALE"}}"#)
+                .expect("well formatted JSON");
+
+        assert_eq!(&phenopacket.id, "proband");
+        assert_eq!(phenopacket.subject.unwrap().sex(), Sex::Male);
+
+        assert_eq!(from_json(PROBAND).unwrap(), proband());
+    }
+
+    #[test]
+    fn read_integer_enum_values() {
+        let value = r#"{
+            "id": "proband",
+            "subject": {"id": "II-1", "sex": 2, "karyotypicSex": 2, "vitalStatus": {"status": 2, "survivalTimeInDays": 0}},
+            "interpretations": [{"id": "interpretation-id", "progressStatus": 3}]
+        }"#;
+
+        let phenopacket = from_json(value).expect("integer enum values should be accepted");
+
+        let subject = phenopacket.subject.unwrap();
+        assert_eq!(subject.sex(), Sex::Male);
+        assert_eq!(subject.karyotypic_sex(), KaryotypicSex::Xy);
+        assert_eq!(subject.vital_status.unwrap().status(), Status::Deceased);
+        assert_eq!(
+            phenopacket.interpretations[0].progress_status(),
+            ProgressStatus::Solved
+        );
+    }
+
+    #[test]
+    fn read_an_unknown_enum_value() {
+        for sex in [r#""ROBOT""#, "9"] {
+            let value =
+                format!(r#"{{"id": "proband", "subject": {{"id": "II-1", "sex": {sex}}}}}"#);
+
+            let error = from_json(&value).expect_err("the unknown sex should be reported");
+
+            let ParseError::InvalidField { path, .. } = &error else {
+                panic!("expected an invalid field, got {error}");
+            };
+            assert_eq!(path, "subject.sex");
+        }
+    }
+
+    #[test]
+    fn read_malformed_json() {
+        assert!(matches!(
+            from_json(r#"{"id": "proband""#),
+            Err(ParseError::Json(_))
+        ));
+    }
+}
+
+#[cfg(feature = "yaml")]
+mod yaml {
+    use super::proband;
+    use phenopacket_builder::io::{from_yaml, to_yaml, ParseError};
+    use phenopackets::schema::v2::core::Sex;
+
+    #[test]
+    fn round_trip_through_yaml() {
+        let yaml = to_yaml(&proband()).expect("serialization should not fail");
+
+        assert!(yaml.contains("sex: FEMALE"));
+        assert!(yaml.contains("phenotypicFeatures:"));
+        assert_eq!(from_yaml(&yaml).unwrap(), proband());
+    }
+
+    #[test]
+    fn read_yaml() {
+        let yaml = r#"
+id: proband
+subject:
+  id: II-1
+  sex: MALE
+metaData:
+  created: "2021-11-03T00:00:00Z"
+  createdBy: Peter R.
+"#;
+
+        let phenopacket = from_yaml(yaml).expect("well formatted YAML");
+
+        assert_eq!(phenopacket.subject.unwrap().sex(), Sex::Male);
+        assert_eq!(
+            &phenopacket.meta_data.unwrap().created.unwrap().to_string(),
+            "2021-11-03T00:00:00Z"
+        );
+        assert!(matches!(
+            from_yaml("id: [proband"),
+            Err(ParseError::Yaml(_))
+        ));
+    }
+}
@@ -0,0 +1,164 @@
+use phenopacket_builder::oc;
+use phenopacket_builder::report::{measurement_units, measurements_by_assay, summary_markdown};
+use phenopackets::schema::v2::core::measurement::MeasurementValue;
+use phenopackets::schema::v2::core::{
+    time_element, value, ComplexValue, Disease, Individual, Measurement, PhenotypicFeature,
+    Quantity, Sex, TimeElement, TypedQuantity, Value,
+};
+use phenopackets::schema::v2::Phenopacket;
+use prost_types::Timestamp;
+
+fn quantity(unit_id: &str, unit_label: &str, value: f64) -> Quantity {
+    Quantity {
+        unit: Some(oc(unit_id, unit_label)),
+        value,
+        reference_range: None,
+    }
+}
+
+#[test]
+fn measurement_units_of_quantitative_and_complex_measurements() {
+    let phenopacket = Phenopacket {
+        measurements: vec![
+            Measurement {
+                assay: Some(oc(
+                    "LOINC:2345-7",
+                    "Glucose [Mass/volume] in Serum or Plasma",
+                )),
+                measurement_value: Some(MeasurementValue::Value(Value {
+                    value: Some(value::Value::Quantity(quantity(
+                        "UCUM:mg/dL",
+                        "mg/dL",
+                        110.,
+                    ))),
+                })),
+                ..Default::default()
+            },
+            Measurement {
+                assay: Some(oc("CMO:0000003", "blood pressure")),
+                measurement_value: Some(MeasurementValue::ComplexValue(ComplexValue {
+                    typed_quantities: vec![
+                        TypedQuantity {
+                            r#type: Some(oc("NCIT:C25298", "Systolic Blood Pressure")),
+                            quantity: Some(quantity("UCUM:mm[Hg]", "mmHg", 125.)),
+                        },
+                        TypedQuantity {
+                            r#type: Some(oc("NCIT:C25299", "Diastolic Blood Pressure")),
+                            quantity: Some(quantity("UCUM:mm[Hg]", "mmHg", 75.)),
+                        },
+                    ],
+                })),
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    };
+
+    assert_eq!(
+        measurement_units(&phenopacket),
+        vec![
+            ("LOINC:2345-7".to_string(), Some("UCUM:mg/dL".to_string())),
+            ("CMO:0000003".to_string(), None),
+        ]
+    );
+}
+
+fn glucose(value: f64, seconds: i64) -> Measurement {
+    Measurement {
+        assay: Some(oc(
+            "LOINC:2345-7",
+            "Glucose [Mass/volume] in Serum or Plasma",
+        )),
+        measurement_value: Some(MeasurementValue::Value(Value {
+            value: Some(value::Value::Quantity(quantity(
+                "UCUM:mg/dL",
+                "mg/dL",
+                value,
+            ))),
+        })),
+        time_observed: Some(TimeElement {
+            element: Some(time_element::Element::Timestamp(Timestamp {
+                seconds,
+                nanos: 0,
+            })),
+        }),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn measurements_by_assay_groups_glucose_measurements() {
+    let height = Measurement {
+        assay: Some(oc("LOINC:8302-2", "Body height")),
+        measurement_value: Some(MeasurementValue::Value(Value {
+            value: Some(value::Value::Quantity(quantity("UCUM:cm", "cm", 172.))),
+        })),
+        ..Default::default()
+    };
+    let phenopacket = Phenopacket {
+        measurements: vec![
+            glucose(110., 1_600_000_000),
+            height.clone(),
+            glucose(95., 1_610_000_000),
+            glucose(102., 1_620_000_000),
+        ],
+        ..Default::default()
+    };
+
+    let groups = measurements_by_assay(&phenopacket);
+
+    assert_eq!(groups.len(), 2);
+    assert_eq!(
+        groups["LOINC:2345-7"],
+        vec![
+            &glucose(110., 1_600_000_000),
+            &glucose(95., 1_610_000_000),
+            &glucose(102., 1_620_000_000),
+        ]
+    );
+    assert_eq!(groups["LOINC:8302-2"], vec![&height]);
+}
+
+#[test]
+fn summary_markdown_lists_the_features_diseases_and_measurements() {
+    let phenopacket = Phenopacket {
+        id: "pp-id".into(),
+        subject: Some(Individual {
+            id: "subject-id".into(),
+            sex: Sex::Female.into(),
+            ..Default::default()
+        }),
+        phenotypic_features: vec![
+            PhenotypicFeature {
+                r#type: Some(oc("HP:0001250", "Seizure")),
+                ..Default::default()
+            },
+            PhenotypicFeature {
+                r#type: Some(oc("HP:0001263", "Global developmental delay")),
+                excluded: true,
+                ..Default::default()
+            },
+        ],
+        diseases: vec![Disease {
+            term: Some(oc("MONDO:0100038", "complex neurodevelopmental disorder")),
+            ..Default::default()
+        }],
+        measurements: vec![glucose(110., 1_600_000_000)],
+        ..Default::default()
+    };
+
+    let markdown = summary_markdown(&phenopacket);
+
+    assert!(markdown.starts_with("# Phenopacket pp-id\n"));
+    assert!(markdown.contains("## Subject\n\n- ID: subject-id\n- Sex: FEMALE\n"));
+    assert!(markdown.contains("## Phenotypic Features\n"));
+    assert!(markdown.contains("- Seizure (HP:0001250)\n"));
+    assert!(markdown.contains("- Global developmental delay (HP:0001263), excluded\n"));
+    assert!(
+        markdown.contains("## Diseases\n\n- complex neurodevelopmental disorder (MONDO:0100038)\n")
+    );
+    assert!(markdown.contains(
+        "| Glucose [Mass/volume] in Serum or Plasma (LOINC:2345-7) | 110 mg/dL | 2020-09-13T12:26:40Z |"
+    ));
+    assert_eq!(markdown, summary_markdown(&phenopacket));
+}
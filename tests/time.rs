@@ -0,0 +1,199 @@
+use phenopacket_builder::time::{compare_time_elements_with_dob, time_element_to_human};
+use phenopacket_builder::v2::{DateError, TimestampParseError};
+use phenopacket_builder::{oc, Build, Buildable};
+use phenopackets::schema::v2::core::time_element::Element;
+use phenopackets::schema::v2::core::{Age, AgeRange, TimeElement};
+use prost_types::Timestamp;
+use std::cmp::Ordering;
+use std::error::Error;
+use std::time::{Duration, UNIX_EPOCH};
+
+fn timestamp(value: &str) -> Timestamp {
+    Timestamp::builder()
+        .iso8601timestamp(value)
+        .expect("the timestamp should be well formatted")
+        .build()
+}
+
+fn at(value: &str) -> TimeElement {
+    TimeElement::builder().timestamp(timestamp(value)).build()
+}
+
+fn age(iso8601duration: &str) -> TimeElement {
+    TimeElement::builder()
+        .age_iso8601duration(iso8601duration)
+        .build()
+}
+
+#[test]
+fn compare_age_and_timestamp() {
+    let dob = timestamp("2018-03-01");
+
+    assert_eq!(
+        compare_time_elements_with_dob(&age("P6M"), &at("2018-09-01"), &dob),
+        Some(Ordering::Equal)
+    );
+    assert_eq!(
+        compare_time_elements_with_dob(&age("P1Y2M"), &at("2019-05-02"), &dob),
+        Some(Ordering::Less)
+    );
+    assert_eq!(
+        compare_time_elements_with_dob(&at("2018-03-02"), &age("PT12H"), &dob),
+        Some(Ordering::Greater)
+    );
+}
+
+#[test]
+fn compare_ages_clamps_to_the_end_of_the_month() {
+    let dob = timestamp("2020-01-31");
+
+    assert_eq!(
+        compare_time_elements_with_dob(&age("P1M"), &at("2020-02-29"), &dob),
+        Some(Ordering::Equal)
+    );
+}
+
+#[test]
+fn compare_elements_that_cannot_be_placed_on_a_timeline() {
+    let dob = timestamp("2018-03-01");
+    let gestational_age = TimeElement::builder().gestational_age_weeks(25).build();
+
+    assert_eq!(
+        compare_time_elements_with_dob(&gestational_age, &age("P1Y"), &dob),
+        None
+    );
+    assert_eq!(
+        compare_time_elements_with_dob(&age("1 year"), &age("P1Y"), &dob),
+        None
+    );
+}
+
+#[test]
+fn age_to_human() {
+    assert_eq!(time_element_to_human(&age("P3Y4M")), "3y 4m");
+    assert_eq!(time_element_to_human(&age("P2W")), "2w");
+    assert_eq!(time_element_to_human(&age("P1DT12H30M")), "1d 12h 30min");
+    assert_eq!(time_element_to_human(&age("P0D")), "0d");
+}
+
+#[test]
+fn age_range_to_human() {
+    let age_range = TimeElement {
+        element: Some(Element::AgeRange(AgeRange {
+            start: Some(Age::builder().iso8601duration("P1Y").build()),
+            end: Some(Age::builder().iso8601duration("P2Y6M").build()),
+        })),
+    };
+
+    assert_eq!(time_element_to_human(&age_range), "1y - 2y 6m");
+}
+
+#[test]
+fn gestational_age_to_human() {
+    let gestational_age: TimeElement = TimeElement::builder()
+        .gestational_age_weeks_days(25, 5)
+        .build();
+
+    assert_eq!(time_element_to_human(&gestational_age), "25w 5d");
+}
+
+#[test]
+fn timestamp_to_human() {
+    assert_eq!(
+        time_element_to_human(&at("2019-07-21")),
+        "2019-07-21T00:00:00Z"
+    );
+}
+
+#[test]
+fn ontology_class_to_human() {
+    let onset: TimeElement = TimeElement::builder()
+        .ontology_class(oc("HP:0003577", "Congenital onset"))
+        .build();
+
+    assert_eq!(time_element_to_human(&onset), "Congenital onset");
+}
+
+#[test]
+fn timestamp_from_system_time_after_epoch() {
+    let ts: Timestamp = Timestamp::builder()
+        .system_time(UNIX_EPOCH + Duration::new(1_635_897_600, 500))
+        .build();
+
+    assert_eq!(ts, timestamp("2021-11-03T00:00:00.000000500Z"));
+}
+
+#[test]
+fn timestamp_from_system_time_before_epoch() {
+    let ts: Timestamp = Timestamp::builder()
+        .system_time(UNIX_EPOCH - Duration::new(1, 250_000_000))
+        .build();
+
+    assert_eq!(ts.seconds, -2);
+    assert_eq!(ts.nanos, 750_000_000);
+    assert_eq!(ts, timestamp("1969-12-31T23:59:58.750Z"));
+
+    let ts: Timestamp = Timestamp::builder()
+        .system_time(UNIX_EPOCH - Duration::from_secs(86_400))
+        .build();
+
+    assert_eq!(ts, timestamp("1969-12-31"));
+}
+
+#[test]
+fn timestamp_from_ymd() {
+    let ts: Timestamp = Timestamp::builder()
+        .ymd(2021, 11, 3)
+        .expect("a valid date")
+        .build();
+
+    assert_eq!(ts, timestamp("2021-11-03"));
+
+    let ts: Timestamp = Timestamp::builder()
+        .ymd(1969, 12, 31)
+        .expect("a valid date")
+        .build();
+
+    assert_eq!(ts.seconds, -86_400);
+}
+
+#[test]
+fn timestamp_from_ymd_with_invalid_month() {
+    let error = Timestamp::builder()
+        .ymd(2021, 13, 1)
+        .expect_err("month 13 should be rejected");
+
+    assert_eq!(error, DateError::InvalidMonth { month: 13 });
+    assert_eq!(&error.to_string(), "month 13 is not in 1..=12");
+}
+
+#[test]
+fn timestamp_from_ymd_on_leap_day() {
+    let ts: Timestamp = Timestamp::builder()
+        .ymd(2024, 2, 29)
+        .expect("2024 is a leap year")
+        .build();
+
+    assert_eq!(ts, timestamp("2024-02-29"));
+
+    assert_eq!(
+        Timestamp::builder().ymd(2023, 2, 29),
+        Err(DateError::InvalidDay {
+            year: 2023,
+            month: 2,
+            day: 29
+        })
+    );
+    assert!(Timestamp::builder().ymd(2021, 2, 30).is_err());
+    assert!(Timestamp::builder().ymd(2021, 2, 0).is_err());
+}
+
+#[test]
+fn malformed_iso8601_timestamp() {
+    let error = Timestamp::builder()
+        .iso8601timestamp("3 Nov 2021")
+        .expect_err("the timestamp should be rejected");
+
+    assert!(matches!(error, TimestampParseError::Malformed(_)));
+    assert!(error.source().is_some());
+}
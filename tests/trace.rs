@@ -0,0 +1,89 @@
+#[cfg(feature = "tracing")]
+mod events {
+    use phenopacket_builder::{Build, Buildable};
+    use phenopackets::schema::v2::core::VitalStatus;
+    use std::fmt::Debug;
+    use std::sync::{Arc, Mutex};
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Metadata, Subscriber};
+
+    /// Collects the `builder` and `field` values of the events.
+    #[derive(Default, Clone)]
+    struct Collector(Arc<Mutex<Vec<(String, String)>>>);
+
+    #[derive(Default)]
+    struct Fields {
+        builder: String,
+        field: String,
+    }
+
+    impl Visit for Fields {
+        fn record_str(&mut self, field: &Field, value: &str) {
+            match field.name() {
+                "builder" => self.builder = value.to_string(),
+                "field" => self.field = value.to_string(),
+                _ => {}
+            }
+        }
+
+        fn record_debug(&mut self, _field: &Field, _value: &dyn Debug) {}
+    }
+
+    impl Subscriber for Collector {
+        fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, _span: &Attributes<'_>) -> Id {
+            Id::from_u64(1)
+        }
+
+        fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+        fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+        fn event(&self, event: &Event<'_>) {
+            let mut fields = Fields::default();
+            event.record(&mut fields);
+            self.0.lock().unwrap().push((fields.builder, fields.field));
+        }
+
+        fn enter(&self, _span: &Id) {}
+
+        fn exit(&self, _span: &Id) {}
+    }
+
+    #[test]
+    fn event_fires_when_survival_time_defaults() {
+        let collector = Collector::default();
+
+        let vital_status: VitalStatus =
+            tracing::subscriber::with_default(collector.clone(), || {
+                VitalStatus::builder().deceased().build()
+            });
+
+        assert_eq!(vital_status.survival_time_in_days, 0);
+        assert_eq!(
+            *collector.0.lock().unwrap(),
+            vec![(
+                "VitalStatusBuilder".to_string(),
+                "survival_time_in_days".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn no_event_fires_when_survival_time_is_set() {
+        let collector = Collector::default();
+
+        tracing::subscriber::with_default(collector.clone(), || {
+            let _: VitalStatus = VitalStatus::builder()
+                .deceased()
+                .survival_time_in_days(212u32)
+                .build();
+        });
+
+        assert!(collector.0.lock().unwrap().is_empty());
+    }
+}
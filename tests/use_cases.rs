@@ -1,14 +1,39 @@
 /// Examples with Phenopacket Schema v2.
 mod v2 {
-    use phenopacket_builder::{oc, Build, Buildable};
+    use phenopacket_builder::io::to_deterministic_bytes;
+    use phenopacket_builder::v2::{
+        AgeError, CurieError, DiseaseBuilder, GestationalAgeError, IndividualBuilder,
+        InterpretationBuilder, MetaDataBuilder, OntologyClassBuilder, PhenopacketBuilder,
+        PhenopacketTemplate, PhenotypicFeatureBuilder, ReferenceRangeError, ResourceBuilder,
+        VitalStatusBuilder,
+    };
+    use phenopacket_builder::{
+        excluded_feature, observed_feature, oc, Build, BuildError, Buildable, DynBuildable, Set,
+        TryBuild,
+    };
+    use phenopackets::ga4gh::vrsatile::v1::GeneDescriptor;
+    use phenopackets::schema::v2::core::genomic_interpretation::{Call, InterpretationStatus};
+    use phenopackets::schema::v2::core::interpretation::ProgressStatus;
+    use phenopackets::schema::v2::core::measurement::MeasurementValue;
+    use phenopackets::schema::v2::core::medical_action::Action;
+    use phenopackets::schema::v2::core::pedigree::person::AffectedStatus;
+    use phenopackets::schema::v2::core::pedigree::Person;
+    use phenopackets::schema::v2::core::therapeutic_regimen::{Identifier, RegimenStatus};
     use phenopackets::schema::v2::core::time_element::Element;
     use phenopackets::schema::v2::core::vital_status::Status;
     use phenopackets::schema::v2::core::{
-        Age, ExternalReference, Individual, KaryotypicSex, MetaData, OntologyClass,
-        PhenotypicFeature, Resource, Sex, TimeElement, VitalStatus,
+        value, AcmgPathogenicityClassification, Age, Biosample, ComplexValue, Diagnosis, Disease,
+        Evidence, ExternalReference, File, GenomicInterpretation, GestationalAge, Individual,
+        Interpretation, KaryotypicSex, Measurement, MedicalAction, MetaData, OntologyClass,
+        Pedigree, PhenotypicFeature, Procedure, Quantity, ReferenceRange, Resource, Sex,
+        TherapeuticRegimen, TimeElement, TimeInterval, Treatment, TypedQuantity, Update, Value,
+        VariantInterpretation, VitalStatus,
     };
-    use phenopackets::schema::v2::Phenopacket;
+    use phenopackets::schema::v2::{Family, Phenopacket};
+    use prost::Message;
     use prost_types::Timestamp;
+    use std::collections::HashMap;
+    use std::time::{SystemTime, UNIX_EPOCH};
 
     #[test]
     fn build_a_phenopacket() {
@@ -66,6 +91,282 @@ mod v2 {
             .build();
     }
 
+    #[test]
+    fn build_a_strict_phenopacket() {
+        let phenopacket: Phenopacket = Phenopacket::builder()
+            .require_subject()
+            .id("phenopacket-id")
+            .subject(Individual::builder().id("individual-id").female())
+            .add_phenotypic_feature(
+                PhenotypicFeature::builder().r#type(oc("HP:0001250", "Seizure")),
+            )
+            .meta_data(
+                MetaData::builder()
+                    .created(Timestamp::builder().seconds_nanos(1_000_000, 0))
+                    .created_by("Peter R.")
+                    .v2(),
+            )
+            .build();
+
+        assert_eq!(&phenopacket.id, "phenopacket-id");
+        assert_eq!(&phenopacket.subject.unwrap().id, "individual-id");
+        assert_eq!(phenopacket.phenotypic_features.len(), 1);
+    }
+
+    #[test]
+    fn populate_all_collections_of_a_strict_phenopacket() {
+        let phenopacket: Phenopacket = Phenopacket::builder()
+            .require_subject()
+            .id("phenopacket-id")
+            .subject_id("individual-id")
+            .add_measurement(Measurement::builder().assay(oc("LOINC:2345-7", "Glucose")))
+            .extend_interpretations([Interpretation::builder().id("interpretation-id").solved()])
+            .add_disease(Disease::builder().term(oc("MONDO:0007915", "Lupus")))
+            .add_disease(Disease::builder().term(oc("MONDO:0005233", "NSCLC")))
+            .clear_diseases()
+            .add_medical_action(MedicalAction::builder().procedure(Procedure {
+                code: Some(oc("NCIT:C28743", "Punch Biopsy")),
+                ..Default::default()
+            }))
+            .extend_files([File::builder().uri("file:///data/genomes/proband.vcf.gz")])
+            .clear_measurements()
+            .meta_data(
+                MetaData::builder()
+                    .created(Timestamp::builder().seconds_nanos(1_000_000, 0))
+                    .created_by("Peter R.")
+                    .v2(),
+            )
+            .build();
+
+        assert!(phenopacket.measurements.is_empty());
+        assert_eq!(phenopacket.interpretations.len(), 1);
+        assert!(phenopacket.diseases.is_empty());
+        assert_eq!(phenopacket.medical_actions.len(), 1);
+        assert_eq!(phenopacket.files.len(), 1);
+    }
+
+    #[test]
+    fn record_a_timestamped_update() {
+        let phenopacket: Phenopacket = Phenopacket::builder()
+            .id("phenopacket-id")
+            .meta_data(
+                MetaData::builder()
+                    .created(Timestamp::builder().iso8601timestamp("2019-07-21").unwrap())
+                    .created_by("Peter R.")
+                    .v2(),
+            )
+            .timestamped_update(
+                "Daniel D.",
+                "Added the subject",
+                Timestamp::builder().seconds_nanos(1_600_000_000, 0),
+            )
+            .build();
+
+        let meta_data = phenopacket.meta_data.unwrap();
+        assert_eq!(
+            meta_data.updates,
+            vec![Update {
+                timestamp: Some(Timestamp {
+                    seconds: 1_600_000_000,
+                    nanos: 0
+                }),
+                updated_by: "Daniel D.".into(),
+                comment: "Added the subject".into(),
+            }]
+        );
+    }
+
+    #[test]
+    fn mark_the_subject_deceased_at_age() {
+        let phenopacket: Phenopacket = Phenopacket::builder()
+            .id("phenopacket-id")
+            .subject(Individual::builder().id("individual-id").alive())
+            .subject_deceased_at_age("P3Y4M")
+            .meta_data(
+                MetaData::builder()
+                    .created(Timestamp::builder().seconds_nanos(0, 0))
+                    .created_by("Peter R.")
+                    .v2(),
+            )
+            .build();
+
+        let subject = phenopacket.subject.unwrap();
+        assert_eq!(&subject.id, "individual-id");
+        assert_eq!(
+            subject.vital_status,
+            Some(VitalStatus {
+                status: Status::Deceased.into(),
+                time_of_death: Some(TimeElement {
+                    element: Some(Element::Age(Age {
+                        iso8601duration: "P3Y4M".into()
+                    })),
+                }),
+                cause_of_death: None,
+                survival_time_in_days: 0,
+            })
+        );
+    }
+
+    #[test]
+    fn redacted_debug_hides_personal_information() {
+        let date_of_birth: Timestamp = Timestamp::builder()
+            .iso8601timestamp("2018-03-01")
+            .expect("the timestamp should be well formatted")
+            .build();
+        let file = || {
+            File::builder()
+                .uri("file:///data/genomes/proband.vcf.gz")
+                .add_individual_file_identifier("individual-id", "NA12345")
+        };
+        let builder = Phenopacket::builder()
+            .id("phenopacket-id")
+            .subject(
+                Individual::builder()
+                    .id("individual-id")
+                    .add_alternate_id("alternate-id")
+                    .date_of_birth(date_of_birth)
+                    .female(),
+            )
+            .add_biosample(
+                Biosample::builder()
+                    .id("biosample-id")
+                    .individual_id("individual-id")
+                    .add_file(file()),
+            )
+            .add_interpretation(
+                Interpretation::builder()
+                    .id("interpretation-id")
+                    .solved()
+                    .add_genomic_interpretation(
+                        GenomicInterpretation::builder()
+                            .subject_or_biosample_id("individual-id")
+                            .causative()
+                            .gene_descriptor(GeneDescriptor::builder().hgnc("1100", "BRCA1")),
+                    ),
+            )
+            .add_file(file());
+
+        let debug = format!("{:?}", builder.redacted_debug());
+
+        assert!(debug.contains("<redacted>"));
+        assert!(debug.contains("Female"));
+        assert!(debug.contains("NA12345"));
+        for pii in [
+            "phenopacket-id",
+            "individual-id",
+            "alternate-id",
+            &date_of_birth.seconds.to_string(),
+        ] {
+            assert!(!debug.contains(pii), "{pii} should not be in {debug}");
+        }
+    }
+
+    #[test]
+    fn promote_a_phenopacket_into_a_family() {
+        let meta_data: MetaData = MetaData::builder()
+            .created(Timestamp::builder().seconds_nanos(0, 0))
+            .created_by("Peter R.")
+            .v2()
+            .build();
+
+        let family: Family = Phenopacket::builder()
+            .id("proband-id")
+            .subject(Individual::builder().id("individual-id"))
+            .meta_data(meta_data.clone())
+            .into_family("family-id")
+            .build();
+
+        assert_eq!(&family.id, "family-id");
+        assert_eq!(&family.proband.unwrap().id, "proband-id");
+        assert!(family.relatives.is_empty());
+        assert_eq!(family.meta_data, Some(meta_data));
+    }
+
+    #[test]
+    fn build_a_trio_pedigree() {
+        let person = |id: &str| Person::builder().family_id("family-id").individual_id(id);
+
+        let pedigree: Pedigree = Pedigree::builder()
+            .add_person(person("father").male().unaffected())
+            .add_person(person("mother").female().missing_status())
+            .add_person(
+                person("proband")
+                    .paternal_id("father")
+                    .maternal_id("mother")
+                    .male()
+                    .affected(),
+            )
+            .build();
+
+        let statuses: Vec<_> = pedigree
+            .persons
+            .iter()
+            .map(|p| (p.individual_id.as_str(), p.affected_status()))
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                ("father", AffectedStatus::Unaffected),
+                ("mother", AffectedStatus::Missing),
+                ("proband", AffectedStatus::Affected),
+            ]
+        );
+        let proband = &pedigree.persons[2];
+        assert_eq!(&proband.paternal_id, "father");
+        assert_eq!(&proband.maternal_id, "mother");
+        assert_eq!(proband.sex(), Sex::Male);
+        assert_eq!(&pedigree.persons[1].paternal_id, "");
+    }
+
+    #[test]
+    fn instantiate_phenopackets_from_a_template() {
+        let template = PhenopacketTemplate::new(
+            MetaData::builder()
+                .created(Timestamp::builder().seconds_nanos(0, 0))
+                .created_by("Peter R.")
+                .add_resource(Resource::builder().hpo("2018-03-08"))
+                .v2(),
+        )
+        .add_phenotypic_feature(PhenotypicFeature::builder().r#type(oc("HP:0001250", "Seizure")));
+
+        let first: Phenopacket = template
+            .instantiate("first-id", Individual::builder().id("first-individual"))
+            .build();
+        let second: Phenopacket = template
+            .instantiate("second-id", Individual::builder().id("second-individual"))
+            .add_phenotypic_feature(
+                PhenotypicFeature::builder().r#type(oc("HP:0012469", "Infantile spasms")),
+            )
+            .build();
+
+        assert_eq!(first.meta_data, second.meta_data);
+        assert_eq!(&first.subject.unwrap().id, "first-individual");
+        assert_eq!(&second.subject.unwrap().id, "second-individual");
+        assert_eq!(first.phenotypic_features.len(), 1);
+        assert_eq!(second.phenotypic_features.len(), 2);
+    }
+
+    #[test]
+    fn clear_phenotypic_features_and_count_them() {
+        let builder = Phenopacket::builder()
+            .add_phenotypic_feature(
+                PhenotypicFeature::builder().r#type(oc("HP:0001250", "Seizure")),
+            )
+            .add_phenotypic_feature(
+                PhenotypicFeature::builder().r#type(oc("HP:0012469", "Infantile spasms")),
+            )
+            .add_phenotypic_feature(
+                PhenotypicFeature::builder().r#type(oc("HP:0031796", "Recurrent")),
+            );
+
+        let (builder, removed) = builder.clear_phenotypic_features_counting();
+        assert_eq!(removed, 3);
+        assert!(builder.feature_type_ids().is_empty());
+
+        let (_, removed) = builder.clear_phenotypic_features_counting();
+        assert_eq!(removed, 0);
+    }
+
     #[test]
     fn build_an_individual() {
         let individual: Individual = Individual::builder()
@@ -105,8 +406,8 @@ mod v2 {
             })
         );
 
-        assert_eq!(&individual.sex, &Sex::Male.into());
-        assert_eq!(&individual.karyotypic_sex, &KaryotypicSex::Xy.into());
+        assert_eq!(individual.sex, i32::from(Sex::Male));
+        assert_eq!(individual.karyotypic_sex, i32::from(KaryotypicSex::Xy));
         assert_eq!(
             &individual.taxonomy,
             &Some(OntologyClass {
@@ -117,13 +418,1819 @@ mod v2 {
     }
 
     #[test]
-    fn build_an_ontology_class() {
-        let oc: OntologyClass = OntologyClass::builder()
-            .id_label("HP:0001250", "Seizure")
+    fn set_the_date_of_birth_from_a_string_or_a_date() {
+        let individual: Individual = Individual::builder()
+            .id("individual-id")
+            .date_of_birth_iso8601("2018-03-01")
+            .expect("the timestamp should be well formatted")
+            .build();
+        assert_eq!(
+            &individual.date_of_birth.unwrap().to_string(),
+            "2018-03-01T00:00:00Z"
+        );
+
+        let individual: Individual = Individual::builder()
+            .id("individual-id")
+            .date_of_birth_ymd(2018, 3, 1)
             .build();
+        assert_eq!(
+            &individual.date_of_birth.unwrap().to_string(),
+            "2018-03-01T00:00:00Z"
+        );
+    }
 
-        assert_eq!(&oc.id, "HP:0001250");
-        assert_eq!(&oc.label, "Seizure");
+    #[test]
+    fn reject_a_malformed_date_of_birth() {
+        let result = Individual::builder()
+            .id("individual-id")
+            .date_of_birth_iso8601("March 1st, 2018");
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn clear_the_collections_and_count_them() {
+        let builder = Phenopacket::builder()
+            .add_measurement(Measurement::builder().assay(oc("LOINC:2345-7", "Glucose")))
+            .add_interpretation(Interpretation::builder().id("interpretation-id").solved())
+            .add_disease(Disease::builder().term(oc("MONDO:0007915", "Lupus")))
+            .add_disease(Disease::builder().term(oc("MONDO:0005233", "NSCLC")))
+            .add_medical_action(MedicalAction::builder().procedure(Procedure::default()))
+            .add_file(File::builder().uri("file:///data/genomes/proband.vcf.gz"));
+
+        let (builder, measurements) = builder.clear_measurements_counting();
+        let (builder, interpretations) = builder.clear_interpretations_counting();
+        let (builder, diseases) = builder.clear_diseases_counting();
+        let (builder, medical_actions) = builder.clear_medical_actions_counting();
+        let (_, files) = builder.clear_files_counting();
+
+        assert_eq!(
+            (
+                measurements,
+                interpretations,
+                diseases,
+                medical_actions,
+                files
+            ),
+            (1, 1, 2, 1, 1)
+        );
+    }
+
+    #[test]
+    fn build_populates_every_individual_field() {
+        let individual: Individual = Individual::builder()
+            .id("individual-id")
+            .add_alternate_id("alternate-id")
+            .date_of_birth(
+                Timestamp::builder()
+                    .iso8601timestamp("2018-03-01")
+                    .expect("the timestamp should be well formatted"),
+            )
+            .age_at_last_encounter("P3Y4M")
+            .alive()
+            .female()
+            .karyotypic_sex_xx()
+            .gender(oc("LOINC:LA22878-5", "Identifies as female"))
+            .homo_sapiens()
+            .build();
+
+        let default = Individual::default();
+        assert_ne!(individual.id, default.id);
+        assert_ne!(individual.alternate_ids, default.alternate_ids);
+        assert_ne!(individual.date_of_birth, default.date_of_birth);
+        assert_ne!(
+            individual.time_at_last_encounter,
+            default.time_at_last_encounter
+        );
+        assert_ne!(individual.vital_status, default.vital_status);
+        assert_ne!(individual.sex, default.sex);
+        assert_ne!(individual.karyotypic_sex, default.karyotypic_sex);
+        assert_ne!(individual.gender, default.gender);
+        assert_ne!(individual.taxonomy, default.taxonomy);
+    }
+
+    #[test]
+    fn infer_sex_from_karyotype() {
+        for (karyotypic_sex, sex) in [
+            (KaryotypicSex::Xx, Sex::Female),
+            (KaryotypicSex::Xy, Sex::Male),
+            (KaryotypicSex::Xxy, Sex::OtherSex),
+        ] {
+            let individual: Individual = Individual::builder()
+                .id("individual-id")
+                .karyotypic_sex(karyotypic_sex)
+                .infer_sex_from_karyotype()
+                .build();
+
+            assert_eq!(individual.sex, i32::from(sex));
+        }
+    }
+
+    #[test]
+    fn create_meta_data_now() {
+        let before = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("the clock should be after the epoch")
+            .as_secs() as i64;
+
+        let md: MetaData = MetaData::builder()
+            .created_now()
+            .created_by("Peter R.")
+            .build();
+
+        let created = md.created.expect("created should be set");
+        assert!(created.seconds >= before);
+        assert!(created.seconds - before < 60);
+    }
+
+    #[test]
+    fn edit_meta_data() {
+        let original: MetaData = MetaData::builder()
+            .created(
+                Timestamp::builder()
+                    .iso8601timestamp("2019-07-21T00:25:54.662Z")
+                    .unwrap(),
+            )
+            .created_by("Peter R.")
+            .submitted_by("Daniel D.")
+            .add_resource(Resource::builder().hpo("2018-03-08"))
+            .add_external_reference(ExternalReference::builder().id("PMID:30808312"))
+            .v2()
+            .build();
+
+        let edited: MetaData = MetaDataBuilder::from_meta_data(original.clone())
+            .expect("the original metadata is complete")
+            .add_update(
+                Update::builder()
+                    .timestamp(Timestamp::builder().iso8601timestamp("2020-01-01").unwrap())
+                    .updated_by("Peter R.")
+                    .comment("Added onset"),
+            )
+            .build();
+
+        assert_eq!(edited.updates.len(), 1);
+        assert_eq!(&edited.updates[0].comment, "Added onset");
+        assert_eq!(
+            MetaData {
+                updates: vec![],
+                ..edited
+            },
+            original
+        );
+    }
+
+    #[test]
+    fn merge_meta_data() {
+        let update = |date: &str, comment: &str| {
+            Update::builder()
+                .timestamp(Timestamp::builder().iso8601timestamp(date).unwrap())
+                .updated_by("Peter R.")
+                .comment(comment)
+        };
+        let ours = MetaData::builder()
+            .created(Timestamp::builder().iso8601timestamp("2020-01-01").unwrap())
+            .created_by("Peter R.")
+            .add_resource(Resource::builder().hpo("2024-08-13"))
+            .add_resource(Resource::builder().geno("2022-03-05"))
+            .add_update(update("2021-06-01", "Added the onset"))
+            .v2();
+        let theirs: MetaData = MetaData::builder()
+            .created(Timestamp::builder().iso8601timestamp("2019-07-21").unwrap())
+            .created_by("Daniel D.")
+            .add_resource(Resource::builder().hpo("2018-03-08"))
+            .add_resource(Resource::builder().mondo("2024-06-04"))
+            .add_update(update("2020-03-01", "Added the diagnosis"))
+            .v2()
+            .build();
+
+        let phenopacket: Phenopacket = Phenopacket::builder()
+            .id("phenopacket-id")
+            .meta_data(ours)
+            .merge_metadata(theirs)
+            .build();
+
+        let meta_data = phenopacket.meta_data.unwrap();
+        let prefixes: Vec<_> = meta_data
+            .resources
+            .iter()
+            .map(|r| r.namespace_prefix.as_str())
+            .collect();
+        assert_eq!(prefixes, vec!["HP", "GENO", "MONDO"]);
+        assert_eq!(&meta_data.resources[0].version, "2024-08-13");
+
+        let comments: Vec<_> = meta_data
+            .updates
+            .iter()
+            .map(|u| u.comment.as_str())
+            .collect();
+        assert_eq!(comments, vec!["Added the diagnosis", "Added the onset"]);
+
+        assert_eq!(
+            &meta_data.created.unwrap().to_string(),
+            "2019-07-21T00:00:00Z"
+        );
+        assert_eq!(&meta_data.created_by, "Peter R.");
+    }
+
+    #[test]
+    fn look_up_meta_data_resources() {
+        let builder = MetaData::builder()
+            .add_resource(Resource::builder().hpo("2018-03-08"))
+            .add_resource(Resource::builder().geno("2018-03-19"));
+
+        assert_eq!(builder.resource_prefixes(), vec!["HP", "GENO"]);
+        assert!(builder.has_resource_for("HP"));
+        assert!(!builder.has_resource_for("MONDO"));
+    }
+
+    #[test]
+    fn build_orcid_and_orphanet_resources() {
+        let orcid: Resource = Resource::builder().orcid().build();
+        let orphanet: Resource = Resource::builder().orphanet("4.6").build();
+
+        assert_eq!(&orcid.namespace_prefix, "ORCID");
+        assert_eq!(&orcid.iri_prefix, "https://orcid.org/");
+        assert_eq!(&orphanet.namespace_prefix, "ORPHA");
+        assert_eq!(&orphanet.iri_prefix, "https://www.orpha.net/ORDO/Orphanet_");
+        assert_eq!(&orphanet.version, "4.6");
+    }
+
+    #[test]
+    fn build_efo_eco_and_chebi_resources() {
+        let efo: Resource = Resource::builder().efo("3.69.0").build();
+        let eco: Resource = Resource::builder().eco("2024-07-19").build();
+        let chebi: Resource = Resource::builder().chebi("235").build();
+
+        assert_eq!(&efo.namespace_prefix, "EFO");
+        assert_eq!(&efo.iri_prefix, "https://www.ebi.ac.uk/efo/EFO_");
+        assert_eq!(&eco.namespace_prefix, "ECO");
+        assert_eq!(&eco.iri_prefix, "https://purl.obolibrary.org/obo/ECO_");
+        assert_eq!(&chebi.namespace_prefix, "CHEBI");
+        assert_eq!(&chebi.iri_prefix, "https://purl.obolibrary.org/obo/CHEBI_");
+        assert_eq!(&chebi.version, "235");
+    }
+
+    #[test]
+    fn build_a_resource_from_a_known_prefix() {
+        let mondo: Resource = ResourceBuilder::from_prefix("MONDO", "2024-06-04")
+            .expect("MONDO should have a preset")
+            .build();
+
+        assert_eq!(mondo, Resource::builder().mondo("2024-06-04").build());
+    }
+
+    #[test]
+    fn resource_presets_point_to_their_own_namespace() {
+        for prefix in ResourceBuilder::preset_prefixes() {
+            let resource: Resource = ResourceBuilder::from_prefix(prefix, "")
+                .expect("the prefix should have a preset")
+                .build();
+
+            assert_eq!(&resource.namespace_prefix, prefix);
+            if resource.url.starts_with("https://purl.obolibrary.org/obo/") {
+                assert!(
+                    resource
+                        .url
+                        .contains(&format!("/obo/{}.", prefix.to_lowercase())),
+                    "{prefix} url {} points to another ontology",
+                    resource.url
+                );
+                assert_eq!(
+                    resource.iri_prefix,
+                    format!("https://purl.obolibrary.org/obo/{prefix}_")
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn no_resource_from_an_unknown_prefix() {
+        assert!(ResourceBuilder::from_prefix("DOID", "2024-07-31").is_none());
+    }
+
+    #[test]
+    fn build_clinical_terminology_resources() {
+        let snomed: Resource = Resource::builder().snomed("2024-09-01").build();
+        let icd10: Resource = Resource::builder().icd10("2019").build();
+        let umls: Resource = Resource::builder().umls("2024AB").build();
+
+        assert_eq!(
+            snomed,
+            Resource {
+                id: "snomedct".into(),
+                name: "SNOMED Clinical Terms".into(),
+                url: "https://www.snomed.org".into(),
+                version: "2024-09-01".into(),
+                namespace_prefix: "SNOMEDCT".into(),
+                iri_prefix: "http://snomed.info/id/".into(),
+            }
+        );
+        assert_eq!(
+            icd10,
+            Resource {
+                id: "icd10".into(),
+                name: "International Classification of Diseases, 10th Revision".into(),
+                url: "https://icd.who.int/browse10".into(),
+                version: "2019".into(),
+                namespace_prefix: "ICD10".into(),
+                iri_prefix: "https://icd.who.int/browse10/2019/en#/".into(),
+            }
+        );
+        assert_eq!(
+            umls,
+            Resource {
+                id: "umls".into(),
+                name: "Unified Medical Language System".into(),
+                url: "https://www.nlm.nih.gov/research/umls".into(),
+                version: "2024AB".into(),
+                namespace_prefix: "UMLS".into(),
+                iri_prefix: "https://uts.nlm.nih.gov/uts/umls/concept/".into(),
+            }
+        );
+    }
+
+    #[test]
+    fn build_maxo_cl_pato_and_go_resources() {
+        let obo = |id: &str, name: &str, prefix: &str, version: &str| Resource {
+            id: id.into(),
+            name: name.into(),
+            url: format!("https://purl.obolibrary.org/obo/{id}.owl"),
+            version: version.into(),
+            namespace_prefix: prefix.into(),
+            iri_prefix: format!("https://purl.obolibrary.org/obo/{prefix}_"),
+        };
+
+        let built: Vec<Resource> = vec![
+            Resource::builder().maxo("2024-05-24").build(),
+            Resource::builder().cl("2024-08-16").build(),
+            Resource::builder().pato("2024-03-28").build(),
+            Resource::builder().go("2024-06-17").build(),
+        ];
+
+        assert_eq!(
+            built,
+            vec![
+                obo("maxo", "Medical Action Ontology", "MAXO", "2024-05-24"),
+                obo("cl", "Cell Ontology", "CL", "2024-08-16"),
+                obo("pato", "Phenotype And Trait Ontology", "PATO", "2024-03-28"),
+                obo("go", "Gene Ontology", "GO", "2024-06-17"),
+            ]
+        );
+    }
+
+    #[test]
+    fn build_tumor_and_normal_biosamples() {
+        let tumor: Biosample = Biosample::builder()
+            .id("tumor-id")
+            .individual_id("individual-id")
+            .sampled_tissue(oc("UBERON:0002367", "prostate gland"))
+            .tumor()
+            .build();
+        let normal: Biosample = Biosample::builder()
+            .id("normal-id")
+            .individual_id("individual-id")
+            .normal()
+            .build();
+
+        assert_eq!(
+            tumor.tumor_progression,
+            Some(oc("NCIT:C84509", "Primary Malignant Neoplasm"))
+        );
+        assert_eq!(
+            normal.tumor_progression,
+            Some(oc("NCIT:C14165", "Normal Tissue"))
+        );
+    }
+
+    #[test]
+    fn note_family_history() {
+        let phenopacket: Phenopacket = Phenopacket::builder()
+            .id("phenopacket-id")
+            .note_family_history(oc("HP:0001250", "Seizure"))
+            .meta_data(
+                MetaData::builder()
+                    .created(Timestamp::builder().seconds_nanos(0, 0))
+                    .created_by("Peter R.")
+                    .v2(),
+            )
+            .build();
+
+        let feature = &phenopacket.phenotypic_features[0];
+        assert_eq!(feature.r#type, Some(oc("HP:0001250", "Seizure")));
+        assert_eq!(feature.modifiers, vec![oc("HP:0032316", "Family history")]);
+    }
+
+    #[test]
+    fn extend_observed_and_excluded_features() {
+        let phenopacket: Phenopacket = Phenopacket::builder()
+            .id("phenopacket-id")
+            .extend_observed_features([
+                ("HP:0001250", "Seizure"),
+                ("HP:0001263", "Global developmental delay"),
+                ("HP:0000252", "Microcephaly"),
+            ])
+            .extend_excluded_features([
+                ("HP:0001627", "Abnormal heart morphology"),
+                ("HP:0000407", "Sensorineural hearing impairment"),
+            ])
+            .meta_data(
+                MetaData::builder()
+                    .created(Timestamp::builder().seconds_nanos(0, 0))
+                    .created_by("Peter R.")
+                    .v2(),
+            )
+            .build();
+
+        let excluded: Vec<_> = phenopacket
+            .phenotypic_features
+            .iter()
+            .map(|feature| feature.excluded)
+            .collect();
+        assert_eq!(excluded, vec![false, false, false, true, true]);
+        assert_eq!(
+            phenopacket.phenotypic_features[3].r#type,
+            Some(oc("HP:0001627", "Abnormal heart morphology"))
+        );
+    }
+
+    #[test]
+    fn inspect_biosample_and_feature_type_ids() {
+        let builder = Phenopacket::builder()
+            .add_biosample(Biosample::builder().id("biosample-1").tumor())
+            .add_biosample(Biosample::builder().id("biosample-2").normal())
+            .add_phenotypic_feature(
+                PhenotypicFeature::builder().r#type(oc("HP:0012469", "Infantile spasms")),
+            )
+            .add_phenotypic_feature(
+                PhenotypicFeature::builder().r#type(oc("HP:0031796", "Recurrent")),
+            );
+
+        assert_eq!(builder.biosample_ids(), vec!["biosample-1", "biosample-2"]);
+        assert_eq!(builder.feature_type_ids(), vec!["HP:0012469", "HP:0031796"]);
+    }
+
+    #[test]
+    fn build_a_phenopacket_with_all_repeated_fields() {
+        let phenopacket: Phenopacket = Phenopacket::builder()
+            .id("phenopacket-id")
+            .add_phenotypic_feature(
+                PhenotypicFeature::builder().r#type(oc("HP:0001250", "Seizure")),
+            )
+            .add_measurement(Measurement::builder().assay(oc(
+                "LOINC:2345-7",
+                "Glucose [Mass/volume] in Serum or Plasma",
+            )))
+            .add_biosample(Biosample::builder().id("biosample-id"))
+            .add_interpretation(Interpretation::builder().id("interpretation-id").solved())
+            .add_disease(Disease::builder().term(oc("MONDO:0007915", "Lupus")))
+            .add_medical_action(
+                MedicalAction::builder()
+                    .treatment(Treatment::builder().agent(oc("DrugCentral:1678", "cisplatin"))),
+            )
+            .add_file(File::builder().uri("file:///data/genomes/proband.vcf.gz"))
+            .meta_data(
+                MetaData::builder()
+                    .created(Timestamp::builder().seconds_nanos(0, 0))
+                    .created_by("Peter R.")
+                    .v2(),
+            )
+            .build();
+
+        assert_eq!(phenopacket.phenotypic_features.len(), 1);
+        assert_eq!(phenopacket.measurements.len(), 1);
+        assert_eq!(phenopacket.biosamples.len(), 1);
+        assert_eq!(&phenopacket.interpretations[0].id, "interpretation-id");
+        assert_eq!(
+            phenopacket.diseases[0].term,
+            Some(oc("MONDO:0007915", "Lupus"))
+        );
+        assert_eq!(phenopacket.medical_actions.len(), 1);
+        assert_eq!(
+            &phenopacket.files[0].uri,
+            "file:///data/genomes/proband.vcf.gz"
+        );
+    }
+
+    #[test]
+    fn extend_and_clear_repeated_fields() {
+        let builder = Phenopacket::builder()
+            .extend_diseases([
+                Disease::builder().term(oc("MONDO:0007915", "Lupus")),
+                Disease::builder().term(oc("MONDO:0005233", "non-small cell lung carcinoma")),
+            ])
+            .extend_files([File::builder().uri("file:///data/a.vcf")])
+            .clear_files();
+
+        let phenopacket: Phenopacket = builder
+            .id("phenopacket-id")
+            .meta_data(
+                MetaData::builder()
+                    .created(Timestamp::builder().seconds_nanos(0, 0))
+                    .created_by("Peter R.")
+                    .v2(),
+            )
+            .build();
+
+        assert_eq!(phenopacket.diseases.len(), 2);
+        assert!(phenopacket.files.is_empty());
+    }
+
+    #[test]
+    fn medical_action_replaces_the_action() {
+        let medical_action: MedicalAction = MedicalAction::builder()
+            .treatment(Treatment::builder().agent(oc("DrugCentral:1678", "cisplatin")))
+            .procedure(Procedure {
+                code: Some(oc("NCIT:C28743", "Punch Biopsy")),
+                ..Default::default()
+            })
+            .build();
+
+        assert!(matches!(medical_action.action, Some(Action::Procedure(_))));
+    }
+
+    #[test]
+    fn build_a_treatment_with_cumulative_dose() {
+        let treatment: Treatment = Treatment::builder()
+            .agent(oc("DrugCentral:1678", "cisplatin"))
+            .prescription()
+            .cumulative_dose_ucum(450., "mg")
+            .build();
+
+        let dose = treatment.cumulative_dose.unwrap();
+        assert_eq!(dose.value, 450.);
+        assert_eq!(
+            dose.unit,
+            Some(OntologyClass {
+                id: "UCUM:mg".into(),
+                label: "mg".into(),
+            })
+        );
+    }
+
+    #[test]
+    fn build_a_medical_action_with_adverse_events() {
+        let medical_action: MedicalAction = MedicalAction::builder()
+            .treatment(Treatment::builder().agent(oc("DrugCentral:1678", "cisplatin")))
+            .add_adverse_event(oc("HP:0002018", "Nausea"))
+            .add_adverse_event(oc("HP:0000365", "Hearing impairment"))
+            .build();
+
+        assert_eq!(
+            medical_action.adverse_events,
+            vec![
+                oc("HP:0002018", "Nausea"),
+                oc("HP:0000365", "Hearing impairment")
+            ]
+        );
+    }
+
+    #[test]
+    fn build_a_therapeutic_regimen_identified_by_an_ontology_class() {
+        let regimen: TherapeuticRegimen = TherapeuticRegimen::builder()
+            .ontology_class(oc("NCIT:C10894", "Carboplatin/Paclitaxel"))
+            .start_time(TimeElement::builder().age_iso8601duration("P62Y"))
+            .end_time(TimeElement::builder().age_iso8601duration("P62Y6M"))
+            .started()
+            .build();
+
+        assert_eq!(
+            regimen.identifier,
+            Some(Identifier::OntologyClass(oc(
+                "NCIT:C10894",
+                "Carboplatin/Paclitaxel"
+            )))
+        );
+        assert!(regimen.start_time.is_some());
+        assert!(regimen.end_time.is_some());
+        assert_eq!(regimen.regimen_status, RegimenStatus::Started as i32);
+    }
+
+    #[test]
+    fn build_a_therapeutic_regimen_identified_by_an_external_reference() {
+        let reference = ExternalReference {
+            id: "PMID:30808005".into(),
+            ..Default::default()
+        };
+        let regimen: TherapeuticRegimen = TherapeuticRegimen::builder()
+            .ontology_class(oc("NCIT:C10894", "Carboplatin/Paclitaxel"))
+            .external_reference(reference.clone())
+            .build();
+
+        assert_eq!(
+            regimen.identifier,
+            Some(Identifier::ExternalReference(reference))
+        );
+        assert_eq!(regimen.regimen_status, RegimenStatus::UnknownStatus as i32);
+    }
+
+    #[test]
+    fn set_the_therapeutic_regimen_status() {
+        let regimen = || {
+            TherapeuticRegimen::builder()
+                .ontology_class(oc("NCIT:C10894", "Carboplatin/Paclitaxel"))
+        };
+        let cases = [
+            (regimen().unknown_status(), RegimenStatus::UnknownStatus),
+            (regimen().started(), RegimenStatus::Started),
+            (regimen().completed(), RegimenStatus::Completed),
+            (regimen().discontinued(), RegimenStatus::Discontinued),
+        ];
+
+        for (builder, expected) in cases {
+            let regimen: TherapeuticRegimen = builder.build();
+            assert_eq!(regimen.regimen_status, expected as i32);
+        }
+    }
+
+    #[test]
+    fn build_a_qualitative_measurement() {
+        let measurement: Measurement = Measurement::builder()
+            .assay(oc(
+                "LOINC:5195-3",
+                "Hepatitis B virus surface Ag [Presence] in Serum",
+            ))
+            .value_ontology_class(oc("NCIT:C38757", "Negative"))
+            .build();
+
+        match measurement.measurement_value {
+            Some(MeasurementValue::Value(Value {
+                value: Some(value::Value::OntologyClass(result)),
+            })) => assert_eq!(result, oc("NCIT:C38757", "Negative")),
+            _ => panic!("expected an ontology class value"),
+        }
+    }
+
+    #[test]
+    fn build_a_blood_pressure_measurement() {
+        let mm_hg = || oc("UCUM:mm[Hg]", "mmHg");
+        let measurement: Measurement = Measurement::builder()
+            .assay(oc("CMO:0000003", "blood pressure"))
+            .complex_value(
+                ComplexValue::builder()
+                    .add_typed_quantity(
+                        TypedQuantity::builder()
+                            .r#type(oc("NCIT:C25298", "Systolic Blood Pressure"))
+                            .quantity(Quantity::builder().unit(mm_hg()).value(125.)),
+                    )
+                    .add_typed_quantity(
+                        TypedQuantity::builder()
+                            .r#type(oc("NCIT:C25299", "Diastolic Blood Pressure"))
+                            .quantity(Quantity::builder().unit(mm_hg()).value(75.)),
+                    ),
+            )
+            .build();
+
+        let Some(MeasurementValue::ComplexValue(complex_value)) = measurement.measurement_value
+        else {
+            panic!("the measurement should have a complex value");
+        };
+        let values: Vec<_> = complex_value
+            .typed_quantities
+            .iter()
+            .map(|tq| {
+                (
+                    tq.r#type.clone().unwrap().id,
+                    tq.quantity.clone().unwrap().value,
+                )
+            })
+            .collect();
+        assert_eq!(
+            values,
+            vec![
+                ("NCIT:C25298".to_string(), 125.),
+                ("NCIT:C25299".to_string(), 75.)
+            ]
+        );
+    }
+
+    #[test]
+    fn build_an_ontology_class_value() {
+        let value: Value = Value::builder()
+            .quantity(Quantity::builder().percent(5.))
+            .ontology_class(oc("LOINC:LA6576-8", "Positive"))
+            .build();
+
+        assert_eq!(
+            value.value,
+            Some(value::Value::OntologyClass(oc(
+                "LOINC:LA6576-8",
+                "Positive"
+            )))
+        );
+    }
+
+    #[test]
+    fn measurement_value_replaces_complex_value() {
+        let measurement: Measurement = Measurement::builder()
+            .assay(oc("CMO:0000003", "blood pressure"))
+            .complex_value(ComplexValue::default())
+            .value(Value {
+                value: Some(value::Value::Quantity(
+                    Quantity::builder().percent(5.).build(),
+                )),
+            })
+            .build();
+
+        assert!(matches!(
+            measurement.measurement_value,
+            Some(MeasurementValue::Value(_))
+        ));
+    }
+
+    #[test]
+    fn build_a_quantity_with_reference_range() {
+        let quantity: Quantity = Quantity::builder()
+            .unit(oc("UCUM:mmol/L", "mmol/L"))
+            .value(5.9)
+            .try_reference_range_low_high(3.9, 5.6)
+            .expect("the bounds are ordered")
+            .build();
+
+        assert_eq!(
+            quantity.reference_range,
+            Some(ReferenceRange {
+                unit: Some(oc("UCUM:mmol/L", "mmol/L")),
+                low: 3.9,
+                high: 5.6,
+            })
+        );
+    }
+
+    #[test]
+    fn build_percent_and_ratio_quantities() {
+        let percent: Quantity = Quantity::builder().percent(6.5).build();
+        let ratio: Quantity = Quantity::builder().ratio(1.2).build();
+
+        assert_eq!(percent.unit, Some(oc("UCUM:%", "percent")));
+        assert_eq!(percent.value, 6.5);
+        assert_eq!(ratio.unit, Some(oc("UCUM:{ratio}", "ratio")));
+        assert_eq!(ratio.value, 1.2);
+    }
+
+    #[test]
+    fn reference_range_bounds_must_be_ordered() {
+        let result = Quantity::builder()
+            .unit(oc("UCUM:mmol/L", "mmol/L"))
+            .try_reference_range_low_high(5.6, 3.9);
+
+        assert_eq!(
+            result.map(|_| ()),
+            Err(ReferenceRangeError::LowAboveHigh {
+                low: 5.6,
+                high: 3.9
+            })
+        );
+    }
+
+    #[test]
+    fn reference_range_bounds_must_be_numbers() {
+        let result = Quantity::builder()
+            .unit(oc("UCUM:mmol/L", "mmol/L"))
+            .try_reference_range_low_high(f64::NAN, 3.9);
+
+        assert_eq!(result.map(|_| ()), Err(ReferenceRangeError::NotANumber));
+    }
+
+    #[test]
+    fn build_an_excluded_disease() {
+        let disease: Disease = Disease::builder()
+            .term(oc("MONDO:0007915", "systemic lupus erythematosus"))
+            .excluded()
+            .onset(TimeElement::builder().age_iso8601duration("P15Y"))
+            .add_disease_stage(oc("NCIT:C28054", "Stage 2"))
+            .laterality(oc("HP:0012834", "Right"))
+            .build();
+
+        assert_eq!(
+            disease.term,
+            Some(oc("MONDO:0007915", "systemic lupus erythematosus"))
+        );
+        assert!(disease.excluded);
+        assert_eq!(
+            disease.onset,
+            Some(TimeElement {
+                element: Some(Element::Age(Age {
+                    iso8601duration: "P15Y".into()
+                }))
+            })
+        );
+        assert_eq!(disease.disease_stage, vec![oc("NCIT:C28054", "Stage 2")]);
+        assert_eq!(disease.laterality, Some(oc("HP:0012834", "Right")));
+    }
+
+    #[test]
+    fn build_a_time_interval() {
+        let start = || Timestamp::builder().seconds_nanos(1_635_897_600, 0);
+        let end = || Timestamp::builder().seconds_nanos(1_636_502_400, 0);
+
+        let te: TimeElement = TimeElement::builder()
+            .age_iso8601duration("P3Y")
+            .interval(TimeInterval::builder().start(start()).end(end()))
+            .build();
+
+        assert_eq!(
+            te.element,
+            Some(Element::Interval(TimeInterval {
+                start: Some(start().build()),
+                end: Some(end().build()),
+            }))
+        );
+    }
+
+    #[test]
+    fn build_a_time_interval_from_iso8601_timestamps() {
+        let te: TimeElement = TimeElement::builder()
+            .interval_iso8601("2021-11-03", "2021-11-10")
+            .expect("well formatted timestamps")
+            .build();
+
+        let Some(Element::Interval(interval)) = te.element else {
+            panic!("expected an interval");
+        };
+        assert_eq!(interval.start.unwrap().seconds, 1_635_897_600);
+        assert_eq!(interval.end.unwrap().seconds, 1_636_502_400);
+
+        assert!(TimeElement::builder()
+            .interval_iso8601("2021-11-03", "10 Nov 2021")
+            .is_err());
+    }
+
+    #[test]
+    fn build_a_gestational_age_from_iso8601_duration() {
+        let te: TimeElement = TimeElement::builder()
+            .gestational_age_iso8601("P25W5D")
+            .expect("well formatted duration")
+            .build();
+
+        let Some(Element::GestationalAge(gestational_age)) = te.element else {
+            panic!("expected a gestational age");
+        };
+        assert_eq!(gestational_age.weeks, 25);
+        assert_eq!(gestational_age.days, 5);
+
+        assert_eq!(
+            TimeElement::builder().gestational_age_iso8601("P1Y2W"),
+            Err(GestationalAgeError::Malformed)
+        );
+        assert_eq!(
+            TimeElement::builder().gestational_age_iso8601("P-2W"),
+            Err(GestationalAgeError::Negative)
+        );
+        assert_eq!(
+            TimeElement::builder().gestational_age_iso8601("P40W12D"),
+            Err(GestationalAgeError::DaysOutOfRange(12))
+        );
+    }
+
+    #[test]
+    fn deceased_at_an_age() {
+        let age: Age = Age::builder().iso8601duration("P70Y").build();
+
+        let individual: Individual = Individual::builder()
+            .id("subject-id")
+            .deceased_at(age.clone())
+            .build();
+
+        let time_of_death = individual.vital_status.unwrap().time_of_death.unwrap();
+        assert_eq!(time_of_death.element, Some(Element::Age(age)));
+    }
+
+    #[test]
+    fn vital_status_deceased_at_age() {
+        let vital_status: VitalStatus = VitalStatus::builder()
+            .deceased_at_age_iso8601("P63Y")
+            .build();
+
+        assert_eq!(vital_status.status(), Status::Deceased);
+        assert_eq!(
+            vital_status.time_of_death,
+            Some(TimeElement {
+                element: Some(Element::Age(Age {
+                    iso8601duration: "P63Y".into()
+                })),
+            })
+        );
+    }
+
+    #[test]
+    fn vital_status_deceased_at_timestamp() {
+        let vital_status: VitalStatus = VitalStatus::builder()
+            .deceased_at_timestamp(Timestamp::builder().seconds_nanos(1_700_000_000, 0))
+            .build();
+
+        assert_eq!(vital_status.status(), Status::Deceased);
+        assert_eq!(
+            vital_status.time_of_death,
+            Some(TimeElement {
+                element: Some(Element::Timestamp(Timestamp {
+                    seconds: 1_700_000_000,
+                    nanos: 0,
+                })),
+            })
+        );
+    }
+
+    #[test]
+    fn time_of_death_from_time_element_values() {
+        let time_of_death =
+            |time_of_death: VitalStatus| time_of_death.time_of_death.unwrap().element;
+
+        let gestational_age = GestationalAge { weeks: 33, days: 2 };
+        let vs: VitalStatus = VitalStatus::builder()
+            .deceased()
+            .time_of_death(gestational_age)
+            .build();
+        assert_eq!(
+            time_of_death(vs),
+            Some(Element::GestationalAge(gestational_age))
+        );
+
+        let timestamp: Timestamp = Timestamp::builder().seconds_nanos(1_636_502_400, 0).build();
+        let vs: VitalStatus = VitalStatus::builder()
+            .deceased()
+            .time_of_death(timestamp)
+            .build();
+        assert_eq!(time_of_death(vs), Some(Element::Timestamp(timestamp)));
+
+        let neonatal = oc("HP:0003623", "Neonatal onset");
+        let vs: VitalStatus = VitalStatus::builder()
+            .deceased()
+            .time_of_death(neonatal.clone())
+            .build();
+        assert_eq!(time_of_death(vs), Some(Element::OntologyClass(neonatal)));
+    }
+
+    #[test]
+    fn build_an_age_from_components() {
+        let age: Age = Age::builder().years(3).months(4).days(5).build();
+        assert_eq!(&age.iso8601duration, "P3Y4M5D");
+
+        let age: Age = Age::builder().years(1).months(0).build();
+        assert_eq!(&age.iso8601duration, "P1Y");
+
+        let age: Age = Age::builder().hours(12).build();
+        assert_eq!(&age.iso8601duration, "PT12H");
+
+        let age: Age = Age::builder().days(2).hours(6).build();
+        assert_eq!(&age.iso8601duration, "P2DT6H");
+
+        let age: Age = Age::builder().years(0).build();
+        assert_eq!(&age.iso8601duration, "P0D");
+    }
+
+    #[test]
+    fn check_gestational_age_weeks_and_days() {
+        let gestational_age: GestationalAge = GestationalAge::builder()
+            .try_weeks(25)
+            .expect("non-negative weeks")
+            .try_days(6)
+            .expect("days in range")
+            .build();
+        assert_eq!(gestational_age, GestationalAge { weeks: 25, days: 6 });
+
+        let error = GestationalAge::builder()
+            .weeks(25)
+            .try_days(7)
+            .expect_err("7 days make another week");
+        assert_eq!(error, GestationalAgeError::DaysOutOfRange(7));
+        assert_eq!(&error.to_string(), "gestational days 7 must be in 0..=6");
+
+        assert_eq!(
+            GestationalAge::builder().weeks(25).try_days(-1),
+            Err(GestationalAgeError::Negative)
+        );
+        assert_eq!(
+            GestationalAge::builder().try_weeks(-1),
+            Err(GestationalAgeError::Negative)
+        );
+    }
+
+    #[test]
+    fn reject_a_negative_age() {
+        assert_eq!(
+            Age::builder().try_iso8601duration("P-1Y"),
+            Err(AgeError::Negative)
+        );
+    }
+
+    #[test]
+    fn accept_a_positive_age() {
+        let age: Age = Age::builder()
+            .try_iso8601duration("P1Y")
+            .expect("P1Y is a valid age")
+            .build();
+
+        assert_eq!(&age.iso8601duration, "P1Y");
+    }
+
+    #[test]
+    #[should_panic(expected = "iso8601duration P-1Y must not be negative")]
+    fn non_negative_age_panics_on_a_negative_duration() {
+        let _: Age = Age::builder()
+            .non_negative()
+            .iso8601duration("P-1Y")
+            .build();
+    }
+
+    #[test]
+    fn non_negative_age_fails_to_try_build_a_negative_duration() {
+        let result: Result<Age, _> = Age::builder()
+            .non_negative()
+            .iso8601duration("P-1Y")
+            .try_build();
+
+        assert_eq!(result, Err(BuildError::NegativeDuration("P-1Y".into())));
+    }
+
+    #[test]
+    fn attach_evidence_to_a_phenotypic_feature() {
+        let feature: PhenotypicFeature = PhenotypicFeature::builder()
+            .r#type(oc("HP:0001250", "Seizure"))
+            .add_evidence(
+                Evidence::builder()
+                    .evidence_code(oc(
+                        "ECO:0000033",
+                        "author statement supported by traceable reference",
+                    ))
+                    .reference(
+                        ExternalReference::builder()
+                            .id("PMID:30808312")
+                            .description("COL6A1 mutation leading to Bethlem myopathy"),
+                    ),
+            )
+            .build();
+
+        assert_eq!(
+            feature.evidence,
+            vec![Evidence {
+                evidence_code: Some(oc(
+                    "ECO:0000033",
+                    "author statement supported by traceable reference"
+                )),
+                reference: Some(ExternalReference {
+                    id: "PMID:30808312".into(),
+                    reference: "".into(),
+                    description: "COL6A1 mutation leading to Bethlem myopathy".into(),
+                }),
+            }]
+        );
+    }
+
+    #[test]
+    fn try_build_an_ontology_class_without_id() {
+        assert_eq!(
+            OntologyClass::builder().try_build(),
+            Err(BuildError::MissingField("id"))
+        );
+    }
+
+    #[test]
+    fn try_build_an_ontology_class_with_blank_label() {
+        let unknown = OntologyClass::builder().unknown("HP:0001250");
+
+        assert_eq!(
+            unknown.clone().try_build(),
+            Err(BuildError::BlankLabel("HP:0001250".into()))
+        );
+        let built: OntologyClass = unknown.build();
+        assert_eq!(&built.label, "");
+    }
+
+    #[test]
+    fn try_build_an_ontology_class_with_malformed_id() {
+        assert_eq!(
+            OntologyClass::builder()
+                .id_label("HP_0001250", "Seizure")
+                .try_build(),
+            Err(BuildError::MalformedCurie("HP_0001250".into()))
+        );
+    }
+
+    #[test]
+    fn try_build_an_ontology_class_through_the_trait() {
+        let result: Result<OntologyClass, _> = TryBuild::try_build(OntologyClass::builder());
+
+        assert_eq!(result, Err(BuildError::MissingField("id")));
+    }
+
+    #[test]
+    fn try_build_a_resource_without_name() {
+        let result: Result<Resource, _> = Resource::builder().id("hp").try_build();
+
+        assert_eq!(result, Err(BuildError::MissingField("name")));
+    }
+
+    #[test]
+    fn try_build_a_complete_resource() {
+        let result: Result<Resource, _> = Resource::builder().hpo("2024-08-13").try_build();
+
+        assert_eq!(result.map(|r| r.id), Ok("hp".to_string()));
+    }
+
+    #[test]
+    fn try_build_a_phenopacket_without_meta_data() {
+        let result: Result<Phenopacket, _> = Phenopacket::builder().id("pp-id").try_build();
+
+        assert_eq!(result, Err(BuildError::MissingField("meta_data")));
+    }
+
+    #[test]
+    fn try_build_a_phenopacket_without_id() {
+        let result: Result<Phenopacket, _> = Phenopacket::builder().try_build();
+
+        assert_eq!(result, Err(BuildError::MissingField("id")));
+    }
+
+    #[test]
+    fn try_build_a_disease_without_term() {
+        let result: Result<Disease, _> = Disease::builder().onset_adult().try_build();
+
+        assert_eq!(result, Err(BuildError::MissingField("term")));
+    }
+
+    #[test]
+    fn try_build_reports_the_missing_required_field() {
+        let individual: Result<Individual, _> = Individual::builder().male().try_build();
+        assert_eq!(individual, Err(BuildError::MissingField("id")));
+
+        let feature: Result<PhenotypicFeature, _> = PhenotypicFeature::builder().mild().try_build();
+        assert_eq!(feature, Err(BuildError::MissingField("type")));
+
+        let evidence: Result<Evidence, _> = Evidence::builder().try_build();
+        assert_eq!(evidence, Err(BuildError::MissingField("evidence_code")));
+
+        let biosample: Result<Biosample, _> = Biosample::builder().try_build();
+        assert_eq!(biosample, Err(BuildError::MissingField("id")));
+
+        let meta_data: Result<MetaData, _> = MetaData::builder().v2().try_build();
+        assert_eq!(meta_data, Err(BuildError::MissingField("created")));
+
+        let measurement: Result<Measurement, _> = Measurement::builder().try_build();
+        assert_eq!(measurement, Err(BuildError::MissingField("assay")));
+
+        let file: Result<File, _> = File::builder().try_build();
+        assert_eq!(file, Err(BuildError::MissingField("uri")));
+
+        let medical_action: Result<MedicalAction, _> = MedicalAction::builder().try_build();
+        assert_eq!(medical_action, Err(BuildError::MissingField("action")));
+    }
+
+    #[test]
+    fn build_a_dynamic_ontology_class() {
+        let seizure = OntologyClass::builder_dyn()
+            .id("HP:0001250")
+            .label("Seizure")
+            .build();
+
+        assert_eq!(seizure, Ok(oc("HP:0001250", "Seizure")));
+    }
+
+    #[test]
+    fn build_a_dynamic_ontology_class_without_id() {
+        let result = OntologyClass::builder_dyn().label("Seizure").build();
+
+        assert_eq!(
+            result,
+            Err(BuildError::MissingFields {
+                type_name: "OntologyClass",
+                fields: vec!["id"],
+            })
+        );
+    }
+
+    #[test]
+    fn build_a_dynamic_ontology_class_without_fields() {
+        let error = OntologyClass::builder_dyn().build().unwrap_err();
+
+        assert_eq!(error.to_string(), "OntologyClass is missing id, label");
+    }
+
+    #[test]
+    fn try_build_a_complete_ontology_class() {
+        assert_eq!(
+            OntologyClass::builder()
+                .id_label("HP:0001250", "Seizure")
+                .try_build(),
+            Ok(oc("HP:0001250", "Seizure"))
+        );
+    }
+
+    #[test]
+    fn build_ontology_classes_from_local_ids() {
+        let seizure: OntologyClass = OntologyClass::builder().hp("0001250", "Seizure").build();
+        let marfan: OntologyClass = OntologyClass::builder()
+            .mondo("0007947", "Marfan syndrome")
+            .build();
+        let pancoast_tumor: OntologyClass = OntologyClass::builder()
+            .ncit("C7377", "Pancoast Tumor")
+            .build();
+
+        assert_eq!(seizure, oc("HP:0001250", "Seizure"));
+        assert_eq!(marfan, oc("MONDO:0007947", "Marfan syndrome"));
+        assert_eq!(pancoast_tumor, oc("NCIT:C7377", "Pancoast Tumor"));
+    }
+
+    #[test]
+    fn deterministic_phenopackets_encode_into_identical_bytes() {
+        let seizure = || PhenotypicFeature::builder().r#type(oc("HP:0001250", "Seizure"));
+        let delay =
+            || PhenotypicFeature::builder().r#type(oc("HP:0001263", "Global developmental delay"));
+        let meta_data = |nanos| {
+            MetaData::builder()
+                .created(Timestamp::builder().seconds_nanos(1_000_000, nanos))
+                .created_by("Peter R.")
+                .v2()
+        };
+
+        let first: Phenopacket = Phenopacket::builder()
+            .id("pp-id")
+            .add_phenotypic_feature(seizure())
+            .add_phenotypic_feature(delay())
+            .add_biosample(Biosample::builder().id("sample-2"))
+            .add_biosample(Biosample::builder().id("sample-1"))
+            .meta_data(meta_data(123_000_000))
+            .deterministic()
+            .build();
+        let second: Phenopacket = Phenopacket::builder()
+            .deterministic()
+            .id("pp-id")
+            .add_biosample(Biosample::builder().id("sample-1"))
+            .add_phenotypic_feature(delay())
+            .add_biosample(Biosample::builder().id("sample-2"))
+            .add_phenotypic_feature(seizure())
+            .meta_data(meta_data(456_000_000))
+            .build();
+
+        assert_eq!(first.encode_to_vec(), second.encode_to_vec());
+        assert_eq!(first.meta_data.unwrap().created.unwrap().nanos, 0);
+    }
+
+    #[test]
+    fn deterministic_phenopackets_order_files_with_the_same_uri() {
+        let file = |genome_assembly| {
+            File::builder()
+                .uri("file:///data/genomes/proband.vcf.gz")
+                .add_file_attribute("fileFormat", "vcf")
+                .add_file_attribute("genomeAssembly", genome_assembly)
+                .add_file_attribute("description", "proband genome")
+        };
+        let phenopacket = |first, second| -> Phenopacket {
+            Phenopacket::builder()
+                .id("pp-id")
+                .add_file(file(first))
+                .add_file(file(second))
+                .add_biosample(Biosample::builder().id("sample-1").add_file(file(first)))
+                .add_biosample(Biosample::builder().id("sample-1").add_file(file(second)))
+                .meta_data(
+                    MetaData::builder()
+                        .created(Timestamp::builder().seconds_nanos(1_000_000, 0))
+                        .created_by("Peter R."),
+                )
+                .deterministic()
+                .build()
+        };
+
+        let first = phenopacket("GRCh38", "GRCh37");
+        let second = phenopacket("GRCh37", "GRCh38");
+
+        assert_eq!(first, second);
+        assert_eq!(
+            to_deterministic_bytes(&first),
+            to_deterministic_bytes(&second)
+        );
+        assert_eq!(&first.files[0].file_attributes["genomeAssembly"], "GRCh37");
+        assert_eq!(
+            &first.biosamples[0].files[0].file_attributes["genomeAssembly"],
+            "GRCh37"
+        );
+    }
+
+    #[test]
+    fn build_a_vcf_file() {
+        let file: File = File::builder()
+            .uri("file:///data/genomes/proband.vcf.gz")
+            .add_file_attribute("fileFormat", "vcf")
+            .add_file_attribute("genomeAssembly", "GRCh38")
+            .add_individual_file_identifier("proband A", "NA12345")
+            .build();
+
+        assert_eq!(&file.uri, "file:///data/genomes/proband.vcf.gz");
+        assert_eq!(
+            file.file_attributes,
+            HashMap::from([
+                ("fileFormat".to_string(), "vcf".to_string()),
+                ("genomeAssembly".to_string(), "GRCh38".to_string()),
+            ])
+        );
+        assert_eq!(
+            file.individual_to_file_identifiers,
+            HashMap::from([("proband A".to_string(), "NA12345".to_string())])
+        );
+    }
+
+    #[test]
+    fn add_cause_of_death_to_a_vital_status() {
+        let vital_status = VitalStatus {
+            status: Status::Deceased.into(),
+            time_of_death: Some(TimeElement::builder().age_iso8601duration("P64Y").build()),
+            survival_time_in_days: 212,
+            ..Default::default()
+        };
+
+        let edited: VitalStatus = VitalStatusBuilder::from_vital_status(vital_status.clone())
+            .cause_of_death(oc("MONDO:0005233", "non-small cell lung carcinoma"))
+            .build();
+
+        assert_eq!(edited.status, vital_status.status);
+        assert_eq!(edited.time_of_death, vital_status.time_of_death);
+        assert_eq!(edited.survival_time_in_days, 212);
+        assert_eq!(
+            edited.cause_of_death,
+            Some(oc("MONDO:0005233", "non-small cell lung carcinoma"))
+        );
+    }
+
+    #[test]
+    fn build_a_gene_level_genomic_interpretation() {
+        let gi: GenomicInterpretation = GenomicInterpretation::builder()
+            .subject_or_biosample_id("subject-id")
+            .candidate()
+            .gene_descriptor(GeneDescriptor::builder().hgnc("3603", "FBN1"))
+            .build();
+
+        assert_eq!(&gi.subject_or_biosample_id, "subject-id");
+        assert_eq!(gi.interpretation_status(), InterpretationStatus::Candidate);
+        match gi.call {
+            Some(Call::Gene(gene)) => assert_eq!(&gene.symbol, "FBN1"),
+            _ => panic!("expected a gene call"),
+        }
+    }
+
+    #[test]
+    fn build_a_variant_level_genomic_interpretation() {
+        let variant_interpretation = VariantInterpretation {
+            acmg_pathogenicity_classification: AcmgPathogenicityClassification::Pathogenic.into(),
+            ..Default::default()
+        };
+        let gi: GenomicInterpretation = GenomicInterpretation::builder()
+            .causative()
+            .gene_descriptor(GeneDescriptor::builder().hgnc("3603", "FBN1"))
+            .variant_interpretation(variant_interpretation.clone())
+            .subject_or_biosample_id("subject-id")
+            .build();
+
+        assert_eq!(gi.interpretation_status(), InterpretationStatus::Causative);
+        assert_eq!(
+            gi.call,
+            Some(Call::VariantInterpretation(variant_interpretation))
+        );
+    }
+
+    #[test]
+    fn mark_an_interpretation_as_solved() {
+        let interpretation: Interpretation = Interpretation::builder()
+            .id("interpretation-id")
+            .in_progress()
+            .diagnosis(Diagnosis {
+                disease: Some(oc("MONDO:0007947", "Marfan syndrome")),
+                ..Default::default()
+            })
+            .build();
+
+        let solved: Interpretation = InterpretationBuilder::from_interpretation(interpretation)
+            .set_status(ProgressStatus::Solved)
+            .add_genomic_interpretation(
+                GenomicInterpretation::builder()
+                    .subject_or_biosample_id("subject-id")
+                    .causative()
+                    .gene_descriptor(GeneDescriptor::builder().hgnc("3603", "FBN1")),
+            )
+            .build();
+
+        assert_eq!(&solved.id, "interpretation-id");
+        assert_eq!(solved.progress_status(), ProgressStatus::Solved);
+        let diagnosis = solved.diagnosis.unwrap();
+        assert_eq!(
+            diagnosis.disease,
+            Some(oc("MONDO:0007947", "Marfan syndrome"))
+        );
+        assert_eq!(diagnosis.genomic_interpretations.len(), 1);
+    }
+
+    #[test]
+    fn edit_a_disease() {
+        let disease = Disease {
+            term: Some(oc("MONDO:0005233", "non-small cell lung carcinoma")),
+            excluded: false,
+            onset: Some(TimeElement::builder().age_iso8601duration("P62Y").build()),
+            ..Default::default()
+        };
+
+        let edited: Disease = DiseaseBuilder::from_disease(disease.clone())
+            .expect("the disease has a term")
+            .add_clinical_tnm_finding(oc("NCIT:C48724", "T2b Stage Finding"))
+            .build();
+
+        assert_eq!(edited.term, disease.term);
+        assert_eq!(edited.onset, disease.onset);
+        assert_eq!(
+            edited.clinical_tnm_finding,
+            vec![oc("NCIT:C48724", "T2b Stage Finding")]
+        );
+    }
+
+    #[test]
+    fn edit_a_disease_without_term() {
+        let result = DiseaseBuilder::from_disease(Disease::default());
+
+        assert_eq!(result, Err(BuildError::MissingField("term")));
+    }
+
+    #[test]
+    fn build_a_gene_descriptor() {
+        let gene: GeneDescriptor = GeneDescriptor::builder()
+            .hgnc("2211", "COL6A1")
+            .add_alternate_id("ensembl:ENSG00000142156")
+            .add_alternate_id("ncbigene:1291")
+            .build();
+
+        assert_eq!(&gene.value_id, "HGNC:2211");
+        assert_eq!(&gene.symbol, "COL6A1");
+        assert_eq!(
+            &gene.alternate_ids,
+            &["ensembl:ENSG00000142156", "ncbigene:1291"]
+        );
+    }
+
+    #[test]
+    fn build_an_ontology_class() {
+        let oc: OntologyClass = OntologyClass::builder()
+            .id_label("HP:0001250", "Seizure")
+            .build();
+
+        assert_eq!(&oc.id, "HP:0001250");
+        assert_eq!(&oc.label, "Seizure");
+    }
+
+    #[test]
+    fn build_a_checked_ontology_class() {
+        let oc: OntologyClass = OntologyClass::builder()
+            .try_id_label("HP:0001250", "Seizure")
+            .expect("the label should not be blank")
+            .build();
+
+        assert_eq!(&oc.id, "HP:0001250");
+        assert_eq!(&oc.label, "Seizure");
+    }
+
+    #[test]
+    fn checked_ontology_class_rejects_blank_label() {
+        let result = OntologyClass::builder().try_id_label("HP:0001250", " \t ");
+
+        assert_eq!(result, Err(CurieError::BlankLabel));
+    }
+
+    #[test]
+    fn checked_ontology_class_rejects_id_without_colon() {
+        let result = OntologyClass::builder().try_id_label("Seizure", "Seizure");
+
+        assert_eq!(result, Err(CurieError::MissingColon("Seizure".into())));
+    }
+
+    #[test]
+    fn checked_ontology_class_rejects_empty_prefix() {
+        let result = OntologyClass::builder().try_id_label(":0001250", "Seizure");
+
+        assert_eq!(result, Err(CurieError::EmptyPrefix(":0001250".into())));
+    }
+
+    #[test]
+    fn checked_ontology_class_rejects_empty_local_part() {
+        let result = OntologyClass::builder().try_id_label("HP:", "Seizure");
+
+        assert_eq!(result, Err(CurieError::EmptyLocalPart("HP:".into())));
+    }
+
+    #[test]
+    fn checked_ontology_class_agrees_with_try_build() {
+        let result = OntologyClass::builder().try_id_label("1HP:00 01", "x");
+        assert_eq!(result, Err(CurieError::Malformed("1HP:00 01".into())));
+
+        let built: Result<OntologyClass, _> = OntologyClass::builder()
+            .id_label("1HP:00 01", "x")
+            .try_build();
+        assert_eq!(built, Err(BuildError::MalformedCurie("1HP:00 01".into())));
+    }
+
+    #[test]
+    fn subject_with_id_only() {
+        let phenopacket: Phenopacket = minimal_phenopacket().subject_id("x").build();
+
+        assert_eq!(
+            phenopacket.subject,
+            Some(Individual {
+                id: "x".into(),
+                ..Default::default()
+            })
+        );
+    }
+
+    /// A phenopacket builder with the required fields set.
+    fn minimal_phenopacket() -> PhenopacketBuilder<Set, Set> {
+        Phenopacket::builder().id("pp-id").meta_data(
+            MetaData::builder()
+                .created(Timestamp::builder().seconds_nanos(0, 0))
+                .created_by("Peter R.")
+                .v2(),
+        )
+    }
+
+    #[test]
+    fn edit_a_phenopacket_in_its_builder() {
+        let phenopacket = Phenopacket {
+            id: "pp-id".into(),
+            meta_data: Some(MetaData::default()),
+            diseases: vec![Disease {
+                term: Some(oc("MONDO:0007915", "Lupus")),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let phenopacket: Phenopacket = PhenopacketBuilder::try_from(phenopacket)
+            .unwrap()
+            .add_disease(Disease::builder().term(oc("MONDO:0005044", "hypertensive disorder")))
+            .build();
+
+        assert_eq!(&phenopacket.id, "pp-id");
+        assert_eq!(phenopacket.diseases.len(), 2);
+    }
+
+    #[test]
+    fn edit_a_phenopacket_without_meta_data() {
+        let phenopacket = Phenopacket {
+            id: "pp-id".into(),
+            ..Default::default()
+        };
+
+        let result = PhenopacketBuilder::try_from(phenopacket);
+
+        assert_eq!(result, Err(BuildError::MissingField("meta_data")));
+    }
+
+    #[test]
+    fn edit_an_individual_in_its_builder() {
+        let individual: Individual = Individual::builder().id("subject-id").female().build();
+
+        let individual: Individual = IndividualBuilder::from(individual)
+            .add_alternate_id("alternate-id")
+            .build();
+
+        assert_eq!(&individual.id, "subject-id");
+        assert_eq!(individual.sex, Sex::Female as i32);
+        assert_eq!(individual.alternate_ids, vec!["alternate-id"]);
+    }
+
+    #[test]
+    fn edit_incomplete_meta_data() {
+        let result = MetaDataBuilder::from_meta_data(MetaData::default());
+        assert_eq!(result, Err(BuildError::MissingField("created")));
+
+        let result = MetaDataBuilder::from_meta_data(MetaData {
+            created: Some(Timestamp::default()),
+            ..Default::default()
+        });
+        assert_eq!(result, Err(BuildError::MissingField("created_by")));
+    }
+
+    #[test]
+    fn edit_meta_data_in_its_builder() {
+        let meta_data: MetaData = MetaData::builder()
+            .created(Timestamp::builder().seconds_nanos(0, 0))
+            .created_by("Peter R.")
+            .add_resource(Resource::builder().hpo("2024-08-13"))
+            .v2()
+            .build();
+
+        let meta_data: MetaData = MetaDataBuilder::try_from(meta_data)
+            .expect("the metadata is complete")
+            .add_resource(Resource::builder().mondo("2024-06-04"))
+            .build();
+
+        let prefixes: Vec<_> = meta_data
+            .resources
+            .iter()
+            .map(|r| r.namespace_prefix.as_str())
+            .collect();
+        assert_eq!(prefixes, vec!["HP", "MONDO"]);
+    }
+
+    #[test]
+    fn edit_meta_data_without_schema_version() {
+        let meta_data = MetaData {
+            created: Some(Timestamp::default()),
+            created_by: "Peter R.".into(),
+            ..Default::default()
+        };
+
+        let meta_data: MetaData = MetaDataBuilder::from_meta_data(meta_data)
+            .expect("the metadata is complete")
+            .build();
+
+        assert_eq!(&meta_data.phenopacket_schema_version, "2.0.0");
+    }
+
+    #[test]
+    fn rebuild_an_ontology_class() {
+        let seizure = oc("HP:0001250", "Seizure");
+
+        let rebuilt: OntologyClass = OntologyClassBuilder::from(seizure.clone()).build();
+
+        assert_eq!(rebuilt, seizure);
+    }
+
+    #[test]
+    fn set_replaces_and_extend_appends() {
+        let seizure = || PhenotypicFeature::builder().r#type(oc("HP:0001250", "Seizure"));
+        let ataxia = || PhenotypicFeature::builder().r#type(oc("HP:0001251", "Ataxia"));
+
+        let extended: Phenopacket = minimal_phenopacket()
+            .add_phenotypic_feature(seizure())
+            .extend_phenotypic_features([ataxia()])
+            .build();
+        let replaced: Phenopacket = minimal_phenopacket()
+            .add_phenotypic_feature(seizure())
+            .set_phenotypic_features([ataxia()])
+            .build();
+
+        assert_eq!(extended.phenotypic_features.len(), 2);
+        assert_eq!(
+            replaced.phenotypic_features,
+            vec![PhenotypicFeature {
+                r#type: Some(oc("HP:0001251", "Ataxia")),
+                ..Default::default()
+            }]
+        );
+    }
+
+    #[test]
+    fn severe_phenotypic_feature() {
+        let feature: PhenotypicFeature = PhenotypicFeature::builder()
+            .r#type(oc("HP:0001250", "Seizure"))
+            .severe()
+            .build();
+
+        assert_eq!(feature.severity, Some(oc("HP:0012828", "Severe")));
+    }
+
+    #[test]
+    fn congenital_onset_of_a_phenotypic_feature() {
+        let feature: PhenotypicFeature = PhenotypicFeature::builder()
+            .r#type(oc("HP:0001166", "Arachnodactyly"))
+            .onset_congenital()
+            .build();
+
+        assert_eq!(
+            feature.onset.and_then(|onset| onset.element),
+            Some(Element::OntologyClass(oc("HP:0003577", "Congenital onset")))
+        );
+    }
+
+    #[test]
+    fn onset_presets_of_a_disease() {
+        let onset_id = |builder: DiseaseBuilder<Set>| {
+            let disease: Disease = builder.build();
+            match disease.onset.and_then(|onset| onset.element) {
+                Some(Element::OntologyClass(oc)) => oc.id,
+                other => panic!("expected an ontology class onset, got {other:?}"),
+            }
+        };
+        let lupus = || Disease::builder().term(oc("MONDO:0007915", "Lupus"));
+
+        assert_eq!(onset_id(lupus().onset_congenital()), "HP:0003577");
+        assert_eq!(onset_id(lupus().onset_neonatal()), "HP:0003623");
+        assert_eq!(onset_id(lupus().onset_infantile()), "HP:0003593");
+        assert_eq!(onset_id(lupus().onset_childhood()), "HP:0011463");
+        assert_eq!(onset_id(lupus().onset_adult()), "HP:0003581");
+    }
+
+    #[test]
+    fn dedup_modifiers_by_id() {
+        let feature: PhenotypicFeature = PhenotypicFeature::builder()
+            .r#type(oc("HP:0001250", "Seizure"))
+            .add_modifier(oc("HP:0031796", "Recurrent"))
+            .add_modifier(oc("HP:0012832", "Bilateral"))
+            .add_modifier(oc("HP:0031796", "Recurrent"))
+            .add_modifier(oc("HP:0031796", "recurrent"))
+            .dedup_modifiers()
+            .build();
+
+        assert_eq!(
+            feature.modifiers,
+            vec![oc("HP:0031796", "Recurrent"), oc("HP:0012832", "Bilateral")]
+        );
+    }
+
+    #[test]
+    fn dedup_phenotypic_features_by_type_id() {
+        let phenopacket: Phenopacket = minimal_phenopacket()
+            .add_phenotypic_feature(observed_feature("HP:0001250", "Seizure"))
+            .add_phenotypic_feature(observed_feature("HP:0001251", "Ataxia"))
+            .add_phenotypic_feature(observed_feature("HP:0001250", "Seizure"))
+            .add_phenotypic_feature(excluded_feature("HP:0001250", "Seizures"))
+            .dedup_phenotypic_features()
+            .build();
+
+        assert_eq!(
+            phenopacket.phenotypic_features,
+            vec![
+                observed_feature("HP:0001250", "Seizure"),
+                observed_feature("HP:0001251", "Ataxia"),
+            ]
+        );
+    }
+
+    #[test]
+    fn severity_presets() {
+        let severity = |builder: PhenotypicFeatureBuilder<Set>| {
+            let feature: PhenotypicFeature = builder.build();
+            feature.severity.map(|s| s.id)
+        };
+        let seizure = || PhenotypicFeature::builder().r#type(oc("HP:0001250", "Seizure"));
+
+        assert_eq!(
+            severity(seizure().borderline()).as_deref(),
+            Some("HP:0012827")
+        );
+        assert_eq!(severity(seizure().mild()).as_deref(), Some("HP:0012825"));
+        assert_eq!(
+            severity(seizure().moderate()).as_deref(),
+            Some("HP:0012826")
+        );
+        assert_eq!(
+            severity(seizure().profound()).as_deref(),
+            Some("HP:0012829")
+        );
+    }
+
+    #[test]
+    fn set_alternate_ids() {
+        let individual: Individual = Individual::builder()
+            .id("subject-id")
+            .add_alternate_id("old-id")
+            .set_alternate_ids(["new-id", "other-id"])
+            .build();
+
+        assert_eq!(individual.alternate_ids, vec!["new-id", "other-id"]);
+    }
+
+    #[test]
+    fn meta_data_defaults_to_schema_version_2_0_0() {
+        let meta_data: MetaData = MetaData::builder()
+            .created(Timestamp::builder().seconds_nanos(0, 0))
+            .created_by("Peter R.")
+            .build();
+
+        assert_eq!(&meta_data.phenopacket_schema_version, "2.0.0");
+    }
+
+    #[test]
+    fn meta_data_schema_version_can_be_overridden() {
+        let meta_data: MetaData = MetaData::builder()
+            .v2_0_2()
+            .created(Timestamp::builder().seconds_nanos(0, 0))
+            .created_by("Peter R.")
+            .build();
+
+        assert_eq!(&meta_data.phenopacket_schema_version, "2.0.2");
+    }
+
+    #[test]
+    fn set_meta_data_collections() {
+        let meta_data: MetaData = MetaData::builder()
+            .created(Timestamp::builder().seconds_nanos(0, 0))
+            .created_by("Peter R.")
+            .add_resource(Resource::builder().hpo("2024-08-13"))
+            .set_resources([Resource::builder().mondo("2024-06-04")])
+            .add_update(Update::default())
+            .set_updates(Vec::<Update>::new())
+            .add_external_reference(ExternalReference::default())
+            .set_external_references([ExternalReference {
+                id: "PMID:30808312".into(),
+                ..Default::default()
+            }])
+            .v2()
+            .build();
+
+        let resources: Vec<_> = meta_data
+            .resources
+            .iter()
+            .map(|r| r.namespace_prefix.as_str())
+            .collect();
+        assert_eq!(resources, vec!["MONDO"]);
+        assert!(meta_data.updates.is_empty());
+        assert_eq!(meta_data.external_references.len(), 1);
+        assert_eq!(&meta_data.external_references[0].id, "PMID:30808312");
     }
 
     #[test]
@@ -0,0 +1,514 @@
+use phenopacket_builder::validate::{
+    validate, validate_with, Severity, ValidationIssue, ValidationProfile,
+};
+use phenopacket_builder::{oc, Build, Buildable};
+use phenopackets::schema::v2::core::medical_action::Action;
+use phenopackets::schema::v2::core::time_element::Element;
+use phenopackets::schema::v2::core::{
+    Age, AgeRange, Biosample, Diagnosis, Disease, DoseInterval, Evidence, ExternalReference, File,
+    GenomicInterpretation, Individual, Interpretation, MedicalAction, MetaData, OntologyClass,
+    PhenotypicFeature, Resource, TimeElement, TimeInterval, Treatment,
+};
+use phenopackets::schema::v2::Phenopacket;
+use prost_types::Timestamp;
+
+fn meta_data() -> MetaData {
+    MetaData::builder()
+        .created(Timestamp::builder().seconds_nanos(0, 0))
+        .created_by("Peter R.")
+        .add_resource(Resource::builder().hpo("2024-08-13"))
+        .add_resource(Resource::builder().mondo("2024-06-04"))
+        .v2()
+        .build()
+}
+
+fn diagnosed_with(disease_id: &str, disease_label: &str) -> Interpretation {
+    Interpretation {
+        id: "interpretation-id".into(),
+        diagnosis: Some(Diagnosis {
+            disease: Some(oc(disease_id, disease_label)),
+            genomic_interpretations: vec![],
+        }),
+        ..Default::default()
+    }
+}
+
+fn interpretations_reference_diseases() -> ValidationProfile {
+    ValidationProfile {
+        interpretations_reference_diseases: true,
+        ..Default::default()
+    }
+}
+
+fn features_require_subject() -> ValidationProfile {
+    ValidationProfile {
+        features_require_subject: true,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn diagnosis_disease_among_phenopacket_diseases() {
+    let phenopacket = Phenopacket {
+        interpretations: vec![diagnosed_with("MONDO:0007915", "Lupus")],
+        diseases: vec![Disease {
+            term: Some(oc("MONDO:0007915", "Lupus")),
+            ..Default::default()
+        }],
+        meta_data: Some(meta_data()),
+        ..Default::default()
+    };
+
+    assert!(validate_with(&phenopacket, &interpretations_reference_diseases()).is_ok());
+}
+
+#[test]
+fn orphan_diagnosis_disease() {
+    let phenopacket = Phenopacket {
+        interpretations: vec![diagnosed_with("MONDO:0007915", "Lupus")],
+        meta_data: Some(meta_data()),
+        ..Default::default()
+    };
+
+    let issues = validate_with(&phenopacket, &interpretations_reference_diseases())
+        .expect_err("the diagnosis disease should be reported");
+
+    assert_eq!(
+        issues,
+        vec![ValidationIssue::OrphanDiagnosisDisease {
+            path: "interpretations[0].diagnosis.disease".into(),
+            id: "MONDO:0007915".into(),
+        }]
+    );
+    assert_eq!(issues[0].severity(), Severity::Warning);
+}
+
+#[test]
+fn term_with_unknown_label() {
+    let phenopacket = Phenopacket {
+        phenotypic_features: vec![
+            PhenotypicFeature::builder()
+                .r#type(oc("HP:0001250", "Seizure"))
+                .build(),
+            PhenotypicFeature::builder()
+                .r#type(OntologyClass::builder().unknown("HP:0012469"))
+                .build(),
+        ],
+        meta_data: Some(meta_data()),
+        ..Default::default()
+    };
+
+    let issues = validate(&phenopacket).expect_err("the empty label should be reported");
+
+    assert_eq!(
+        issues,
+        vec![ValidationIssue::EmptyTermLabel {
+            path: "phenotypic_features[1].type".into(),
+            id: "HP:0012469".into(),
+        }]
+    );
+}
+
+#[test]
+fn build_or_panic_a_valid_phenopacket() {
+    let phenopacket = Phenopacket::builder()
+        .id("phenopacket-id")
+        .subject(Individual::builder().id("subject-id"))
+        .add_phenotypic_feature(PhenotypicFeature::builder().r#type(oc("HP:0001250", "Seizure")))
+        .meta_data(meta_data())
+        .build_or_panic();
+
+    assert_eq!(&phenopacket.id, "phenopacket-id");
+}
+
+#[test]
+#[should_panic(expected = "phenotypic_features[0].type: term HP:0001250 has an empty label")]
+fn build_or_panic_an_invalid_phenopacket() {
+    Phenopacket::builder()
+        .id("phenopacket-id")
+        .add_phenotypic_feature(
+            PhenotypicFeature::builder().r#type(OntologyClass::builder().unknown("HP:0001250")),
+        )
+        .meta_data(meta_data())
+        .build_or_panic();
+}
+
+#[test]
+fn build_checked_curies_strict_a_clean_phenopacket() {
+    let phenopacket = Phenopacket::builder()
+        .id("phenopacket-id")
+        .add_phenotypic_feature(PhenotypicFeature::builder().r#type(oc("HP:0001250", "Seizure")))
+        .meta_data(meta_data())
+        .build_checked_curies_strict()
+        .expect("all ids are CURIEs");
+
+    assert_eq!(&phenopacket.id, "phenopacket-id");
+}
+
+#[test]
+fn build_checked_curies_strict_a_malformed_id() {
+    let result = Phenopacket::builder()
+        .id("phenopacket-id")
+        .add_phenotypic_feature(PhenotypicFeature::builder().r#type(oc("HP:0001250", "Seizure")))
+        .add_phenotypic_feature(
+            PhenotypicFeature::builder().r#type(oc("HP_0001263", "Global developmental delay")),
+        )
+        .meta_data(meta_data())
+        .build_checked_curies_strict();
+
+    assert_eq!(result, Err(vec!["HP_0001263".to_string()]));
+}
+
+#[test]
+fn contradictory_features() {
+    let phenopacket = Phenopacket {
+        phenotypic_features: vec![
+            PhenotypicFeature::builder()
+                .r#type(oc("HP:0001250", "Seizure"))
+                .build(),
+            PhenotypicFeature::builder()
+                .r#type(oc("HP:0001250", "Seizure"))
+                .excluded()
+                .build(),
+        ],
+        meta_data: Some(meta_data()),
+        ..Default::default()
+    };
+
+    let issues = validate(&phenopacket).expect_err("the contradiction should be reported");
+
+    assert_eq!(
+        issues,
+        vec![ValidationIssue::ContradictoryFeature {
+            path: "phenotypic_features[1]".into(),
+            id: "HP:0001250".into(),
+        }]
+    );
+    assert_eq!(issues[0].severity(), Severity::Error);
+}
+
+#[test]
+fn duplicate_features() {
+    let seizure: PhenotypicFeature = PhenotypicFeature::builder()
+        .r#type(oc("HP:0001250", "Seizure"))
+        .excluded()
+        .build();
+    let phenopacket = Phenopacket {
+        phenotypic_features: vec![seizure.clone(), seizure],
+        meta_data: Some(meta_data()),
+        ..Default::default()
+    };
+
+    let issues = validate(&phenopacket).expect_err("the duplicate should be reported");
+
+    assert_eq!(
+        issues,
+        vec![ValidationIssue::DuplicateFeature {
+            path: "phenotypic_features[1]".into(),
+            id: "HP:0001250".into(),
+        }]
+    );
+    assert_eq!(issues[0].severity(), Severity::Warning);
+}
+
+fn onset(element: Element) -> Phenopacket {
+    Phenopacket {
+        phenotypic_features: vec![PhenotypicFeature::builder()
+            .r#type(oc("HP:0001250", "Seizure"))
+            .onset(TimeElement {
+                element: Some(element),
+            })
+            .build()],
+        meta_data: Some(meta_data()),
+        ..Default::default()
+    }
+}
+
+fn interval(start: &str, end: &str) -> Element {
+    let timestamp = |value: &str| -> Timestamp {
+        Timestamp::builder()
+            .iso8601timestamp(value)
+            .expect("the timestamp should be well formatted")
+            .build()
+    };
+    Element::Interval(TimeInterval {
+        start: Some(timestamp(start)),
+        end: Some(timestamp(end)),
+    })
+}
+
+fn age_range(start: &str, end: &str) -> Element {
+    Element::AgeRange(AgeRange {
+        start: Some(Age::builder().iso8601duration(start).build()),
+        end: Some(Age::builder().iso8601duration(end).build()),
+    })
+}
+
+#[test]
+fn valid_interval_and_age_range() {
+    assert!(validate(&onset(interval("2020-01-01", "2020-06-01"))).is_ok());
+    assert!(validate(&onset(age_range("P11M", "P1Y"))).is_ok());
+}
+
+#[test]
+fn inverted_interval() {
+    let issues = validate(&onset(interval("2020-06-01", "2020-01-01")))
+        .expect_err("the inverted interval should be reported");
+
+    assert_eq!(
+        issues,
+        vec![ValidationIssue::StartAfterEnd {
+            path: "phenotypic_features[0].onset".into()
+        }]
+    );
+}
+
+#[test]
+fn inverted_age_range() {
+    let issues = validate(&onset(age_range("P1Y", "P6M")))
+        .expect_err("the inverted age range should be reported");
+
+    assert_eq!(
+        issues,
+        vec![ValidationIssue::StartAfterEnd {
+            path: "phenotypic_features[0].onset".into()
+        }]
+    );
+    assert_eq!(issues[0].severity(), Severity::Error);
+}
+
+#[test]
+fn inverted_dose_interval() {
+    let Element::Interval(inverted) = interval("2020-06-01", "2020-01-01") else {
+        unreachable!()
+    };
+    let phenopacket = Phenopacket {
+        medical_actions: vec![MedicalAction {
+            action: Some(Action::Treatment(Treatment {
+                dose_intervals: vec![DoseInterval {
+                    interval: Some(inverted),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            })),
+            ..Default::default()
+        }],
+        meta_data: Some(meta_data()),
+        ..Default::default()
+    };
+
+    let issues = validate(&phenopacket).expect_err("the inverted dose interval should be reported");
+
+    assert_eq!(
+        issues,
+        vec![ValidationIssue::StartAfterEnd {
+            path: "medical_actions[0].treatment.dose_intervals[0].interval".into()
+        }]
+    );
+}
+
+fn with_file(uri: &str) -> Phenopacket {
+    Phenopacket {
+        files: vec![File {
+            uri: uri.into(),
+            ..Default::default()
+        }],
+        ..Default::default()
+    }
+}
+
+#[test]
+fn file_with_scheme() {
+    assert!(validate(&with_file("file:///data/genomes/proband.vcf.gz")).is_ok());
+}
+
+#[test]
+fn file_with_empty_uri() {
+    let issues = validate(&with_file("")).expect_err("the empty uri should be reported");
+
+    assert_eq!(
+        issues,
+        vec![ValidationIssue::EmptyFileUri {
+            path: "files[0]".into()
+        }]
+    );
+    assert_eq!(issues[0].severity(), Severity::Error);
+}
+
+#[test]
+fn file_with_bare_path() {
+    let issues = validate(&with_file("/data/genomes/proband.vcf.gz"))
+        .expect_err("the bare path should be reported");
+
+    assert_eq!(
+        issues,
+        vec![ValidationIssue::FileUriWithoutScheme {
+            path: "files[0]".into(),
+            uri: "/data/genomes/proband.vcf.gz".into(),
+        }]
+    );
+    assert_eq!(issues[0].severity(), Severity::Warning);
+}
+
+fn seizure() -> PhenotypicFeature {
+    PhenotypicFeature::builder()
+        .r#type(oc("HP:0001250", "Seizure"))
+        .build()
+}
+
+#[test]
+fn features_with_subject() {
+    let phenopacket = Phenopacket {
+        subject: Some(Individual::builder().id("subject-id").build()),
+        phenotypic_features: vec![seizure()],
+        meta_data: Some(meta_data()),
+        ..Default::default()
+    };
+
+    assert!(validate_with(&phenopacket, &features_require_subject()).is_ok());
+}
+
+#[test]
+fn features_without_subject() {
+    let phenopacket = Phenopacket {
+        phenotypic_features: vec![seizure()],
+        meta_data: Some(meta_data()),
+        ..Default::default()
+    };
+
+    let issues = validate_with(&phenopacket, &features_require_subject())
+        .expect_err("the missing subject should be reported");
+
+    assert_eq!(
+        issues,
+        vec![ValidationIssue::FeaturesWithoutSubject {
+            path: "subject".into()
+        }]
+    );
+    assert_eq!(issues[0].severity(), Severity::Warning);
+}
+
+fn genomic_interpretation_of(subject_or_biosample_id: &str) -> Interpretation {
+    Interpretation {
+        id: "interpretation-id".into(),
+        diagnosis: Some(Diagnosis {
+            disease: None,
+            genomic_interpretations: vec![GenomicInterpretation {
+                subject_or_biosample_id: subject_or_biosample_id.into(),
+                ..Default::default()
+            }],
+        }),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn references_to_subject_and_biosample() {
+    let phenopacket = Phenopacket {
+        subject: Some(Individual::builder().id("subject-id").build()),
+        biosamples: vec![Biosample {
+            id: "biosample-id".into(),
+            individual_id: "subject-id".into(),
+            ..Default::default()
+        }],
+        interpretations: vec![
+            genomic_interpretation_of("subject-id"),
+            genomic_interpretation_of("biosample-id"),
+        ],
+        ..Default::default()
+    };
+
+    assert!(validate(&phenopacket).is_ok());
+}
+
+#[test]
+fn dangling_biosample_reference() {
+    let phenopacket = Phenopacket {
+        subject: Some(Individual::builder().id("subject-id").build()),
+        biosamples: vec![Biosample {
+            id: "biosample-id".into(),
+            individual_id: "other-id".into(),
+            ..Default::default()
+        }],
+        interpretations: vec![genomic_interpretation_of("unknown-biosample-id")],
+        ..Default::default()
+    };
+
+    let issues = validate(&phenopacket).expect_err("the dangling references should be reported");
+
+    assert_eq!(
+        issues,
+        vec![
+            ValidationIssue::DanglingReference {
+                path: "biosamples[0].individual_id".into(),
+                id: "other-id".into(),
+            },
+            ValidationIssue::DanglingReference {
+                path: "interpretations[0].diagnosis.genomic_interpretations[0].subject_or_biosample_id"
+                    .into(),
+                id: "unknown-biosample-id".into(),
+            },
+        ]
+    );
+    assert_eq!(issues[0].severity(), Severity::Error);
+}
+
+#[test]
+fn curie_with_declared_resource() {
+    let phenopacket = Phenopacket {
+        phenotypic_features: vec![seizure()],
+        meta_data: Some(meta_data()),
+        ..Default::default()
+    };
+
+    assert!(validate(&phenopacket).is_ok());
+}
+
+#[test]
+fn curie_without_declared_resource() {
+    let phenopacket = Phenopacket {
+        diseases: vec![Disease {
+            term: Some(oc("ORPHA:536", "Systemic lupus erythematosus")),
+            ..Default::default()
+        }],
+        meta_data: Some(meta_data()),
+        ..Default::default()
+    };
+
+    let issues = validate(&phenopacket).expect_err("the undeclared prefix should be reported");
+
+    assert_eq!(
+        issues,
+        vec![ValidationIssue::UndeclaredPrefix {
+            path: "diseases[0].term".into(),
+            prefix: "ORPHA".into(),
+        }]
+    );
+    assert_eq!(issues[0].severity(), Severity::Warning);
+}
+
+#[test]
+fn external_reference_ids_need_no_resource() {
+    let phenopacket = Phenopacket {
+        phenotypic_features: vec![PhenotypicFeature {
+            evidence: vec![Evidence::builder()
+                .tas()
+                .reference(ExternalReference::builder().id("PMID:30808312"))
+                .build()],
+            ..seizure()
+        }],
+        meta_data: Some(MetaData {
+            resources: vec![
+                Resource::builder().hpo("2024-08-13").build(),
+                Resource::builder().eco("2024-07-19").build(),
+            ],
+            external_references: vec![ExternalReference::builder()
+                .id("https://example.org/cohort")
+                .build()],
+            ..meta_data()
+        }),
+        ..Default::default()
+    };
+
+    assert!(validate(&phenopacket).is_ok());
+}